@@ -0,0 +1,42 @@
+use rustyline::{Context, Helper, completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator};
+
+/// Completes the word under the cursor from the commands and legal moves of the current position
+#[derive(Default)]
+pub struct MoveCompleter {
+	pub candidates: Vec<String>,
+}
+
+impl MoveCompleter {
+	/// Start of the word under the cursor and the candidates it is a prefix of
+	pub fn candidates_for(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+		let start = line[..pos].rfind(char::is_whitespace).map_or(0, |index| index + 1);
+		let prefix = &line[start..pos];
+
+		let mut matches: Vec<String> = self.candidates.iter().filter(|candidate| candidate.starts_with(prefix)).cloned().collect();
+		matches.sort();
+		matches.dedup();
+
+		(start, matches)
+	}
+}
+
+impl Completer for MoveCompleter {
+	type Candidate = String;
+
+	fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+		Ok(self.candidates_for(line, pos))
+	}
+}
+
+impl Hinter for MoveCompleter {
+	type Hint = String;
+}
+
+impl Highlighter for MoveCompleter {}
+
+impl Validator for MoveCompleter {}
+
+impl Helper for MoveCompleter {}
+
+#[cfg(test)]
+mod tests;
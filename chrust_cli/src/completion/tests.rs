@@ -0,0 +1,24 @@
+use super::*;
+
+fn completer() -> MoveCompleter {
+	MoveCompleter {
+		candidates: ["Nf3", "Nc3", "e4", "e3", "e2e4", "undo", "new"].map(str::to_string).to_vec(),
+	}
+}
+
+#[test]
+fn completes_the_word_under_the_cursor() {
+	assert_eq!(completer().candidates_for("N", 1), (0, vec!["Nc3".to_string(), "Nf3".to_string()]));
+	assert_eq!(completer().candidates_for("e", 1), (0, vec!["e2e4".to_string(), "e3".to_string(), "e4".to_string()]));
+	assert_eq!(completer().candidates_for("un", 2), (0, vec!["undo".to_string()]));
+}
+
+#[test]
+fn completes_after_earlier_words() {
+	assert_eq!(completer().candidates_for("new  Nf", 7), (5, vec!["Nf3".to_string()]));
+}
+
+#[test]
+fn unknown_prefix_has_no_candidates() {
+	assert_eq!(completer().candidates_for("Qh", 2), (0, Vec::new()));
+}
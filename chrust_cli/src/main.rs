@@ -0,0 +1,162 @@
+mod completion;
+mod session;
+
+use std::{process::ExitCode, time::Duration};
+
+use chrust_core::{Side, position::STARTING_FEN, search::SearchOptions, time_manager::SearchLimits};
+use rustyline::{Config, Editor, error::ReadlineError, history::DefaultHistory};
+
+use crate::{
+	completion::MoveCompleter,
+	session::{Command, Response, Session, completion_candidates},
+};
+
+const USAGE: &str = "Usage: chrust_cli [--engine white|black] [--fen <fen>] [--movetime <ms> | --depth <n>] [--threads <n>] [--hash <mb>] [--ascii] [--mate <n>]
+
+Plays a game in the terminal, against another player at the same keyboard or against the engine.
+Type help in the game for the commands. With --mate the position is solved as a mate in n problem instead.";
+
+struct Args {
+	fen: String,
+	engine_side: Option<Side>,
+	search_options: SearchOptions,
+	limits: SearchLimits,
+	unicode: bool,
+	/// Solve for mate in this many moves instead of playing
+	mate: Option<u32>,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run(args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let mut fen = STARTING_FEN.to_string();
+	let mut engine_side = None;
+	let mut search_options = SearchOptions::default();
+	let mut limits = SearchLimits {
+		movetime: Some(Duration::from_millis(1_000)),
+		..Default::default()
+	};
+	let mut unicode = true;
+	let mut mate = None;
+
+	while let Some(flag) = args.next() {
+		let mut value = || args.next().ok_or(format!("Missing value for {}", flag));
+
+		match flag.as_str() {
+			"--fen" => fen = value()?,
+			"--engine" => {
+				engine_side = match value()?.as_str() {
+					"white" => Some(Side::White),
+					"black" => Some(Side::Black),
+					"none" => None,
+					other => return Err(format!("Invalid side {}", other)),
+				}
+			}
+			"--movetime" => {
+				limits = SearchLimits {
+					movetime: Some(Duration::from_millis(parse_number(&value()?)?)),
+					..Default::default()
+				}
+			}
+			"--depth" => {
+				limits = SearchLimits {
+					depth: Some(parse_number(&value()?)?),
+					..Default::default()
+				}
+			}
+			"--threads" => search_options.threads = parse_number(&value()?)?,
+			"--hash" => search_options.hash_size_mb = parse_number(&value()?)?,
+			"--ascii" => unicode = false,
+			"--mate" => mate = Some(parse_number(&value()?)?),
+			"--help" | "-h" => return Err(String::new()),
+			_ => return Err(format!("Unknown argument {}", flag)),
+		}
+	}
+
+	Ok(Args {
+		fen,
+		engine_side,
+		search_options,
+		limits,
+		unicode,
+		mate,
+	})
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid number {}", value))
+}
+
+fn run(args: Args) -> Result<(), String> {
+	let mut session = Session::new(&args.fen, args.unicode, args.engine_side, args.search_options, args.limits)?;
+	if let Some(moves) = args.mate {
+		println!("{}", session.solve_mate(moves)?);
+		return Ok(());
+	}
+
+	let mut editor: Editor<MoveCompleter, DefaultHistory> = Editor::with_config(Config::builder().auto_add_history(true).build()).map_err(|e| format!("Can't open the terminal: {}", e))?;
+	editor.set_helper(Some(MoveCompleter::default()));
+
+	print!("{}", session.board());
+	println!("{}", session.status());
+
+	loop {
+		if session.is_engine_to_move() {
+			match session.play_engine_move() {
+				Ok(message) => {
+					print!("\n{}", session.board());
+					println!("{}\n{}", message, session.status());
+				}
+				// Give the player a chance to undo or start over instead of looping on the error
+				Err(message) => {
+					println!("{}", message);
+					session.engine_side = None;
+				}
+			}
+			continue;
+		}
+
+		if let Some(helper) = editor.helper_mut() {
+			helper.candidates = completion_candidates(&session.game);
+		}
+
+		let prompt = format!("{}> ", session.game.position.side_to_move);
+		let line = match editor.readline(&prompt) {
+			Ok(line) => line,
+			Err(ReadlineError::Interrupted | ReadlineError::Eof) => return Ok(()),
+			Err(error) => return Err(format!("Can't read input: {}", error)),
+		};
+
+		let Some(command) = Command::parse(&line) else {
+			continue;
+		};
+
+		match session.execute(command) {
+			Ok(Response::Message(message)) => println!("{}", message),
+			Ok(Response::Board(message)) => {
+				print!("\n{}", session.board());
+				if !message.is_empty() {
+					println!("{}", message);
+				}
+			}
+			Ok(Response::Quit) => return Ok(()),
+			Err(message) => println!("{}", message),
+		}
+	}
+}
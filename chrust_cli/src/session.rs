@@ -0,0 +1,334 @@
+use chrust_core::{
+	Side,
+	game_status::GameStatus,
+	mate_solver::find_mate,
+	moves::make_move::Move,
+	position::{Game, Position},
+	search::{Search, SearchOptions, mate_in},
+	time_manager::SearchLimits,
+};
+
+pub const COMMANDS: [&str; 14] = [
+	"board", "draw", "fen", "flip", "go", "help", "mate", "moves", "new", "pgn", "quit", "redo", "resign", "undo",
+];
+
+pub const HELP: &str = "Enter moves in SAN (Nf3, exd5, O-O, e8=Q) or UCI (g1f3, e7e8q), tab completes legal moves.
+
+  board           show the board
+  flip            turn the board around
+  moves           list the legal moves
+  undo / redo     take back or replay a move, against the engine a full move
+  draw            offer a draw, or accept the draw your opponent offered
+  resign          resign the game
+  go              let the engine play the side to move
+  mate <n>        look for a forced mate in n moves
+  fen             print the position as FEN
+  pgn             print the game as PGN
+  new [fen]       start a new game
+  quit            leave";
+
+/// The engine only accepts a draw offer if it doesn't think it's better than this
+const DRAW_ACCEPT_SCORE: i32 = 0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+	Board,
+	Flip,
+	Moves,
+	Undo,
+	Redo,
+	Draw,
+	Resign,
+	Go,
+	Fen,
+	Pgn,
+	/// Solve for mate in this many moves, `None` if the number is missing or invalid
+	Mate(Option<u32>),
+	New(Option<String>),
+	Help,
+	Quit,
+	Move(String),
+}
+
+/// What the caller should do after a command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Response {
+	/// Text to print
+	Message(String),
+	/// The position changed, print the board followed by the text
+	Board(String),
+	Quit,
+}
+
+pub struct Session {
+	pub game: Game,
+	/// Show the board from blacks side
+	pub flipped: bool,
+	pub unicode: bool,
+	/// Side played by the engine, `None` for two players
+	pub engine_side: Option<Side>,
+	pub search: Search,
+	pub limits: SearchLimits,
+	start_fen: String,
+}
+
+impl Command {
+	pub fn parse(input: &str) -> Option<Command> {
+		let mut words = input.split_whitespace();
+		let first = words.next()?;
+		let rest: Vec<&str> = words.collect();
+
+		let command = match first {
+			"board" => Command::Board,
+			"flip" => Command::Flip,
+			"moves" => Command::Moves,
+			"undo" | "takeback" => Command::Undo,
+			"redo" => Command::Redo,
+			"draw" => Command::Draw,
+			"resign" => Command::Resign,
+			"go" => Command::Go,
+			"fen" => Command::Fen,
+			"pgn" => Command::Pgn,
+			"mate" => Command::Mate(rest.first().and_then(|moves| moves.parse().ok())),
+			"new" if rest.is_empty() => Command::New(None),
+			"new" => Command::New(Some(rest.join(" "))),
+			"help" | "?" => Command::Help,
+			"quit" | "exit" => Command::Quit,
+			_ => Command::Move(first.to_string()),
+		};
+
+		Some(command)
+	}
+}
+
+impl Session {
+	pub fn new(fen: &str, unicode: bool, engine_side: Option<Side>, search_options: SearchOptions, limits: SearchLimits) -> Result<Session, String> {
+		let game = Game::try_from_fen(fen).map_err(|e| format!("Invalid FEN {}: {}", fen, e))?;
+
+		Ok(Session {
+			game,
+			flipped: engine_side == Some(Side::White),
+			unicode,
+			engine_side,
+			search: Search::new(search_options),
+			limits,
+			start_fen: fen.to_string(),
+		})
+	}
+
+	pub fn board(&self) -> String {
+		let perspective = match self.flipped {
+			false => Side::White,
+			true => Side::Black,
+		};
+
+		self.game.position.board_to_string(perspective, self.unicode)
+	}
+
+	/// Whether the engine has to move next
+	pub fn is_engine_to_move(&self) -> bool {
+		self.game.is_legal_game_state() && self.engine_side == Some(self.game.position.side_to_move)
+	}
+
+	pub fn execute(&mut self, command: Command) -> Result<Response, String> {
+		match command {
+			Command::Board => Ok(Response::Board(self.status())),
+			Command::Flip => {
+				self.flipped = !self.flipped;
+				Ok(Response::Board(String::new()))
+			}
+			Command::Moves => Ok(Response::Message(legal_move_sans(&self.game).join(" "))),
+			Command::Undo => self.undo(),
+			Command::Redo => {
+				self.game.redo().map_err(|_| "Nothing to redo".to_string())?;
+				Ok(Response::Board(self.status()))
+			}
+			Command::Draw => self.draw(),
+			Command::Resign => {
+				let side = self.game.position.side_to_move;
+				self.game.resign(side).map_err(|_| "The game is already over".to_string())?;
+				Ok(Response::Message(self.status()))
+			}
+			Command::Go => {
+				if !self.game.is_legal_game_state() {
+					return Err("The game is already over".to_string());
+				}
+				self.engine_side = Some(self.game.position.side_to_move);
+				Ok(Response::Board(self.status()))
+			}
+			Command::Fen => self.game.position.export_position_to_fen().map(Response::Message).map_err(|e| format!("Can't export FEN: {}", e)),
+			Command::Pgn => self.game.export_pgn().map(Response::Message).map_err(|e| format!("Can't export PGN: {}", e)),
+			Command::Mate(moves) => {
+				let moves = moves.ok_or("Usage: mate <moves>".to_string())?;
+				self.solve_mate(moves).map(Response::Message)
+			}
+			Command::New(fen) => {
+				let fen = fen.unwrap_or(self.start_fen.clone());
+				self.game = Game::try_from_fen(&fen).map_err(|e| format!("Invalid FEN {}: {}", fen, e))?;
+				self.start_fen = fen;
+				self.search.new_game();
+				Ok(Response::Board(self.status()))
+			}
+			Command::Help => Ok(Response::Message(HELP.to_string())),
+			Command::Quit => Ok(Response::Quit),
+			Command::Move(text) => {
+				if !self.game.is_legal_game_state() {
+					return Err("The game is already over, use new or undo".to_string());
+				}
+				let mv = parse_move(&self.game, &text)?;
+				self.game.make_move(&mv).map_err(|_| format!("Illegal move {}", text))?;
+				Ok(Response::Board(self.status()))
+			}
+		}
+	}
+
+	/// Searches and plays the engine move. Returns the move in SAN, or that the engine took the draw.
+	pub fn play_engine_move(&mut self) -> Result<String, String> {
+		let result = self.game.search_best_move(&mut self.search, &self.limits).map_err(|e| format!("Search failed: {}", e))?;
+
+		if self.game.draw_offer == Some(self.game.position.side_to_move.opponent()) && result.score <= DRAW_ACCEPT_SCORE {
+			self.game.accept_draw().map_err(|e| format!("Can't accept the draw: {}", e))?;
+			return Ok("The engine accepts the draw".to_string());
+		}
+
+		let mv = result.best_move.ok_or("The engine has no move".to_string())?;
+		let san = self.game.position.move_to_san(&mv).map_err(|e| format!("Can't write the move: {}", e))?;
+		self.game.make_move(&mv).map_err(|e| format!("The engine played an illegal move: {}", e))?;
+
+		let evaluation = match mate_in(result.score) {
+			Some(moves) => format!("mate in {}", moves),
+			None => format!("{:+.2}", result.score as f64 / 100.0),
+		};
+		Ok(format!("The engine plays {} ({}, depth {})", san, evaluation, result.depth))
+	}
+
+	/// Solves the position as a mate in `moves` problem: the main line and every key move
+	pub fn solve_mate(&self, moves: u32) -> Result<String, String> {
+		let position = &self.game.position;
+		let Some(solution) = find_mate(position, moves).map_err(|e| format!("Can't search for mate: {}", e))? else {
+			return Ok(format!("No mate in {}", moves));
+		};
+
+		let keys = solution
+			.mating_moves
+			.iter()
+			.map(|mv| position.move_to_san(mv))
+			.collect::<Result<Vec<String>, _>>()
+			.map_err(|e| format!("Can't write the move: {}", e))?;
+		Ok(format!("Mate in {}: {}\nFirst moves that mate within {}: {}", solution.moves, line_to_san(position, &solution.main_line)?, moves, keys.join(" ")))
+	}
+
+	/// Side to move, check and the result once the game is over
+	pub fn status(&self) -> String {
+		let side = self.game.position.side_to_move;
+
+		match self.game.game_status {
+			GameStatus::Playing => format!("{} to move", side),
+			GameStatus::InCheck => format!("{} to move, check", side),
+			GameStatus::CheckmateForSide(winner) => format!("Checkmate, {} wins", winner),
+			GameStatus::ResignationForSide(winner) => format!("{} resigns, {} wins", winner.opponent(), winner),
+			GameStatus::TimeoutForSide(winner) => format!("{} wins on time", winner),
+			GameStatus::Stalemate => "Draw by stalemate".to_string(),
+			GameStatus::DrawByAgreement => "Draw by agreement".to_string(),
+			GameStatus::DrawByFiftyMoves => "Draw by the fifty move rule".to_string(),
+			GameStatus::DrawByRepetition => "Draw by threefold repetition".to_string(),
+			GameStatus::DrawByInsufficientMaterial => "Draw by insufficient material".to_string(),
+			GameStatus::DrawByTimeoutVsInsufficientMaterial => "Draw, time ran out against insufficient material".to_string(),
+		}
+	}
+
+	/// Against the engine moves are taken back until it's the players turn again
+	fn undo(&mut self) -> Result<Response, String> {
+		self.game.undo_last_move().map_err(|_| "Nothing to undo".to_string())?;
+
+		if self.engine_side == Some(self.game.position.side_to_move) && !self.game.move_history.is_empty() {
+			self.game.undo_last_move().map_err(|_| "Nothing to undo".to_string())?;
+		}
+
+		Ok(Response::Board(self.status()))
+	}
+
+	fn draw(&mut self) -> Result<Response, String> {
+		let side = self.game.position.side_to_move;
+
+		if self.game.draw_offer == Some(side.opponent()) {
+			self.game.accept_draw().map_err(|_| "The game is already over".to_string())?;
+			return Ok(Response::Message(self.status()));
+		}
+
+		self.game.offer_draw().map_err(|_| "The game is already over".to_string())?;
+		Ok(Response::Message(format!("{} offers a draw, make your move", side)))
+	}
+}
+
+/// Reads a move in SAN or UCI notation
+pub fn parse_move(game: &Game, text: &str) -> Result<Move, String> {
+	if let Ok(mv) = game.position.san_to_move(text) {
+		return Ok(mv);
+	}
+
+	let mut position = game.position;
+	let legal_moves = position.get_all_legal_moves_for_side(position.side_to_move).map_err(|e| format!("Can't generate moves: {}", e))?;
+	let text = text.to_ascii_lowercase();
+
+	if let Some(mv) = legal_moves.into_iter().find(|mv| mv.to_uci() == text) {
+		return Ok(mv);
+	}
+
+	match game.convert_uci_to_move(&text).ok().and_then(|mv| game.explain_illegal_move(&mv)) {
+		Some(reason) => Err(format!("Illegal move {}: {}", text, reason)),
+		None => Err(format!("Unknown command or illegal move {}, type help for the commands", text)),
+	}
+}
+
+/// `moves` played from `position` in SAN, numbered from 1 like problem solutions
+fn line_to_san(position: &Position, moves: &[Move]) -> Result<String, String> {
+	let mut position = *position;
+	let mut words = Vec::new();
+	let offset = usize::from(position.side_to_move == Side::Black);
+
+	for (index, mv) in moves.iter().enumerate() {
+		let number = (index + offset) / 2 + 1;
+		match position.side_to_move {
+			Side::White => words.push(format!("{}.", number)),
+			Side::Black if index == 0 => words.push(format!("{}...", number)),
+			Side::Black => {}
+		}
+
+		words.push(position.move_to_san(mv).map_err(|e| format!("Can't write the move: {}", e))?);
+		position.make_move_unvalidated(*mv).map_err(|e| format!("Can't play the move: {}", e))?;
+	}
+
+	Ok(words.join(" "))
+}
+
+/// Legal moves of the side to move in SAN
+pub fn legal_move_sans(game: &Game) -> Vec<String> {
+	let mut position = game.position;
+	if !game.is_legal_game_state() {
+		return Vec::new();
+	}
+
+	let legal_moves = position.get_all_legal_moves_for_side(position.side_to_move).unwrap_or_default();
+	let mut sans: Vec<String> = legal_moves.iter().filter_map(|mv| game.position.move_to_san(mv).ok()).collect();
+	sans.sort();
+
+	sans
+}
+
+/// Everything tab completion offers: commands, and the legal moves in SAN and UCI
+pub fn completion_candidates(game: &Game) -> Vec<String> {
+	let mut candidates: Vec<String> = COMMANDS.iter().map(|command| command.to_string()).collect();
+	candidates.extend(legal_move_sans(game));
+
+	if game.is_legal_game_state() {
+		let mut position = game.position;
+		let legal_moves = position.get_all_legal_moves_for_side(position.side_to_move).unwrap_or_default();
+		candidates.extend(legal_moves.iter().map(Move::to_uci));
+	}
+
+	candidates
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,180 @@
+use chrust_core::position::STARTING_FEN;
+
+use super::*;
+
+fn two_players(fen: &str) -> Session {
+	Session::new(fen, false, None, SearchOptions::default(), SearchLimits::default()).unwrap()
+}
+
+fn against_engine(fen: &str, engine_side: Side) -> Session {
+	let limits = SearchLimits { depth: Some(2), ..Default::default() };
+	let options = SearchOptions { hash_size_mb: 1, ..Default::default() };
+
+	Session::new(fen, false, Some(engine_side), options, limits).unwrap()
+}
+
+fn run(session: &mut Session, input: &str) -> Result<Response, String> {
+	session.execute(Command::parse(input).unwrap())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Command parsing tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn commands_are_parsed() {
+	assert_eq!(Command::parse("  undo "), Some(Command::Undo));
+	assert_eq!(Command::parse("new"), Some(Command::New(None)));
+	assert_eq!(Command::parse("new 4k3/8/8/8/8/8/8/4K3 w - - 0 1"), Some(Command::New(Some("4k3/8/8/8/8/8/8/4K3 w - - 0 1".to_string()))));
+	assert_eq!(Command::parse("mate 3"), Some(Command::Mate(Some(3))));
+	assert_eq!(Command::parse("mate three"), Some(Command::Mate(None)));
+	assert_eq!(Command::parse("Nf3"), Some(Command::Move("Nf3".to_string())));
+	assert_eq!(Command::parse("   "), None);
+}
+
+#[test]
+fn moves_are_read_as_san_or_uci() {
+	let game = Game::try_from_fen(STARTING_FEN).unwrap();
+
+	assert_eq!(parse_move(&game, "Nf3").unwrap().to_uci(), "g1f3");
+	assert_eq!(parse_move(&game, "g1f3").unwrap().to_uci(), "g1f3");
+	assert_eq!(parse_move(&game, "E2E4").unwrap().to_uci(), "e2e4");
+	assert!(parse_move(&game, "Nf6").is_err());
+	assert!(parse_move(&game, "e2e5").is_err());
+}
+
+#[test]
+fn illegal_uci_moves_are_explained() {
+	let game = Game::try_from_fen(STARTING_FEN).unwrap();
+
+	assert_eq!(parse_move(&game, "f1b5"), Err("Illegal move f1b5: the way is blocked on e2".to_string()));
+	assert_eq!(parse_move(&game, "e7e5"), Err("Illegal move e7e5: it's White to move, that is a Black piece".to_string()));
+}
+
+#[test]
+fn completion_offers_commands_san_and_uci() {
+	let game = Game::try_from_fen(STARTING_FEN).unwrap();
+	let candidates = completion_candidates(&game);
+
+	assert_eq!(candidates.len(), COMMANDS.len() + 2 * 20);
+	assert!(candidates.contains(&"Nf3".to_string()));
+	assert!(candidates.contains(&"g1f3".to_string()));
+	assert!(candidates.contains(&"resign".to_string()));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Two player tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn moves_are_played_and_taken_back() {
+	let mut session = two_players(STARTING_FEN);
+
+	assert_eq!(run(&mut session, "e4"), Ok(Response::Board("Black to move".to_string())));
+	assert_eq!(run(&mut session, "e7e5"), Ok(Response::Board("White to move".to_string())));
+	assert!(run(&mut session, "Ke3").is_err());
+
+	run(&mut session, "undo").unwrap();
+	assert_eq!(session.game.move_history.len(), 1);
+	run(&mut session, "redo").unwrap();
+	assert_eq!(session.game.move_history.len(), 2);
+}
+
+#[test]
+fn draw_is_offered_and_accepted() {
+	let mut session = two_players(STARTING_FEN);
+
+	assert_eq!(run(&mut session, "draw"), Ok(Response::Message("White offers a draw, make your move".to_string())));
+	run(&mut session, "d4").unwrap();
+	assert_eq!(run(&mut session, "draw"), Ok(Response::Message("Draw by agreement".to_string())));
+	assert!(run(&mut session, "d5").is_err());
+}
+
+#[test]
+fn resigning_ends_the_game() {
+	let mut session = two_players(STARTING_FEN);
+	run(&mut session, "e4").unwrap();
+
+	assert_eq!(run(&mut session, "resign"), Ok(Response::Message("Black resigns, White wins".to_string())));
+	assert!(matches!(run(&mut session, "pgn"), Ok(Response::Message(pgn)) if pgn.contains("1. e4 1-0")));
+}
+
+#[test]
+fn new_game_starts_from_the_given_fen() {
+	let mut session = two_players(STARTING_FEN);
+	run(&mut session, "e4").unwrap();
+
+	run(&mut session, "new 4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+	assert_eq!(run(&mut session, "fen"), Ok(Response::Message("4k3/8/8/8/8/8/8/4K2R w K - 0 1".to_string())));
+	run(&mut session, "O-O").unwrap();
+	run(&mut session, "new").unwrap();
+	assert!(session.game.move_history.is_empty());
+	assert_eq!(session.game.position.export_position_to_fen().unwrap(), "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+}
+
+#[test]
+fn flip_turns_the_board() {
+	let mut session = two_players(STARTING_FEN);
+
+	assert!(session.board().ends_with("a b c d e f g h\n"));
+	run(&mut session, "flip").unwrap();
+	assert!(session.board().ends_with("h g f e d c b a\n"));
+}
+
+#[test]
+fn mate_problems_are_solved() {
+	let mut session = two_players("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+
+	assert_eq!(run(&mut session, "mate 1"), Ok(Response::Message("No mate in 1".to_string())));
+	assert!(matches!(run(&mut session, "mate 2"), Ok(Response::Message(message)) if message.starts_with("Mate in 2: 1. K") && message.contains("2. Rh8#")));
+	assert!(run(&mut session, "mate").is_err());
+
+	let session = two_players("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1");
+	assert_eq!(session.solve_mate(1), Ok("Mate in 1: 1... Ra1#\nFirst moves that mate within 1: Ra1#".to_string()));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Engine tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn engine_answers_and_undo_takes_back_a_full_move() {
+	let mut session = against_engine(STARTING_FEN, Side::Black);
+	assert!(!session.flipped);
+
+	run(&mut session, "e4").unwrap();
+	assert!(session.is_engine_to_move());
+	assert!(session.play_engine_move().unwrap().starts_with("The engine plays "));
+	assert!(!session.is_engine_to_move());
+
+	run(&mut session, "undo").unwrap();
+	assert!(session.game.move_history.is_empty());
+}
+
+#[test]
+fn engine_finds_mate() {
+	let mut session = against_engine("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", Side::White);
+	assert!(session.flipped);
+
+	assert_eq!(session.play_engine_move(), Ok("The engine plays Ra8# (mate in 1, depth 2)".to_string()));
+	assert_eq!(session.status(), "Checkmate, White wins");
+	assert!(!session.is_engine_to_move());
+}
+
+#[test]
+fn engine_accepts_a_draw_when_worse() {
+	let mut session = against_engine("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", Side::Black);
+	run(&mut session, "draw").unwrap();
+	run(&mut session, "Ra7").unwrap();
+	assert!(session.play_engine_move().unwrap().starts_with("The engine accepts"));
+	assert!(!session.game.is_legal_game_state());
+}
+
+#[test]
+fn go_hands_the_side_to_move_to_the_engine() {
+	let mut session = two_players(STARTING_FEN);
+
+	run(&mut session, "go").unwrap();
+	assert!(session.is_engine_to_move());
+	assert_eq!(session.engine_side, Some(Side::White));
+}
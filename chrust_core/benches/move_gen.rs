@@ -0,0 +1,80 @@
+mod common;
+
+use std::hint::black_box;
+
+use chrust_core::position::{Game, load_position_from_fen};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use crate::common::POSITIONS;
+
+fn legal_moves(c: &mut Criterion) {
+	let mut group = c.benchmark_group("get_all_legal_moves_for_side");
+	for (name, fen) in POSITIONS {
+		let position = load_position_from_fen(fen).unwrap();
+		group.bench_with_input(BenchmarkId::from_parameter(name), &position, |b, position| {
+			b.iter(|| {
+				let mut position = *position;
+				black_box(position.get_all_legal_moves_for_side(position.side_to_move).unwrap())
+			})
+		});
+	}
+	group.finish();
+}
+
+/// Plays and takes back every legal move of the position
+fn make_undo(c: &mut Criterion) {
+	let mut group = c.benchmark_group("make_move_unvalidated_undo_move");
+	for (name, fen) in POSITIONS {
+		let mut position = load_position_from_fen(fen).unwrap();
+		let moves = position.get_all_legal_moves_for_side(position.side_to_move).unwrap();
+		group.bench_with_input(BenchmarkId::from_parameter(name), &moves, |b, moves| {
+			b.iter(|| {
+				for &mv in moves {
+					let undo = position.make_move_unvalidated(mv).unwrap();
+					position.undo_move(undo, mv).unwrap();
+				}
+				black_box(&position);
+			})
+		});
+	}
+	group.finish();
+}
+
+/// Asks for attackers of every square by the side to move
+fn square_attacked(c: &mut Criterion) {
+	let mut group = c.benchmark_group("is_square_attacked");
+	for (name, fen) in POSITIONS {
+		let position = load_position_from_fen(fen).unwrap();
+		group.bench_with_input(BenchmarkId::from_parameter(name), &position, |b, position| {
+			b.iter(|| {
+				for square in 0..64 {
+					black_box(position.is_square_attacked(square, position.side_to_move).unwrap());
+				}
+			})
+		});
+	}
+	group.finish();
+}
+
+fn game_status(c: &mut Criterion) {
+	let mut group = c.benchmark_group("update_game_status");
+	for (name, fen) in POSITIONS {
+		let mut game = Game::try_from_fen(fen).unwrap();
+		group.bench_function(BenchmarkId::from_parameter(name), |b| b.iter(|| black_box(&mut game).update_game_status().unwrap()));
+	}
+	group.finish();
+}
+
+fn fen(c: &mut Criterion) {
+	let mut group = c.benchmark_group("fen");
+	for (name, fen) in POSITIONS {
+		group.bench_with_input(BenchmarkId::new("parse", name), fen, |b, fen| b.iter(|| black_box(load_position_from_fen(black_box(fen)).unwrap())));
+
+		let position = load_position_from_fen(fen).unwrap();
+		group.bench_with_input(BenchmarkId::new("export", name), &position, |b, position| b.iter(|| black_box(position.export_position_to_fen().unwrap())));
+	}
+	group.finish();
+}
+
+criterion_group!(benches, legal_moves, make_undo, square_attacked, game_status, fen);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+mod common;
+
+use std::hint::black_box;
+
+use chrust_core::{perft::PerftOptions, position::load_position_from_fen};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use crate::common::POSITIONS;
+
+/// Fixed depths keep the runs comparable between commits, deeper trees only make them slower
+const DEPTHS: [u8; 2] = [2, 3];
+
+fn perft(c: &mut Criterion) {
+	let mut group = c.benchmark_group("perft");
+	group.sample_size(10);
+	for (name, fen) in POSITIONS {
+		let position = load_position_from_fen(fen).unwrap();
+		for depth in DEPTHS {
+			group.bench_with_input(BenchmarkId::new(name, depth), &depth, |b, &depth| b.iter(|| black_box(position.perft(depth).unwrap())));
+		}
+	}
+	group.finish();
+}
+
+/// Divide with several threads and a hash table, as `chrust_perft` runs it
+fn perft_threads_hash(c: &mut Criterion) {
+	let options = PerftOptions { threads: 4, hash_size_mb: 16 };
+	let position = load_position_from_fen(POSITIONS[1].1).unwrap();
+
+	let mut group = c.benchmark_group("perft_divide");
+	group.sample_size(10);
+	group.bench_function("kiwipete/4_threads_16_mb", |b| b.iter(|| black_box(position.divide(4, &options).unwrap())));
+	group.finish();
+}
+
+criterion_group!(benches, perft, perft_threads_hash);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+#![no_main]
+
+use chrust_core::position::load_position_from_fen;
+use libfuzzer_sys::fuzz_target;
+
+// Any text either fails to parse or gives a position that survives an export and import
+fuzz_target!(|fen: &str| {
+	let Ok(position) = load_position_from_fen(fen) else {
+		return;
+	};
+
+	let exported = position.export_position_to_fen().expect("parsed position can't be exported");
+	let reloaded = load_position_from_fen(&exported).expect("exported FEN can't be parsed");
+	assert_eq!(reloaded.board, position.board);
+	assert_eq!(reloaded.side_to_move, position.side_to_move);
+});
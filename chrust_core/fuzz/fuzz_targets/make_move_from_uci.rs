@@ -0,0 +1,38 @@
+#![no_main]
+
+use chrust_core::position::{Game, STARTING_FEN};
+use libfuzzer_sys::fuzz_target;
+
+/// Legal positions with castling, en passant and promotions available
+const FENS: [&str; 4] = [
+	STARTING_FEN,
+	"r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+	"8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+	"r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+];
+
+// The first byte picks the position, the rest is whitespace separated UCI moves. Rejected moves
+// must leave the game untouched, accepted ones must be undoable.
+fuzz_target!(|data: &[u8]| {
+	let Some((&index, moves)) = data.split_first() else {
+		return;
+	};
+	let Ok(moves) = std::str::from_utf8(moves) else {
+		return;
+	};
+
+	let mut game = Game::try_from_fen(FENS[index as usize % FENS.len()]).unwrap();
+	let start = game.position;
+
+	for uci in moves.split_whitespace() {
+		let before = game.position;
+		if game.make_move_from_uci(uci).is_err() {
+			assert_eq!(game.position, before, "rejected move {} changed the position", uci);
+		}
+	}
+
+	while !game.move_history.is_empty() {
+		game.undo_last_move().unwrap();
+	}
+	assert_eq!(game.position, start);
+});
@@ -0,0 +1,178 @@
+//! Which pieces attack which squares, and what follows from that for the pieces on the board.
+
+use crate::{ColoredPiece, Piece, Side, Square, evaluation::piece_value, position::Position};
+
+/// The attackers of one side for every square, empty or not. Defending an own piece counts as
+/// attacking its square.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttackMap {
+	pub side: Side,
+	attackers: [Vec<Square>; 64],
+}
+
+/// A piece that can't leave the line between an enemy slider and its king
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Pin {
+	pub pinned: Square,
+	pub pinner: Square,
+}
+
+/// A piece whose move uncovers an attack of an own slider on an enemy piece
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredAttack {
+	pub piece: Square,
+	pub attacker: Square,
+	pub target: Square,
+}
+
+impl AttackMap {
+	/// Squares of the pieces attacking `square`
+	pub fn attackers(&self, square: Square) -> &[Square] {
+		&self.attackers[square as usize]
+	}
+
+	pub fn count(&self, square: Square) -> usize {
+		self.attackers[square as usize].len()
+	}
+
+	pub fn is_attacked(&self, square: Square) -> bool {
+		!self.attackers[square as usize].is_empty()
+	}
+
+	/// Number of attackers per square, a1 first
+	pub fn counts(&self) -> [usize; 64] {
+		std::array::from_fn(|square| self.attackers[square].len())
+	}
+}
+
+impl Position {
+	pub fn attack_map(&self, side: Side) -> AttackMap {
+		AttackMap {
+			side,
+			attackers: std::array::from_fn(|square| self.is_square_attacked(square as Square, side).ok().flatten().unwrap_or_default()),
+		}
+	}
+
+	/// Pieces of `side` other than the king that are attacked by an own piece
+	pub fn defended_pieces(&self, side: Side) -> Vec<Square> {
+		let defenders = self.attack_map(side);
+
+		self.pieces_of(side).filter(|&square| defenders.is_attacked(square)).collect()
+	}
+
+	/// Pieces of `side` other than the king that no own piece attacks
+	pub fn undefended_pieces(&self, side: Side) -> Vec<Square> {
+		let defenders = self.attack_map(side);
+
+		self.pieces_of(side).filter(|&square| !defenders.is_attacked(square)).collect()
+	}
+
+	/// Pieces of `side` other than the king that the opponent can win: attacked and undefended, or
+	/// attacked by a less valuable piece
+	pub fn hanging_pieces(&self, side: Side) -> Vec<Square> {
+		let defenders = self.attack_map(side);
+		let attackers = self.attack_map(side.opponent());
+
+		self
+			.pieces_of(side)
+			.filter(|&square| {
+				let value = self.board[square as usize].map_or(0, |piece| piece_value(piece.piece));
+				// The king can only take pieces nobody defends
+				let cheapest_attacker = attackers
+					.attackers(square)
+					.iter()
+					.filter_map(|&attacker| self.board[attacker as usize])
+					.map(|piece| {
+						if piece.piece == Piece::King {
+							i32::MAX
+						} else {
+							piece_value(piece.piece)
+						}
+					})
+					.min();
+
+				match cheapest_attacker {
+					None => false,
+					Some(attacker_value) => !defenders.is_attacked(square) || attacker_value < value,
+				}
+			})
+			.collect()
+	}
+
+	/// Pieces of `side` pinned to their king and the enemy sliders pinning them
+	pub fn pinned_pieces(&self, side: Side) -> Vec<Pin> {
+		self.blocked_lines(self.king_square(side), side, side.opponent()).into_iter().map(|(pinned, pinner)| Pin { pinned, pinner }).collect()
+	}
+
+	/// Pieces of `side` standing between an own slider and an enemy piece, moving them away attacks it.
+	/// A target on the enemy king is a discovered check.
+	pub fn discovered_attack_candidates(&self, side: Side) -> Vec<DiscoveredAttack> {
+		let mut candidates = Vec::new();
+
+		for target in self.pieces_of(side.opponent()).chain([self.king_square(side.opponent())]) {
+			for (piece, attacker) in self.blocked_lines(target, side, side) {
+				candidates.push(DiscoveredAttack { piece, attacker, target });
+			}
+		}
+
+		candidates
+	}
+
+	/// Looks from `target` along every line for a piece of `blocker_side` followed by a slider of
+	/// `slider_side` that moves along that line. Returns the blocker and slider squares.
+	fn blocked_lines(&self, target: Square, blocker_side: Side, slider_side: Side) -> Vec<(Square, Square)> {
+		let mut lines = Vec::new();
+
+		for direction in [-8, 8, -1, 1, -7, 7, -9, 9] {
+			let is_rook_ray = direction == 8 || direction == -8 || direction == 1 || direction == -1;
+			let mut blocker = None;
+
+			self.slide_ray(target, direction, |square, occupant| {
+				let Some(occupant) = occupant else {
+					return true;
+				};
+
+				match blocker {
+					None if occupant.side == blocker_side => {
+						blocker = Some(square);
+						true
+					}
+					Some(blocker) => {
+						if occupant.side == slider_side && moves_along(occupant, is_rook_ray) {
+							lines.push((blocker, square));
+						}
+						false
+					}
+					None => false,
+				}
+			});
+		}
+
+		lines
+	}
+
+	/// Squares of the pieces of `side` except the king
+	fn pieces_of(&self, side: Side) -> impl Iterator<Item = Square> + '_ {
+		(0..64).filter(move |&square| self.board[square as usize].is_some_and(|piece| piece.side == side && piece.piece != Piece::King))
+	}
+
+	pub(crate) fn king_square(&self, side: Side) -> Square {
+		match side {
+			Side::White => self.king_squares[0],
+			Side::Black => self.king_squares[1],
+		}
+	}
+}
+
+/// Whether `piece` slides along a rank or file when `is_rook_ray`, along a diagonal otherwise
+pub(crate) fn moves_along(piece: ColoredPiece, is_rook_ray: bool) -> bool {
+	match piece.piece {
+		Piece::Queen => true,
+		Piece::Rook => is_rook_ray,
+		Piece::Bishop => !is_rook_ray,
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests;
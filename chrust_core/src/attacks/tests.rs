@@ -0,0 +1,105 @@
+use crate::{
+	Side,
+	attacks::{DiscoveredAttack, Pin},
+	position::{Position, STARTING_FEN, load_position_from_fen},
+};
+
+fn position(fen: &str) -> Position {
+	load_position_from_fen(fen).unwrap()
+}
+
+fn sorted(squares: &[u8]) -> Vec<u8> {
+	let mut squares = squares.to_vec();
+	squares.sort();
+	squares
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Attack map
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn attack_map_lists_every_attacker() {
+	let map = position(STARTING_FEN).attack_map(Side::White);
+
+	assert_eq!(map.side, Side::White);
+	// e3 by the d and f pawns, f3 by the e and g pawns and the knight
+	assert_eq!(sorted(map.attackers(20)), vec![11, 13]);
+	assert_eq!(sorted(map.attackers(21)), vec![6, 12, 14]);
+	// e2 by king, queen, bishop and knight
+	assert_eq!(sorted(map.attackers(12)), vec![3, 4, 5, 6]);
+	assert!(!map.is_attacked(28));
+	assert_eq!(map.counts()[12], map.count(12));
+}
+
+#[test]
+fn attack_maps_of_both_sides_mirror_in_the_starting_position() {
+	let position = position(STARTING_FEN);
+	let white = position.attack_map(Side::White).counts();
+	let black = position.attack_map(Side::Black).counts();
+
+	for square in 0..64 {
+		assert_eq!(white[square], black[square ^ 56], "square {}", square);
+	}
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Defended and hanging pieces
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn only_the_corner_rooks_start_undefended() {
+	let position = position(STARTING_FEN);
+
+	assert_eq!(position.undefended_pieces(Side::White), vec![0, 7]);
+	assert_eq!(position.undefended_pieces(Side::Black), vec![56, 63]);
+	assert_eq!(position.defended_pieces(Side::White).len(), 13);
+}
+
+#[test]
+fn attacked_pieces_hang_without_defence_or_to_a_cheaper_attacker() {
+	// The knight attacks a rook, the pawn defending it doesn't help
+	assert_eq!(position("4k3/8/2n5/8/3R4/2P5/8/4K3 w - - 0 1").hanging_pieces(Side::White), vec![27]);
+	// A defended knight is safe from a rook, an undefended one isn't
+	assert!(position("3rk3/8/8/8/3N4/2P5/8/4K3 w - - 0 1").hanging_pieces(Side::White).is_empty());
+	assert_eq!(position("3rk3/8/8/8/3N4/8/8/4K3 w - - 0 1").hanging_pieces(Side::White), vec![27]);
+}
+
+#[test]
+fn the_king_only_wins_undefended_pieces() {
+	assert_eq!(position("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1").hanging_pieces(Side::Black), vec![11]);
+	assert!(position("3rk3/8/8/8/8/8/3p4/4K3 w - - 0 1").hanging_pieces(Side::Black).is_empty());
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Pins and discovered attacks
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn pins_name_the_pinned_piece_and_the_pinner() {
+	// Knight on e2 and pawn on d2 are pinned, the bishop on f1 is shielded by the knight on g1
+	let position = position("4k3/4r3/8/8/1b6/8/3PN3/4KBNr w - - 0 1");
+
+	assert_eq!(
+		position.pinned_pieces(Side::White),
+		vec![
+			Pin { pinned: 12, pinner: 52 },
+			Pin { pinned: 11, pinner: 25 }
+		]
+	);
+	assert!(position.pinned_pieces(Side::Black).is_empty());
+}
+
+#[test]
+fn discovered_attacks_include_discovered_checks() {
+	let position = position("4k3/8/8/8/8/3q4/2P1N3/1B2R1K1 w - - 0 1");
+
+	assert_eq!(
+		position.discovered_attack_candidates(Side::White),
+		vec![
+			DiscoveredAttack { piece: 10, attacker: 1, target: 19 },
+			DiscoveredAttack { piece: 12, attacker: 4, target: 60 }
+		]
+	);
+	assert!(position.discovered_attack_candidates(Side::Black).is_empty());
+}
@@ -0,0 +1,400 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
+	time::{Duration, Instant},
+};
+
+use crate::{Side, errors::ChessError, position::Game};
+
+/// Monotonic time used by the clock. Injected so tests can control time.
+pub trait TimeSource: Send {
+	fn now(&self) -> Duration;
+}
+
+pub struct SystemTimeSource {
+	start: Instant,
+}
+
+/// Time source that only moves when told to. Clones share the same time.
+#[derive(Clone, Default)]
+pub struct ManualTimeSource {
+	nanos: Arc<AtomicU64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimingMethod {
+	SuddenDeath,
+	/// Adds the increment after every move
+	Fischer {
+		increment: Duration,
+	},
+	/// Gives back the time used for a move, up to the delay
+	Bronstein {
+		delay: Duration,
+	},
+	/// The clock only starts counting down after the delay
+	SimpleDelay {
+		delay: Duration,
+	},
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimePeriod {
+	/// Moves to play in this period, `None` for the rest of the game
+	pub moves: Option<u32>,
+	pub time: Duration,
+	pub timing: TimingMethod,
+}
+
+/// One or more periods, e.g. 40 moves in 90 minutes followed by 30 minutes with a 30 second increment
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeControl {
+	pub periods: Vec<TimePeriod>,
+}
+
+pub struct ChessClock {
+	time_control: TimeControl,
+	remaining: [Duration; 2],
+	moves_made: [u32; 2],
+	period_index: [usize; 2],
+	turn: Option<(Side, Duration)>,
+	history: Vec<Duration>,
+	/// Times at the start of every ply since the clock was attached, so moves can be taken back and replayed
+	snapshots: Vec<ClockSnapshot>,
+	/// Plies played since the clock was attached
+	ply: usize,
+	/// Ply of the game when the clock was attached
+	first_ply: usize,
+	time_source: Box<dyn TimeSource>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ClockSnapshot {
+	remaining: [Duration; 2],
+	moves_made: [u32; 2],
+	period_index: [usize; 2],
+}
+
+impl SystemTimeSource {
+	pub fn new() -> SystemTimeSource {
+		SystemTimeSource { start: Instant::now() }
+	}
+}
+
+impl Default for SystemTimeSource {
+	fn default() -> Self {
+		SystemTimeSource::new()
+	}
+}
+
+impl TimeSource for SystemTimeSource {
+	fn now(&self) -> Duration {
+		self.start.elapsed()
+	}
+}
+
+impl ManualTimeSource {
+	pub fn advance(&self, duration: Duration) {
+		self.nanos.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+	}
+}
+
+impl TimeSource for ManualTimeSource {
+	fn now(&self) -> Duration {
+		Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+	}
+}
+
+impl TimeControl {
+	pub fn new(periods: Vec<TimePeriod>) -> TimeControl {
+		TimeControl { periods }
+	}
+
+	pub fn sudden_death(time: Duration) -> TimeControl {
+		TimeControl::single(time, TimingMethod::SuddenDeath)
+	}
+
+	pub fn fischer(time: Duration, increment: Duration) -> TimeControl {
+		TimeControl::single(time, TimingMethod::Fischer { increment })
+	}
+
+	pub fn bronstein(time: Duration, delay: Duration) -> TimeControl {
+		TimeControl::single(time, TimingMethod::Bronstein { delay })
+	}
+
+	pub fn simple_delay(time: Duration, delay: Duration) -> TimeControl {
+		TimeControl::single(time, TimingMethod::SimpleDelay { delay })
+	}
+
+	fn single(time: Duration, timing: TimingMethod) -> TimeControl {
+		TimeControl {
+			periods: vec![TimePeriod { moves: None, time, timing }],
+		}
+	}
+}
+
+impl ChessClock {
+	pub fn new(time_control: TimeControl, time_source: Box<dyn TimeSource>) -> ChessClock {
+		let start_time = time_control.periods.first().map(|p| p.time).unwrap_or_default();
+
+		let mut clock = ChessClock {
+			time_control,
+			remaining: [start_time; 2],
+			moves_made: [0; 2],
+			period_index: [0; 2],
+			turn: None,
+			history: Vec::new(),
+			snapshots: Vec::new(),
+			ply: 0,
+			first_ply: 0,
+			time_source,
+		};
+		clock.snapshots.push(clock.snapshot());
+		clock
+	}
+
+	/// Starts (or restarts) the clock of `side`
+	pub fn start(&mut self, side: Side) {
+		self.turn = Some((side, self.time_source.now()));
+	}
+
+	/// Stops the running clock and charges the time used so far
+	pub fn stop(&mut self) {
+		if let Some((side, _)) = self.turn {
+			let charged = self.charged_time(side);
+			self.remaining[side_index(side)] = self.remaining[side_index(side)].saturating_sub(charged);
+			self.turn = None;
+		}
+	}
+
+	pub fn running_side(&self) -> Option<Side> {
+		self.turn.map(|(side, _)| side)
+	}
+
+	pub fn remaining(&self, side: Side) -> Duration {
+		self.remaining[side_index(side)].saturating_sub(self.charged_time(side))
+	}
+
+	pub fn is_flagged(&self, side: Side) -> bool {
+		self.remaining(side).is_zero()
+	}
+
+	/// Time `side` gets back for every move in its current period: the increment, or the delay that
+	/// isn't charged
+	pub fn increment(&self, side: Side) -> Duration {
+		match self.current_period(side).map(|p| p.timing) {
+			Some(TimingMethod::Fischer { increment }) => increment,
+			Some(TimingMethod::Bronstein { delay } | TimingMethod::SimpleDelay { delay }) => delay,
+			_ => Duration::ZERO,
+		}
+	}
+
+	/// Moves `side` still has to play before its next period starts, `None` in a period that lasts
+	/// for the rest of the game
+	pub fn moves_to_go(&self, side: Side) -> Option<u32> {
+		let period_end = self.period_end(self.period_index[side_index(side)])?;
+
+		Some(period_end.saturating_sub(self.moves_made[side_index(side)]))
+	}
+
+	/// Remaining time of the moving side after each move
+	pub fn history(&self) -> &[Duration] {
+		&self.history[..self.ply]
+	}
+
+	/// Ends the turn of `side`: charges the time used, applies increment or delay, moves on to the
+	/// next period if needed and starts the opponents clock.
+	pub fn press(&mut self, side: Side) {
+		// A new move replaces the plies that were taken back
+		self.snapshots.truncate(self.ply + 1);
+		self.history.truncate(self.ply);
+
+		let index = side_index(side);
+		let elapsed = self.elapsed(side);
+		let timing = self.current_period(side).map(|p| p.timing).unwrap_or(TimingMethod::SuddenDeath);
+
+		self.remaining[index] = self.remaining[index].saturating_sub(self.charged_time(side));
+
+		if !self.remaining[index].is_zero() {
+			match timing {
+				TimingMethod::Fischer { increment } => self.remaining[index] += increment,
+				TimingMethod::Bronstein { delay } => self.remaining[index] += elapsed.min(delay),
+				_ => {}
+			}
+		}
+
+		self.moves_made[index] += 1;
+		let period_index = self.period_index[index];
+		let period_end = self.period_end(period_index);
+		if period_end.is_some_and(|end| self.moves_made[index] >= end) && period_index + 1 < self.time_control.periods.len() {
+			self.period_index[index] += 1;
+			self.remaining[index] += self.time_control.periods[period_index + 1].time;
+		}
+
+		self.history.push(self.remaining[index]);
+		self.ply += 1;
+		self.snapshots.push(self.snapshot());
+		self.start(side.opponent());
+	}
+
+	/// Sets the times back, or forward again, to the start of game ply `ply` and runs the clock of
+	/// `side`. Plies before the clock was attached get its starting times, later ones it didn't see
+	/// are ignored.
+	pub(crate) fn restore(&mut self, ply: usize, side: Side) {
+		let clock_ply = ply.saturating_sub(self.first_ply);
+		let Some(snapshot) = self.snapshots.get(clock_ply).copied() else {
+			return;
+		};
+
+		self.remaining = snapshot.remaining;
+		self.moves_made = snapshot.moves_made;
+		self.period_index = snapshot.period_index;
+		self.ply = clock_ply;
+		self.start(side);
+	}
+
+	/// Ply of the game when the clock was attached, `history` starts there
+	pub(crate) fn first_ply(&self) -> usize {
+		self.first_ply
+	}
+
+	fn snapshot(&self) -> ClockSnapshot {
+		ClockSnapshot {
+			remaining: self.remaining,
+			moves_made: self.moves_made,
+			period_index: self.period_index,
+		}
+	}
+
+	/// Moves made at the end of period `period_index`, counted from the start of the game
+	fn period_end(&self, period_index: usize) -> Option<u32> {
+		self.time_control.periods.get(..=period_index).and_then(|periods| periods.iter().map(|p| p.moves).sum())
+	}
+
+	fn current_period(&self, side: Side) -> Option<&TimePeriod> {
+		self.time_control.periods.get(self.period_index[side_index(side)])
+	}
+
+	fn elapsed(&self, side: Side) -> Duration {
+		match self.turn {
+			Some((running, started)) if running == side => self.time_source.now().saturating_sub(started),
+			_ => Duration::ZERO,
+		}
+	}
+
+	fn charged_time(&self, side: Side) -> Duration {
+		let elapsed = self.elapsed(side);
+
+		match self.current_period(side).map(|p| p.timing) {
+			Some(TimingMethod::SimpleDelay { delay }) => elapsed.saturating_sub(delay),
+			_ => elapsed,
+		}
+	}
+}
+
+impl Game {
+	/// Attaches a clock and starts it for the side to move
+	pub fn start_clock(&mut self, mut clock: ChessClock) {
+		clock.first_ply = self.move_history.len();
+		clock.start(self.position.side_to_move);
+		self.clock = Some(clock);
+	}
+
+	/// Ends the game if the side to move ran out of time. Returns whether the flag fell.
+	pub fn check_flag(&mut self) -> Result<bool, ChessError> {
+		let side = self.position.side_to_move;
+		let flagged = self.clock.as_ref().is_some_and(|clock| clock.is_flagged(side));
+
+		if flagged && self.is_legal_game_state() {
+			if let Some(clock) = self.clock.as_mut() {
+				clock.stop();
+			}
+			self.flag_fall(side)?;
+		}
+
+		Ok(flagged)
+	}
+}
+
+fn side_index(side: Side) -> usize {
+	match side {
+		Side::White => 0,
+		Side::Black => 1,
+	}
+}
+
+/// A clock is saved with its times and snapshots. The time source is not, a loaded clock runs on the
+/// system time and the side that was running continues from the moment it is loaded.
+#[cfg(feature = "serde")]
+mod clock_serde {
+	use std::time::Duration;
+
+	use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+	use super::{ChessClock, ClockSnapshot, SystemTimeSource, TimeControl, TimeSource};
+	use crate::Side;
+
+	#[derive(Serialize, Deserialize)]
+	struct SavedClock {
+		time_control: TimeControl,
+		remaining: [Duration; 2],
+		moves_made: [u32; 2],
+		period_index: [usize; 2],
+		running_side: Option<Side>,
+		history: Vec<Duration>,
+		snapshots: Vec<ClockSnapshot>,
+		ply: usize,
+		first_ply: usize,
+	}
+
+	impl Serialize for ChessClock {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			SavedClock {
+				time_control: self.time_control.clone(),
+				remaining: [self.remaining(Side::White), self.remaining(Side::Black)],
+				moves_made: self.moves_made,
+				period_index: self.period_index,
+				running_side: self.running_side(),
+				history: self.history.clone(),
+				snapshots: self.snapshots.clone(),
+				ply: self.ply,
+				first_ply: self.first_ply,
+			}
+			.serialize(serializer)
+		}
+	}
+
+	impl<'de> Deserialize<'de> for ChessClock {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ChessClock, D::Error> {
+			let saved = SavedClock::deserialize(deserializer)?;
+
+			// `press` keeps one snapshot more than history entries, `history` and `restore` index them by ply
+			if saved.snapshots.len() != saved.history.len() + 1 || saved.ply > saved.history.len() {
+				return Err(D::Error::custom("clock history and snapshots don't match"));
+			}
+
+			let time_source = SystemTimeSource::new();
+			Ok(ChessClock {
+				time_control: saved.time_control,
+				remaining: saved.remaining,
+				moves_made: saved.moves_made,
+				period_index: saved.period_index,
+				turn: saved.running_side.map(|side| (side, time_source.now())),
+				history: saved.history,
+				snapshots: saved.snapshots,
+				ply: saved.ply,
+				first_ply: saved.first_ply,
+				time_source: Box::new(time_source),
+			})
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
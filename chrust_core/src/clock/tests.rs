@@ -0,0 +1,436 @@
+use super::*;
+use crate::game_status::GameStatus;
+use crate::test_common::game_from_fen;
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+fn clock_with(time_control: TimeControl) -> (ChessClock, ManualTimeSource) {
+	let time = ManualTimeSource::default();
+	(ChessClock::new(time_control, Box::new(time.clone())), time)
+}
+
+fn secs(seconds: u64) -> Duration {
+	Duration::from_secs(seconds)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ChessClock tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn sudden_death_counts_down_running_side_only() {
+	let (mut clock, time) = clock_with(TimeControl::sudden_death(5 * MINUTE));
+	clock.start(Side::White);
+
+	time.advance(secs(10));
+
+	assert_eq!(clock.remaining(Side::White), secs(290));
+	assert_eq!(clock.remaining(Side::Black), secs(300));
+	assert_eq!(clock.running_side(), Some(Side::White));
+}
+
+#[test]
+fn press_switches_sides() {
+	let (mut clock, time) = clock_with(TimeControl::sudden_death(5 * MINUTE));
+	clock.start(Side::White);
+	time.advance(secs(10));
+
+	clock.press(Side::White);
+	time.advance(secs(20));
+
+	assert_eq!(clock.remaining(Side::White), secs(290));
+	assert_eq!(clock.remaining(Side::Black), secs(280));
+	assert_eq!(clock.running_side(), Some(Side::Black));
+	assert_eq!(clock.history(), [secs(290)]);
+}
+
+#[test]
+fn sudden_death_flag_falls() {
+	let (mut clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	clock.start(Side::White);
+
+	time.advance(secs(59));
+	assert!(!clock.is_flagged(Side::White));
+	time.advance(secs(1));
+
+	assert!(clock.is_flagged(Side::White));
+	assert!(!clock.is_flagged(Side::Black));
+}
+
+#[test]
+fn fischer_adds_increment_after_move() {
+	let (mut clock, time) = clock_with(TimeControl::fischer(3 * MINUTE, secs(2)));
+	clock.start(Side::White);
+	time.advance(secs(5));
+
+	clock.press(Side::White);
+
+	assert_eq!(clock.remaining(Side::White), secs(177));
+}
+
+#[test]
+fn bronstein_gives_back_used_time_up_to_delay() {
+	let (mut clock, time) = clock_with(TimeControl::bronstein(3 * MINUTE, secs(5)));
+	clock.start(Side::White);
+	time.advance(secs(3));
+	clock.press(Side::White);
+	time.advance(secs(8));
+	clock.press(Side::Black);
+
+	assert_eq!(clock.remaining(Side::White), secs(180), "3s used, 3s given back");
+	assert_eq!(clock.remaining(Side::Black), secs(177), "8s used, 5s given back");
+}
+
+#[test]
+fn simple_delay_does_not_count_during_delay() {
+	let (mut clock, time) = clock_with(TimeControl::simple_delay(3 * MINUTE, secs(5)));
+	clock.start(Side::White);
+
+	time.advance(secs(4));
+	assert_eq!(clock.remaining(Side::White), secs(180));
+	time.advance(secs(3));
+	assert_eq!(clock.remaining(Side::White), secs(178));
+
+	clock.press(Side::White);
+	assert_eq!(clock.remaining(Side::White), secs(178));
+}
+
+#[test]
+fn multi_period_adds_time_after_move_limit() {
+	// 40 moves in 90 minutes, then 30 minutes with a 30 second increment
+	let time_control = TimeControl::new(vec![
+		TimePeriod {
+			moves: Some(40),
+			time: 90 * MINUTE,
+			timing: TimingMethod::SuddenDeath,
+		},
+		TimePeriod {
+			moves: None,
+			time: 30 * MINUTE,
+			timing: TimingMethod::Fischer { increment: secs(30) },
+		},
+	]);
+	let (mut clock, time) = clock_with(time_control);
+	clock.start(Side::White);
+
+	for _ in 0..39 {
+		time.advance(secs(60));
+		clock.press(Side::White);
+		clock.press(Side::Black);
+	}
+	assert_eq!(clock.remaining(Side::White), secs(90 * 60 - 39 * 60));
+
+	time.advance(secs(60));
+	clock.press(Side::White);
+	assert_eq!(clock.remaining(Side::White), secs(50 * 60 + 30 * 60), "40th move adds the second period");
+	assert_eq!(clock.remaining(Side::Black), 90 * MINUTE);
+
+	clock.press(Side::Black);
+	time.advance(secs(60));
+	clock.press(Side::White);
+	assert_eq!(clock.remaining(Side::White), secs(79 * 60 + 30), "second period uses the increment");
+}
+
+#[test]
+fn moves_to_go_and_increment_follow_the_period() {
+	let time_control = TimeControl::new(vec![
+		TimePeriod {
+			moves: Some(40),
+			time: 90 * MINUTE,
+			timing: TimingMethod::SuddenDeath,
+		},
+		TimePeriod {
+			moves: None,
+			time: 30 * MINUTE,
+			timing: TimingMethod::Fischer { increment: secs(30) },
+		},
+	]);
+	let (mut clock, _time) = clock_with(time_control);
+	clock.start(Side::White);
+
+	assert_eq!(clock.moves_to_go(Side::White), Some(40));
+	assert_eq!(clock.increment(Side::White), Duration::ZERO);
+
+	for _ in 0..39 {
+		clock.press(Side::White);
+		clock.press(Side::Black);
+	}
+	clock.press(Side::White);
+
+	assert_eq!(clock.moves_to_go(Side::White), None);
+	assert_eq!(clock.increment(Side::White), secs(30));
+	assert_eq!(clock.moves_to_go(Side::Black), Some(1));
+	assert_eq!(clock.increment(Side::Black), Duration::ZERO);
+}
+
+#[test]
+fn delays_count_as_increment() {
+	assert_eq!(clock_with(TimeControl::bronstein(MINUTE, secs(3))).0.increment(Side::White), secs(3));
+	assert_eq!(clock_with(TimeControl::simple_delay(MINUTE, secs(5))).0.increment(Side::Black), secs(5));
+	assert_eq!(clock_with(TimeControl::sudden_death(MINUTE)).0.moves_to_go(Side::White), None);
+}
+
+#[test]
+fn stop_charges_time_and_pauses() {
+	let (mut clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	clock.start(Side::White);
+	time.advance(secs(10));
+
+	clock.stop();
+	time.advance(secs(10));
+
+	assert_eq!(clock.remaining(Side::White), secs(50));
+	assert_eq!(clock.running_side(), None);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Game clock tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn game_presses_clock_on_move() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::fischer(MINUTE, secs(1)));
+	game.start_clock(clock);
+
+	time.advance(secs(5));
+	game.make_move_from_uci("e2e4").unwrap();
+
+	let clock = game.clock.as_ref().unwrap();
+	assert_eq!(clock.remaining(Side::White), secs(56));
+	assert_eq!(clock.running_side(), Some(Side::Black));
+}
+
+#[test]
+fn game_flag_fall_ends_game() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+
+	time.advance(secs(61));
+
+	assert_eq!(game.make_move_from_uci("e2e4"), Err(ChessError::GameIsFinished));
+	assert!(game.game_status == GameStatus::TimeoutForSide(Side::Black));
+}
+
+#[test]
+fn game_flag_fall_vs_lone_king_is_draw() {
+	let mut game = game_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+	let (clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+
+	time.advance(secs(61));
+
+	assert!(game.check_flag().unwrap());
+	assert!(game.game_status == GameStatus::DrawByTimeoutVsInsufficientMaterial);
+}
+
+#[test]
+fn game_check_flag_without_clock() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+	assert!(!game.check_flag().unwrap());
+	assert!(game.game_status == GameStatus::Playing);
+}
+
+#[test]
+fn game_clock_stops_on_checkmate() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, _time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+
+	for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+
+	assert_eq!(game.clock.as_ref().unwrap().running_side(), None);
+}
+
+#[test]
+fn game_undo_drops_clock_history() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, _time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+	game.make_move_from_uci("e2e4").unwrap();
+	game.make_move_from_uci("e7e5").unwrap();
+
+	game.undo_last_move().unwrap();
+
+	assert_eq!(game.clock.as_ref().unwrap().history().len(), 1);
+}
+
+#[test]
+fn game_undo_restores_clock() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::fischer(MINUTE, secs(1)));
+	game.start_clock(clock);
+	time.advance(secs(5));
+	game.make_move_from_uci("e2e4").unwrap();
+	time.advance(secs(7));
+	game.make_move_from_uci("e7e5").unwrap();
+	time.advance(secs(3));
+
+	game.undo_last_move().unwrap();
+
+	let clock = game.clock.as_ref().unwrap();
+	assert_eq!(clock.remaining(Side::White), secs(56));
+	assert_eq!(clock.remaining(Side::Black), MINUTE);
+	assert_eq!(clock.running_side(), Some(Side::Black));
+
+	time.advance(secs(4));
+	game.make_move_from_uci("d7d5").unwrap();
+
+	let clock = game.clock.as_ref().unwrap();
+	assert_eq!(clock.remaining(Side::Black), secs(57));
+	assert_eq!(clock.history(), [secs(56), secs(57)]);
+}
+
+#[test]
+fn game_undo_restores_clock_period() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let time_control = TimeControl::new(vec![
+		TimePeriod {
+			moves: Some(1),
+			time: MINUTE,
+			timing: TimingMethod::SuddenDeath,
+		},
+		TimePeriod {
+			moves: None,
+			time: MINUTE,
+			timing: TimingMethod::SuddenDeath,
+		},
+	]);
+	let (clock, time) = clock_with(time_control);
+	game.start_clock(clock);
+
+	game.make_move_from_uci("e2e4").unwrap();
+	game.undo_last_move().unwrap();
+	time.advance(secs(10));
+	game.make_move_from_uci("d2d4").unwrap();
+
+	assert_eq!(game.clock.as_ref().unwrap().remaining(Side::White), secs(110), "the second period is added once");
+}
+
+#[test]
+fn game_undo_restarts_clock_after_checkmate() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, _time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+	for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+
+	game.undo_last_move().unwrap();
+
+	assert_eq!(game.clock.as_ref().unwrap().running_side(), Some(Side::Black));
+}
+
+#[test]
+fn game_undo_before_clock_started_keeps_starting_times() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	game.make_move_from_uci("e2e4").unwrap();
+	let (clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+	time.advance(secs(5));
+	game.make_move_from_uci("e7e5").unwrap();
+
+	game.undo_last_move().unwrap();
+	assert_eq!(game.clock.as_ref().unwrap().remaining(Side::Black), MINUTE);
+
+	game.undo_last_move().unwrap();
+	let clock = game.clock.as_ref().unwrap();
+	assert_eq!(clock.remaining(Side::Black), MINUTE);
+	assert_eq!(clock.running_side(), Some(Side::White));
+	assert!(clock.history().is_empty());
+}
+
+#[test]
+fn game_redo_restores_clock_without_pressing() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::fischer(MINUTE, secs(1)));
+	game.start_clock(clock);
+	time.advance(secs(5));
+	game.make_move_from_uci("e2e4").unwrap();
+	time.advance(secs(7));
+	game.make_move_from_uci("e7e5").unwrap();
+	game.undo_last_move().unwrap();
+	time.advance(secs(20));
+
+	game.redo().unwrap();
+
+	let clock = game.clock.as_ref().unwrap();
+	assert_eq!(clock.remaining(Side::Black), secs(54));
+	assert_eq!(clock.running_side(), Some(Side::White));
+	assert_eq!(clock.history(), [secs(56), secs(54)]);
+}
+
+#[test]
+fn game_goto_ply_ignores_flag() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::sudden_death(MINUTE));
+	game.start_clock(clock);
+	for uci in ["e2e4", "e7e5", "g1f3"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+	game.goto_ply(0).unwrap();
+	time.advance(secs(61));
+
+	game.goto_ply(3).unwrap();
+
+	assert_eq!(game.move_history.len(), 3);
+	assert!(game.game_status == GameStatus::Playing);
+	assert_eq!(game.clock.as_ref().unwrap().running_side(), Some(Side::Black));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// serde tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_timed_game_keeps_its_clock() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let (clock, time) = clock_with(TimeControl::fischer(MINUTE, secs(1)));
+	game.start_clock(clock);
+	for (uci, seconds) in [("e2e4", 5), ("e7e5", 10), ("g1f3", 20)] {
+		time.advance(secs(seconds));
+		game.make_move_from_uci(uci).unwrap();
+	}
+	time.advance(secs(3));
+
+	let json = serde_json::to_string(&game).unwrap();
+	let mut restored: Game = serde_json::from_str(&json).unwrap();
+	let clock = restored.clock.as_ref().unwrap();
+
+	assert_eq!(clock.running_side(), Some(Side::Black));
+	assert_eq!(clock.history(), [secs(56), secs(51), secs(37)]);
+	assert_eq!(clock.remaining(Side::White), secs(37));
+	// Black had used 3 seconds of its turn when the game was saved
+	assert!(clock.remaining(Side::Black) <= secs(48) && clock.remaining(Side::Black) > secs(47));
+
+	restored.undo_last_move().unwrap();
+	assert_eq!(restored.clock.as_ref().unwrap().remaining(Side::Black), secs(51));
+	assert_eq!(restored.clock.as_ref().unwrap().running_side(), Some(Side::White));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_clock_with_missing_snapshots_fails() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	game.start_clock(clock_with(TimeControl::sudden_death(MINUTE)).0);
+	game.make_move_from_uci("e2e4").unwrap();
+	let mut value = serde_json::to_value(&game).unwrap();
+	value["clock"]["snapshots"].as_array_mut().unwrap().pop();
+
+	assert!(serde_json::from_value::<Game>(value).is_err(), "a clock that can't be taken back to every ply must be rejected");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_game_saved_without_clock_loads_untimed() {
+	let game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let mut value = serde_json::to_value(&game).unwrap();
+	value.as_object_mut().unwrap().remove("clock");
+
+	assert!(serde_json::from_value::<Game>(value).unwrap().clock.is_none());
+}
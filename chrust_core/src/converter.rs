@@ -1,6 +1,6 @@
 use crate::{
 	ColoredPiece, Piece, Side, Square,
-	errors::{ChessError, FenError},
+	errors::{ChessError, FenErrorKind},
 	moves::{
 		make_move::{Move, MoveKind},
 		move_gen::king::get_file_and_rank_difference,
@@ -15,30 +15,30 @@ pub fn convert_square_to_string(square: u8) -> String {
 	format!("{}{}", file, rank)
 }
 
-pub fn convert_string_to_square(square_string: &str) -> Result<Square, FenError> {
+pub fn convert_string_to_square(square_string: &str) -> Result<Square, FenErrorKind> {
 	if square_string.len() != 2 {
-		return Err(FenError::SquareLenghtIsnt2Wide(square_string.len()));
+		return Err(FenErrorKind::SquareLenghtIsnt2Wide(square_string.len()));
 	}
 
 	let chars: Vec<char> = square_string.to_lowercase().chars().collect();
 
 	let file = (chars[0] as u8).wrapping_sub(b'a');
 	if file > 7 {
-		return Err(FenError::InvalidFile(chars[0]));
+		return Err(FenErrorKind::InvalidFile(chars[0]));
 	}
 
-	let rank = chars[1].to_digit(10).map(|d| d as u8).and_then(|d| d.checked_sub(1)).filter(|&d| d < 8).ok_or(FenError::InvalidRank(chars[1]))?;
+	let rank = chars[1].to_digit(10).map(|d| d as u8).and_then(|d| d.checked_sub(1)).filter(|&d| d < 8).ok_or(FenErrorKind::InvalidRank(chars[1]))?;
 
 	let square_index = rank * 8 + file;
 
 	if square_index > 63 {
-		return Err(FenError::OutOfBounds(square_index));
+		return Err(FenErrorKind::OutOfBounds(square_index));
 	}
 
 	Ok(square_index)
 }
 
-pub fn letter_to_piece(piece_char: char) -> Result<Piece, FenError> {
+pub fn letter_to_piece(piece_char: char) -> Result<Piece, FenErrorKind> {
 	let piece_type = match piece_char.to_ascii_lowercase() {
 		'k' => Piece::King,
 		'p' => Piece::Pawn,
@@ -46,7 +46,7 @@ pub fn letter_to_piece(piece_char: char) -> Result<Piece, FenError> {
 		'b' => Piece::Bishop,
 		'r' => Piece::Rook,
 		'q' => Piece::Queen,
-		_ => return Err(FenError::InvalidPieceChar(piece_char)),
+		_ => return Err(FenErrorKind::InvalidPieceChar(piece_char)),
 	};
 
 	Ok(piece_type)
@@ -68,19 +68,25 @@ impl Piece {
 }
 
 impl Move {
-	pub fn to_uci(&self) {
-		let mut uci_string = String::new();
+	pub fn to_uci(&self) -> String {
+		let mut uci_string = convert_square_to_string(self.from_square);
 
 		uci_string.push_str(&convert_square_to_string(self.to_square));
 
 		if let MoveKind::Promotion { promotion_piece } = self.move_kind {
 			uci_string.push(promotion_piece.to_char());
 		}
+
+		uci_string
 	}
 }
 
 impl Game {
 	pub fn convert_uci_to_move(&self, uci_string: &str) -> Result<Move, ChessError> {
+		if !matches!(uci_string.len(), 4 | 5) || !uci_string.is_ascii() {
+			return Err(ChessError::NotAValidMove);
+		}
+
 		let from_square = convert_string_to_square(&uci_string[..2])?;
 
 		let to_square = convert_string_to_square(&uci_string[2..4])?;
@@ -101,7 +107,7 @@ impl Game {
 		if uci_string.len() == 5 {
 			let promotion_piece = match letter_to_piece(uci_string.chars().last().unwrap()) {
 				Ok(x) => Some(x),
-				Err(x) => return Err(ChessError::FenError { fen_error: x }),
+				Err(x) => return Err(ChessError::FenError { fen_error: x.into() }),
 			};
 
 			mv.move_kind = MoveKind::Promotion {
@@ -168,6 +174,24 @@ impl ColoredPiece {
 
 		piece_char
 	}
+
+	/// Chess symbol of the piece, e.g. '♔' for the white king
+	pub fn to_unicode(&self) -> char {
+		match (self.side, self.piece) {
+			(Side::White, Piece::King) => '♔',
+			(Side::White, Piece::Queen) => '♕',
+			(Side::White, Piece::Rook) => '♖',
+			(Side::White, Piece::Bishop) => '♗',
+			(Side::White, Piece::Knight) => '♘',
+			(Side::White, Piece::Pawn) => '♙',
+			(Side::Black, Piece::King) => '♚',
+			(Side::Black, Piece::Queen) => '♛',
+			(Side::Black, Piece::Rook) => '♜',
+			(Side::Black, Piece::Bishop) => '♝',
+			(Side::Black, Piece::Knight) => '♞',
+			(Side::Black, Piece::Pawn) => '♟',
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 use super::*;
-use crate::errors::{ChessError, FenError};
+use crate::errors::{ChessError, FenErrorKind};
 use crate::moves::make_move::{Move, MoveKind};
 use crate::test_common::game_from_fen;
 use crate::{ColoredPiece, Piece, Side};
@@ -50,27 +50,27 @@ fn string_to_square_is_case_insensitive() {
 
 #[test]
 fn string_to_square_too_short_returns_error() {
-	assert!(matches!(convert_string_to_square("a"), Err(FenError::SquareLenghtIsnt2Wide(1))));
+	assert!(matches!(convert_string_to_square("a"), Err(FenErrorKind::SquareLenghtIsnt2Wide(1))));
 }
 
 #[test]
 fn string_to_square_too_long_returns_error() {
-	assert!(matches!(convert_string_to_square("a1b"), Err(FenError::SquareLenghtIsnt2Wide(3))));
+	assert!(matches!(convert_string_to_square("a1b"), Err(FenErrorKind::SquareLenghtIsnt2Wide(3))));
 }
 
 #[test]
 fn string_to_square_invalid_file_returns_error() {
-	assert!(matches!(convert_string_to_square("z1"), Err(FenError::InvalidFile('z'))));
+	assert!(matches!(convert_string_to_square("z1"), Err(FenErrorKind::InvalidFile('z'))));
 }
 
 #[test]
 fn string_to_square_invalid_rank_zero_returns_error() {
-	assert!(matches!(convert_string_to_square("a0"), Err(FenError::InvalidRank('0'))));
+	assert!(matches!(convert_string_to_square("a0"), Err(FenErrorKind::InvalidRank('0'))));
 }
 
 #[test]
 fn string_to_square_invalid_rank_nine_returns_error() {
-	assert!(matches!(convert_string_to_square("a9"), Err(FenError::InvalidRank('9'))));
+	assert!(matches!(convert_string_to_square("a9"), Err(FenErrorKind::InvalidRank('9'))));
 }
 
 // ── letter_to_piece ───────────────────────────────────────────────────────────
@@ -112,7 +112,7 @@ fn letter_to_piece_p_is_pawn() {
 
 #[test]
 fn letter_to_piece_invalid_char_returns_error() {
-	assert!(matches!(letter_to_piece('x'), Err(FenError::InvalidPieceChar('x'))));
+	assert!(matches!(letter_to_piece('x'), Err(FenErrorKind::InvalidPieceChar('x'))));
 }
 
 // ── Piece::to_char ────────────────────────────────────────────────────────────
@@ -182,7 +182,7 @@ fn colored_piece_to_char_white_rook() {
 // ── Move::to_uci ──────────────────────────────────────────────────────────────
 
 #[test]
-fn to_uci_quiet_move_does_not_panic() {
+fn to_uci_quiet_move() {
 	let white_rook = ColoredPiece { piece: Piece::Rook, side: Side::White };
 	let mv = Move {
 		from_square: 0,
@@ -190,11 +190,11 @@ fn to_uci_quiet_move_does_not_panic() {
 		move_kind: MoveKind::Quiet,
 		colored_piece: white_rook,
 	};
-	mv.to_uci();
+	assert_eq!(mv.to_uci(), "a1a2");
 }
 
 #[test]
-fn to_uci_promotion_does_not_panic() {
+fn to_uci_promotion() {
 	let white_pawn = ColoredPiece { piece: Piece::Pawn, side: Side::White };
 	let mv = Move {
 		from_square: 48,
@@ -202,7 +202,7 @@ fn to_uci_promotion_does_not_panic() {
 		move_kind: MoveKind::Promotion { promotion_piece: Piece::Queen },
 		colored_piece: white_pawn,
 	};
-	mv.to_uci();
+	assert_eq!(mv.to_uci(), "a7a8q");
 }
 
 // ── Game::convert_uci_to_move ─────────────────────────────────────────────────
@@ -349,3 +349,11 @@ fn convert_uci_to_move_invalid_to_rank_returns_error() {
 	let result = game.convert_uci_to_move("e2e9");
 	assert!(result.is_err());
 }
+
+#[test]
+fn convert_uci_to_move_wrong_length_returns_error() {
+	let game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	assert_eq!(game.convert_uci_to_move("e2"), Err(ChessError::NotAValidMove));
+	assert_eq!(game.convert_uci_to_move("e2e4qq"), Err(ChessError::NotAValidMove));
+	assert_eq!(game.convert_uci_to_move("é2e4"), Err(ChessError::NotAValidMove));
+}
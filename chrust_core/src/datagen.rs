@@ -0,0 +1,209 @@
+//! Self-play training data for evaluation tuning and network training.
+//!
+//! A record takes 35 bytes:
+//! - bytes 0..32: the position, see `encoding`
+//! - bytes 32..34: search score in centipawns from white's point of view (little endian `i16`)
+//! - byte 34: game result, 0 for a black win, 1 for a draw and 2 for a white win
+
+use crate::{
+	Side,
+	encoding::{ENCODED_POSITION_SIZE, decode_position},
+	errors::{ChessError, EncodingError},
+	game_status::GameStatus,
+	moves::make_move::MoveKind,
+	position::{Game, Position, STARTING_FEN},
+	search::{Search, is_capture, mate_in},
+	time_manager::SearchLimits,
+};
+
+pub const ENCODED_RECORD_SIZE: usize = ENCODED_POSITION_SIZE + 3;
+
+/// Games that reach this many plies without a result are adjudicated as a draw
+const DEFAULT_MAX_PLIES: usize = 400;
+/// Openings that end the game during the random plies are retried this often before giving up
+const MAX_OPENING_ATTEMPTS: usize = 100;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameResult {
+	WhiteWin,
+	Draw,
+	BlackWin,
+}
+
+/// A position seen in a self-play game together with its search score and the result of the game
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrainingRecord {
+	pub position: Position,
+	/// Centipawns from white's point of view
+	pub score: i16,
+	pub result: GameResult,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatagenOptions {
+	/// Limits of every search, usually a fixed depth or node count
+	pub limits: SearchLimits,
+	/// Number of uniformly random moves played after the opening position
+	pub random_plies: usize,
+	/// Opening positions as FEN, one is picked at random for every game. Empty plays from the starting position.
+	pub book: Vec<String>,
+	pub max_plies: usize,
+	/// Positions whose score is further from zero than this aren't recorded
+	pub max_score: i32,
+}
+
+/// Small xorshift generator, good enough to pick openings and keeps runs reproducible from a seed
+#[derive(Clone, Debug)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Default for DatagenOptions {
+	fn default() -> Self {
+		DatagenOptions {
+			limits: SearchLimits { depth: Some(6), ..Default::default() },
+			random_plies: 8,
+			book: Vec::new(),
+			max_plies: DEFAULT_MAX_PLIES,
+			max_score: 3_000,
+		}
+	}
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Rng {
+		// A zero state would only ever produce zeros
+		Rng { state: seed.max(1) }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state << 13;
+		self.state ^= self.state >> 7;
+		self.state ^= self.state << 17;
+		self.state
+	}
+
+	/// Random number in `0..bound`, `bound` has to be non zero
+	pub fn below(&mut self, bound: usize) -> usize {
+		(self.next_u64() % bound as u64) as usize
+	}
+}
+
+impl GameResult {
+	/// 1.0 for a white win, 0.5 for a draw and 0.0 for a black win
+	pub fn white_score(&self) -> f64 {
+		match self {
+			GameResult::WhiteWin => 1.0,
+			GameResult::Draw => 0.5,
+			GameResult::BlackWin => 0.0,
+		}
+	}
+
+	pub fn from_status(game_status: &GameStatus) -> GameResult {
+		match game_status {
+			GameStatus::CheckmateForSide(Side::White) | GameStatus::TimeoutForSide(Side::White) | GameStatus::ResignationForSide(Side::White) => GameResult::WhiteWin,
+			GameStatus::CheckmateForSide(Side::Black) | GameStatus::TimeoutForSide(Side::Black) | GameStatus::ResignationForSide(Side::Black) => GameResult::BlackWin,
+			_ => GameResult::Draw,
+		}
+	}
+}
+
+impl TrainingRecord {
+	pub fn encode(&self) -> Result<[u8; ENCODED_RECORD_SIZE], EncodingError> {
+		let mut bytes = [0u8; ENCODED_RECORD_SIZE];
+
+		bytes[..ENCODED_POSITION_SIZE].copy_from_slice(&self.position.encode()?);
+		bytes[32..34].copy_from_slice(&self.score.to_le_bytes());
+		bytes[34] = match self.result {
+			GameResult::BlackWin => 0,
+			GameResult::Draw => 1,
+			GameResult::WhiteWin => 2,
+		};
+
+		Ok(bytes)
+	}
+}
+
+pub fn decode_record(bytes: &[u8; ENCODED_RECORD_SIZE]) -> Result<TrainingRecord, EncodingError> {
+	let position = decode_position(bytes[..ENCODED_POSITION_SIZE].try_into().unwrap())?;
+	let score = i16::from_le_bytes([bytes[32], bytes[33]]);
+	let result = match bytes[34] {
+		0 => GameResult::BlackWin,
+		1 => GameResult::Draw,
+		2 => GameResult::WhiteWin,
+		result => return Err(EncodingError::InvalidGameResult(result)),
+	};
+
+	Ok(TrainingRecord { position, score, result })
+}
+
+/// Decodes a whole file of records
+pub fn decode_records(bytes: &[u8]) -> Result<Vec<TrainingRecord>, EncodingError> {
+	if !bytes.len().is_multiple_of(ENCODED_RECORD_SIZE) {
+		return Err(EncodingError::InvalidLength(bytes.len()));
+	}
+
+	bytes.chunks_exact(ENCODED_RECORD_SIZE).map(|chunk| decode_record(chunk.try_into().unwrap())).collect()
+}
+
+/// Plays one game of `search` against itself and returns the quiet positions it went through.
+/// Positions in check, with a capture or promotion as best move, or with a mate or very large score
+/// are left out since a static evaluation can't be expected to match their score.
+pub fn play_game(search: &mut Search, options: &DatagenOptions, rng: &mut Rng) -> Result<Vec<TrainingRecord>, ChessError> {
+	let mut game = random_opening(options, rng)?;
+	let mut samples: Vec<(Position, i16)> = Vec::new();
+
+	search.new_game();
+
+	while game.is_legal_game_state() && game.move_history.len() < options.max_plies {
+		let result = game.search_best_move(search, &options.limits)?;
+		let Some(best_move) = result.best_move else {
+			break;
+		};
+
+		let in_check = game.game_status == GameStatus::InCheck;
+		let is_noisy = is_capture(&game.position, &best_move) || matches!(best_move.move_kind, MoveKind::Promotion { .. });
+		if !in_check && !is_noisy && mate_in(result.score).is_none() && result.score.abs() <= options.max_score {
+			let score = match game.position.side_to_move {
+				Side::White => result.score,
+				Side::Black => -result.score,
+			};
+			samples.push((game.position, score.clamp(i16::MIN as i32, i16::MAX as i32) as i16));
+		}
+
+		game.make_move(&best_move)?;
+	}
+
+	let result = GameResult::from_status(&game.game_status);
+
+	Ok(samples.into_iter().map(|(position, score)| TrainingRecord { position, score, result }).collect())
+}
+
+/// Picks a book position and plays `random_plies` random moves from it, retrying if the game ends
+fn random_opening(options: &DatagenOptions, rng: &mut Rng) -> Result<Game, ChessError> {
+	for _ in 0..MAX_OPENING_ATTEMPTS {
+		let fen = match options.book.is_empty() {
+			true => STARTING_FEN,
+			false => options.book[rng.below(options.book.len())].as_str(),
+		};
+		let mut game = Game::try_from_fen(fen)?;
+
+		for _ in 0..options.random_plies {
+			if !game.is_legal_game_state() {
+				break;
+			}
+
+			let moves = game.position.get_all_legal_moves_for_side(game.position.side_to_move)?;
+			game.make_move(&moves[rng.below(moves.len())])?;
+		}
+
+		if game.is_legal_game_state() {
+			return Ok(game);
+		}
+	}
+
+	Err(ChessError::GameIsFinished)
+}
+
+#[cfg(test)]
+mod tests;
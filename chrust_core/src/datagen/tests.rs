@@ -0,0 +1,131 @@
+use super::*;
+use crate::{position::load_position_from_fen, search::SearchOptions, test_common::TEST_FENS};
+
+fn fast_options() -> DatagenOptions {
+	DatagenOptions {
+		limits: SearchLimits { depth: Some(2), ..Default::default() },
+		random_plies: 4,
+		max_plies: 40,
+		..Default::default()
+	}
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Record encoding tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn record_round_trips_through_bytes() {
+	for (index, fen) in TEST_FENS.iter().enumerate() {
+		let record = TrainingRecord {
+			position: load_position_from_fen(fen).unwrap(),
+			score: -1234 + index as i16 * 111,
+			result: [GameResult::WhiteWin, GameResult::Draw, GameResult::BlackWin][index % 3],
+		};
+
+		assert_eq!(decode_record(&record.encode().unwrap()), Ok(record), "{}", fen);
+	}
+}
+
+#[test]
+fn records_decode_from_concatenated_bytes() {
+	let records: Vec<TrainingRecord> = TEST_FENS[..3]
+		.iter()
+		.map(|fen| TrainingRecord {
+			position: load_position_from_fen(fen).unwrap(),
+			score: 25,
+			result: GameResult::Draw,
+		})
+		.collect();
+	let bytes: Vec<u8> = records.iter().flat_map(|record| record.encode().unwrap()).collect();
+
+	assert_eq!(bytes.len(), 3 * ENCODED_RECORD_SIZE);
+	assert_eq!(decode_records(&bytes), Ok(records));
+	assert_eq!(decode_records(&bytes[1..]), Err(EncodingError::InvalidLength(bytes.len() - 1)));
+}
+
+#[test]
+fn invalid_game_result_is_rejected() {
+	let record = TrainingRecord {
+		position: load_position_from_fen(TEST_FENS[0]).unwrap(),
+		score: 0,
+		result: GameResult::Draw,
+	};
+	let mut bytes = record.encode().unwrap();
+	bytes[34] = 3;
+
+	assert_eq!(decode_record(&bytes), Err(EncodingError::InvalidGameResult(3)));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Self-play tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn self_play_records_only_quiet_positions() {
+	let mut search = Search::new(SearchOptions::default());
+	let options = fast_options();
+	let mut rng = Rng::new(7);
+
+	for _ in 0..3 {
+		let records = play_game(&mut search, &options, &mut rng).unwrap();
+
+		assert!(!records.is_empty());
+		for record in &records {
+			let position = record.position;
+			assert!(position.is_king_in_check(position.side_to_move).unwrap().is_none());
+			assert!(i32::from(record.score).abs() <= options.max_score);
+			assert_eq!(record.result, records[0].result);
+		}
+	}
+}
+
+#[test]
+fn self_play_is_reproducible_from_the_seed() {
+	let options = fast_options();
+
+	let first = play_game(&mut Search::new(SearchOptions::default()), &options, &mut Rng::new(42)).unwrap();
+	let second = play_game(&mut Search::new(SearchOptions::default()), &options, &mut Rng::new(42)).unwrap();
+
+	assert_eq!(first, second);
+}
+
+#[test]
+fn self_play_starts_from_book_positions() {
+	let book_fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+	let options = DatagenOptions {
+		random_plies: 0,
+		book: vec![book_fen.to_string()],
+		..fast_options()
+	};
+
+	let records = play_game(&mut Search::new(SearchOptions::default()), &options, &mut Rng::new(1)).unwrap();
+
+	assert_eq!(records[0].position, load_position_from_fen(book_fen).unwrap());
+}
+
+#[test]
+fn mated_side_loses_the_game() {
+	let options = DatagenOptions {
+		random_plies: 0,
+		book: vec!["6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1".to_string()],
+		..fast_options()
+	};
+
+	// White mates right away and mate scores aren't recorded
+	let records = play_game(&mut Search::new(SearchOptions::default()), &options, &mut Rng::new(1)).unwrap();
+	assert!(records.is_empty());
+
+	let mut game = Game::try_from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+	game.make_move_from_uci("a1a8").unwrap();
+	assert_eq!(GameResult::from_status(&game.game_status), GameResult::WhiteWin);
+}
+
+#[test]
+fn rng_stays_below_bound() {
+	let mut rng = Rng::new(0);
+
+	for bound in 1..50 {
+		assert!(rng.below(bound) < bound);
+	}
+}
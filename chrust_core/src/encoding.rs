@@ -0,0 +1,220 @@
+//! Compact binary encodings for storing positions and moves in datasets.
+//!
+//! A position takes 32 bytes:
+//! - bytes 0..8: occupancy bitmask (little endian), bit n is set if square n holds a piece
+//! - bytes 8..24: one nibble per occupied square in ascending square order, low nibble first
+//! - byte 24: side to move (bit 0) and castling rights (bits 1..=4, same order as `castle`)
+//! - byte 25: en passant square or `NO_EN_PASSANT`
+//! - bytes 26..28: halfmove clock (little endian, saturated at `u16::MAX`)
+//! - bytes 28..32: fullmove counter (little endian)
+//!
+//! A move takes 16 bits: from square (bits 0..6), to square (bits 6..12) and a flag (bits 12..16).
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	errors::EncodingError,
+	helper::{file, rank, square},
+	moves::make_move::{Move, MoveKind},
+	position::Position,
+	zobrist::piece_index,
+};
+
+pub const ENCODED_POSITION_SIZE: usize = 32;
+
+const MAX_ENCODED_PIECES: u32 = 32;
+const NO_EN_PASSANT: u8 = 0xFF;
+
+const FLAG_QUIET: u8 = 0;
+const FLAG_CAPTURE: u8 = 1;
+const FLAG_DOUBLE_PAWN_PUSH: u8 = 2;
+const FLAG_EN_PASSANT: u8 = 3;
+const FLAG_CASTLING: u8 = 4;
+const FLAG_PROMOTION: u8 = 8;
+
+const PROMOTION_PIECES: [Piece; 4] = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen];
+
+impl Position {
+	pub fn encode(&self) -> Result<[u8; ENCODED_POSITION_SIZE], EncodingError> {
+		let mut bytes = [0u8; ENCODED_POSITION_SIZE];
+
+		let occupancy = self.board.iter().enumerate().filter(|(_, piece)| piece.is_some()).fold(0u64, |acc, (sq, _)| acc | (1 << sq));
+		if occupancy.count_ones() > MAX_ENCODED_PIECES {
+			return Err(EncodingError::TooManyPieces(occupancy.count_ones()));
+		}
+		bytes[0..8].copy_from_slice(&occupancy.to_le_bytes());
+
+		for (index, colored_piece) in self.board.iter().flatten().enumerate() {
+			let code = piece_index(*colored_piece) as u8;
+			bytes[8 + index / 2] |= code << ((index % 2) * 4);
+		}
+
+		let mut flags = match self.side_to_move {
+			Side::White => 0,
+			Side::Black => 1,
+		};
+		for (i, &right) in self.castle.iter().enumerate() {
+			if right {
+				flags |= 1 << (i + 1);
+			}
+		}
+		bytes[24] = flags;
+
+		bytes[25] = self.en_passant.unwrap_or(NO_EN_PASSANT);
+
+		let halfmove_clock = self.halfmove_clock.min(u16::MAX as u32) as u16;
+		bytes[26..28].copy_from_slice(&halfmove_clock.to_le_bytes());
+		bytes[28..32].copy_from_slice(&self.fullmove_counter.to_le_bytes());
+
+		Ok(bytes)
+	}
+}
+
+pub fn decode_position(bytes: &[u8; ENCODED_POSITION_SIZE]) -> Result<Position, EncodingError> {
+	let mut position = Position {
+		board: [None; 64],
+		castle: [false; 4],
+		en_passant: None,
+		side_to_move: Side::White,
+		king_squares: [4, 60],
+		halfmove_clock: 0,
+		fullmove_counter: 0,
+		zobrist_hash: 0,
+	};
+
+	let occupancy = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+	if occupancy.count_ones() > MAX_ENCODED_PIECES {
+		return Err(EncodingError::TooManyPieces(occupancy.count_ones()));
+	}
+
+	let mut king_counts = [0; 2];
+	let occupied_squares = (0..64u8).filter(|sq| occupancy & (1 << sq) != 0);
+	for (index, sq) in occupied_squares.enumerate() {
+		let code = (bytes[8 + index / 2] >> ((index % 2) * 4)) & 0x0F;
+		let colored_piece = piece_from_code(code)?;
+
+		if colored_piece.piece == Piece::King {
+			let side_index = match colored_piece.side {
+				Side::White => 0,
+				Side::Black => 1,
+			};
+			position.king_squares[side_index] = sq;
+			king_counts[side_index] += 1;
+		}
+
+		position.board[sq as usize] = Some(colored_piece);
+	}
+
+	// Move generation relies on exactly one king per side
+	for (side, count) in [(Side::White, king_counts[0]), (Side::Black, king_counts[1])] {
+		if count != 1 {
+			return Err(EncodingError::InvalidKingCount(side, count));
+		}
+	}
+
+	let flags = bytes[24];
+	position.side_to_move = if flags & 1 == 0 {
+		Side::White
+	} else {
+		Side::Black
+	};
+	for i in 0..4 {
+		position.castle[i] = flags & (1 << (i + 1)) != 0;
+	}
+
+	position.en_passant = match bytes[25] {
+		NO_EN_PASSANT => None,
+		sq if sq < 64 => Some(sq),
+		sq => return Err(EncodingError::InvalidEnPassantSquare(sq)),
+	};
+
+	position.halfmove_clock = u16::from_le_bytes([bytes[26], bytes[27]]) as u32;
+	position.fullmove_counter = u32::from_le_bytes(bytes[28..32].try_into().unwrap());
+
+	position.zobrist_hash = position.compute_hash();
+
+	Ok(position)
+}
+
+fn piece_from_code(code: u8) -> Result<ColoredPiece, EncodingError> {
+	let piece = match code % 6 {
+		0 => Piece::Pawn,
+		1 => Piece::Knight,
+		2 => Piece::Bishop,
+		3 => Piece::Rook,
+		4 => Piece::Queen,
+		_ => Piece::King,
+	};
+	let side = match code / 6 {
+		0 => Side::White,
+		1 => Side::Black,
+		_ => return Err(EncodingError::InvalidPieceCode(code)),
+	};
+
+	Ok(ColoredPiece { piece, side })
+}
+
+impl Move {
+	pub fn encode(&self) -> Result<u16, EncodingError> {
+		let flag = match self.move_kind {
+			MoveKind::Quiet => FLAG_QUIET,
+			MoveKind::Capture => FLAG_CAPTURE,
+			MoveKind::DoublePawnPush { .. } => FLAG_DOUBLE_PAWN_PUSH,
+			MoveKind::EnPassant { .. } => FLAG_EN_PASSANT,
+			MoveKind::Castling { .. } => FLAG_CASTLING,
+			MoveKind::Promotion { promotion_piece } => {
+				let index = PROMOTION_PIECES.iter().position(|&p| p == promotion_piece).ok_or(EncodingError::InvalidPromotionPiece(promotion_piece))?;
+				FLAG_PROMOTION | index as u8
+			}
+		};
+
+		Ok(self.from_square as u16 | (self.to_square as u16) << 6 | (flag as u16) << 12)
+	}
+}
+
+/// Decodes a packed move. The moving piece isn't part of the encoding and is read from `position`.
+pub fn decode_move(packed: u16, position: &Position) -> Result<Move, EncodingError> {
+	let from_square = (packed & 0x3F) as Square;
+	let to_square = ((packed >> 6) & 0x3F) as Square;
+	let flag = (packed >> 12) as u8;
+
+	let colored_piece = position.board[from_square as usize].ok_or(EncodingError::NoPieceOnSquare(from_square))?;
+
+	let move_kind = match flag {
+		FLAG_QUIET => MoveKind::Quiet,
+		FLAG_CAPTURE => MoveKind::Capture,
+		FLAG_DOUBLE_PAWN_PUSH => MoveKind::DoublePawnPush {
+			passed_square: (from_square + to_square) / 2,
+		},
+		FLAG_EN_PASSANT => MoveKind::EnPassant {
+			capture_square: square(file(to_square), rank(from_square)),
+		},
+		FLAG_CASTLING if from_square != 4 && from_square != 60 => return Err(EncodingError::InvalidMoveFlag(flag)),
+		FLAG_CASTLING => {
+			if to_square > from_square {
+				MoveKind::Castling {
+					rook_from: from_square + 3,
+					rook_to: from_square + 1,
+				}
+			} else {
+				MoveKind::Castling {
+					rook_from: from_square - 4,
+					rook_to: from_square - 1,
+				}
+			}
+		}
+		f if f & FLAG_PROMOTION != 0 && f < FLAG_PROMOTION + 4 => MoveKind::Promotion {
+			promotion_piece: PROMOTION_PIECES[(f - FLAG_PROMOTION) as usize],
+		},
+		f => return Err(EncodingError::InvalidMoveFlag(f)),
+	};
+
+	Ok(Move {
+		from_square,
+		to_square,
+		move_kind,
+		colored_piece,
+	})
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+use super::*;
+use crate::errors::EncodingError;
+use crate::position::load_position_from_fen;
+use crate::test_common::{TEST_FENS, empty_position};
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Position encoding tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn encode_position_round_trips_all_test_fens() {
+	for fen in TEST_FENS {
+		let pos = load_position_from_fen(fen).unwrap();
+
+		let bytes = pos.encode().expect("encode failed");
+		let decoded = decode_position(&bytes).expect("decode failed");
+
+		assert_eq!(decoded, pos, "round trip changed the position for {}", fen);
+		assert_eq!(decoded.export_position_to_fen().unwrap(), *fen);
+	}
+}
+
+#[test]
+fn encode_position_is_32_bytes() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(pos.encode().unwrap().len(), 32);
+}
+
+#[test]
+fn encode_position_starting_occupancy() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+	let bytes = pos.encode().unwrap();
+
+	assert_eq!(u64::from_le_bytes(bytes[0..8].try_into().unwrap()), 0xFFFF_0000_0000_FFFF);
+}
+
+#[test]
+fn encode_position_restores_king_squares_and_hash() {
+	let pos = load_position_from_fen("8/8/8/8/8/1q6/2k5/K7 w - - 0 1").unwrap();
+
+	let decoded = decode_position(&pos.encode().unwrap()).unwrap();
+
+	assert_eq!(decoded.king_squares, [0, 10]);
+	assert_eq!(decoded.zobrist_hash, pos.zobrist_hash);
+}
+
+#[test]
+fn encode_position_different_positions_differ() {
+	let a = load_position_from_fen(TEST_FENS[0]).unwrap().encode().unwrap();
+	let b = load_position_from_fen(TEST_FENS[1]).unwrap().encode().unwrap();
+
+	assert_ne!(a, b);
+}
+
+#[test]
+fn encode_position_with_too_many_pieces_fails() {
+	let mut pos = empty_position();
+	for sq in 0..33 {
+		pos.board[sq] = Some(ColoredPiece { piece: Piece::Pawn, side: Side::White });
+	}
+
+	assert_eq!(pos.encode(), Err(EncodingError::TooManyPieces(33)));
+}
+
+#[test]
+fn decode_position_invalid_piece_code_fails() {
+	let mut bytes = [0u8; ENCODED_POSITION_SIZE];
+	bytes[0] = 1;
+	bytes[8] = 12;
+
+	assert_eq!(decode_position(&bytes), Err(EncodingError::InvalidPieceCode(12)));
+}
+
+#[test]
+fn decode_position_invalid_en_passant_fails() {
+	let mut bytes = load_position_from_fen(TEST_FENS[0]).unwrap().encode().unwrap();
+	bytes[25] = 64;
+
+	assert_eq!(decode_position(&bytes), Err(EncodingError::InvalidEnPassantSquare(64)));
+}
+
+#[test]
+fn decode_position_too_many_pieces_fails() {
+	let mut bytes = [0u8; ENCODED_POSITION_SIZE];
+	bytes[0..8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+	assert_eq!(decode_position(&bytes), Err(EncodingError::TooManyPieces(64)));
+}
+
+#[test]
+fn decode_position_needs_one_king_per_side() {
+	let mut pos = empty_position();
+	pos.board[4] = Some(ColoredPiece { piece: Piece::King, side: Side::White });
+	let bytes = pos.encode().unwrap();
+
+	assert_eq!(decode_position(&bytes), Err(EncodingError::InvalidKingCount(Side::Black, 0)));
+
+	pos.board[60] = Some(ColoredPiece { piece: Piece::King, side: Side::Black });
+	pos.board[7] = Some(ColoredPiece { piece: Piece::King, side: Side::White });
+	let bytes = pos.encode().unwrap();
+
+	assert_eq!(decode_position(&bytes), Err(EncodingError::InvalidKingCount(Side::White, 2)));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Move encoding tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn encode_move_round_trips_all_legal_moves() {
+	for fen in TEST_FENS {
+		let mut pos = load_position_from_fen(fen).unwrap();
+		let moves = pos.get_all_legal_moves_for_side(pos.side_to_move).unwrap();
+
+		for mv in moves {
+			let decoded = decode_move(mv.encode().unwrap(), &pos).expect("decode_move failed");
+			assert_eq!(decoded, mv, "round trip changed the move in {}", fen);
+		}
+	}
+}
+
+#[test]
+fn encode_move_layout() {
+	let mv = Move {
+		from_square: 12,
+		to_square: 28,
+		move_kind: MoveKind::DoublePawnPush { passed_square: 20 },
+		colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::White },
+	};
+
+	assert_eq!(mv.encode(), Ok(12 | (28 << 6) | (2 << 12)));
+}
+
+#[test]
+fn encode_move_promotions_are_distinct() {
+	let encoded: Vec<u16> = PROMOTION_PIECES
+		.iter()
+		.map(|&promotion_piece| {
+			Move {
+				from_square: 52,
+				to_square: 60,
+				move_kind: MoveKind::Promotion { promotion_piece },
+				colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::White },
+			}
+			.encode()
+			.unwrap()
+		})
+		.collect();
+
+	for i in 0..encoded.len() {
+		for j in (i + 1)..encoded.len() {
+			assert_ne!(encoded[i], encoded[j]);
+		}
+	}
+}
+
+#[test]
+fn decode_move_empty_from_square_fails() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(decode_move(28 | (36 << 6), &pos), Err(EncodingError::NoPieceOnSquare(28)));
+}
+
+#[test]
+fn decode_move_invalid_flag_fails() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(decode_move(12 | (28 << 6) | (5 << 12), &pos), Err(EncodingError::InvalidMoveFlag(5)));
+	assert_eq!(decode_move(12 | (28 << 6) | (12 << 12), &pos), Err(EncodingError::InvalidMoveFlag(12)));
+}
+
+#[test]
+fn decode_move_castling_from_wrong_square_fails() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(decode_move(1 | (3 << 6) | (4 << 12), &pos), Err(EncodingError::InvalidMoveFlag(4)));
+}
+
+#[test]
+fn encode_move_invalid_promotion_piece_fails() {
+	for promotion_piece in [Piece::King, Piece::Pawn] {
+		let mv = Move {
+			from_square: 52,
+			to_square: 60,
+			move_kind: MoveKind::Promotion { promotion_piece },
+			colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::White },
+		};
+
+		assert_eq!(mv.encode(), Err(EncodingError::InvalidPromotionPiece(promotion_piece)));
+	}
+}
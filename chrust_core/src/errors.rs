@@ -1,4 +1,6 @@
-use crate::{Piece, Side, Square};
+use core::fmt;
+
+use crate::{ColoredPiece, Piece, Side, Square, converter::convert_square_to_string, moves::make_move::MoveKind};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ChessError {
@@ -6,11 +8,25 @@ pub enum ChessError {
 	GameIsFinished,
 	NotAValidMove,
 	NothingToUndo,
+	NothingToRedo,
+	PlyOutOfRange {
+		ply: usize,
+	},
 	NoDrawOffered,
 	CantAcceptYourOwnDraw,
 	FenError {
 		fen_error: FenError,
 	},
+	EncodingError {
+		encoding_error: EncodingError,
+	},
+	PgnError {
+		pgn_error: PgnError,
+	},
+	NoSuchNode {
+		node_id: usize,
+	},
+	CantDeleteRoot,
 	InvalidPromotionPiece {
 		piece: Piece,
 	},
@@ -31,10 +47,75 @@ pub enum ChessError {
 		expected_side: Side,
 		found_side: Side,
 	},
+	SetupError {
+		setup_error: SetupError,
+	},
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum FenError {
+/// Why a move can't be played, for showing to players
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IllegalMoveReason {
+	GameIsFinished,
+	NotASquareOnBoard {
+		square: Square,
+	},
+	NoPieceOnSquare {
+		square: Square,
+	},
+	WrongSide {
+		expected_side: Side,
+		found_side: Side,
+	},
+	/// The move names a different piece than the one on its from square
+	PieceMismatch {
+		found_piece: ColoredPiece,
+	},
+	CapturesOwnPiece {
+		square: Square,
+	},
+	/// The piece doesn't move that way
+	InvalidMovement,
+	PathBlocked {
+		square: Square,
+	},
+	/// A diagonal pawn move onto an empty square that isn't the en passant square
+	NothingToCapture,
+	MissingPromotion,
+	InvalidPromotion {
+		piece: Piece,
+	},
+	/// The piece reaches the square, but the move is flagged as another kind of move
+	MoveKindMismatch {
+		expected_kind: MoveKind,
+	},
+	PinnedToKing {
+		pinner: Square,
+	},
+	LeavesKingInCheck {
+		attackers: Vec<Square>,
+	},
+	MovesIntoCheck {
+		attackers: Vec<Square>,
+	},
+	CastlingRightsLost,
+	CastlingOutOfCheck {
+		attackers: Vec<Square>,
+	},
+	CastlingThroughCheck {
+		square: Square,
+		attackers: Vec<Square>,
+	},
+}
+
+/// What is wrong with a FEN, and where if it was found in a whole FEN
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FenError {
+	kind: FenErrorKind,
+	location: Option<(FenField, usize)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FenErrorKind {
 	InvalidPieceChar(char),
 	SquareLenghtIsnt2Wide(usize),
 	OutOfBounds(u8),
@@ -44,6 +125,117 @@ pub enum FenError {
 	MissingFenParts,
 	NotAValideSide,
 	InvalidNumber(String),
+	/// The piece placement doesn't have 8 ranks
+	InvalidRankCount(usize),
+	/// A rank of the piece placement is longer than 8 squares
+	InvalidRankLength(String),
+}
+
+/// The six space separated fields of a FEN
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FenField {
+	PiecePlacement,
+	SideToMove,
+	Castling,
+	EnPassant,
+	HalfmoveClock,
+	FullmoveNumber,
+}
+
+/// Why a position set up with a `PositionBuilder` can't occur in a game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SetupError {
+	NotASquareOnBoard {
+		square: Square,
+	},
+	MissingKing {
+		side: Side,
+	},
+	TooManyKings {
+		side: Side,
+	},
+	TooManyPieces {
+		side: Side,
+		count: usize,
+	},
+	TooManyPawns {
+		side: Side,
+		count: usize,
+	},
+	/// More pieces beyond the initial set than pawns could have promoted
+	TooManyPromotedPieces {
+		side: Side,
+	},
+	PawnOnBackRank {
+		square: Square,
+	},
+	KingsAreAdjacent,
+	/// The side that isn't to move is in check
+	OpponentInCheck {
+		side: Side,
+	},
+	TooManyCheckers {
+		count: usize,
+	},
+	/// The king or the rook isn't on its initial square
+	InvalidCastlingRights {
+		side: Side,
+		king_side: bool,
+	},
+	InvalidEnPassant {
+		square: Square,
+	},
+	InvalidFullmoveNumber,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncodingError {
+	TooManyPieces(u32),
+	InvalidPieceCode(u8),
+	InvalidEnPassantSquare(u8),
+	/// A side has no king or more than one
+	InvalidKingCount(Side, usize),
+	InvalidMoveFlag(u8),
+	InvalidPromotionPiece(Piece),
+	NoPieceOnSquare(Square),
+	InvalidGameResult(u8),
+	/// The data isn't a whole number of records
+	InvalidLength(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PgnError {
+	InvalidSan(String),
+	AmbiguousSan(String),
+	IllegalMove(String),
+	InvalidTag(String),
+	InvalidNag(String),
+	InvalidAnnotation(String),
+	UnterminatedComment,
+	UnbalancedVariation,
+	VariationWithoutMove,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NnueError {
+	Io(std::io::ErrorKind),
+	InvalidMagic,
+	UnsupportedVersion(u32),
+	/// The hidden layer size has to be a non zero multiple of 16
+	InvalidHiddenSize(u32),
+	InvalidFileSize {
+		expected: usize,
+		found: usize,
+	},
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TunerError {
+	MissingResult(String),
+	InvalidResult(String),
+	FenError {
+		fen_error: FenError,
+	},
 }
 
 impl From<FenError> for ChessError {
@@ -51,3 +243,244 @@ impl From<FenError> for ChessError {
 		ChessError::FenError { fen_error }
 	}
 }
+
+impl From<FenErrorKind> for FenError {
+	fn from(kind: FenErrorKind) -> Self {
+		FenError { kind, location: None }
+	}
+}
+
+impl From<FenErrorKind> for ChessError {
+	fn from(kind: FenErrorKind) -> Self {
+		ChessError::FenError { fen_error: kind.into() }
+	}
+}
+
+impl From<EncodingError> for ChessError {
+	fn from(encoding_error: EncodingError) -> Self {
+		ChessError::EncodingError { encoding_error }
+	}
+}
+
+impl From<PgnError> for ChessError {
+	fn from(pgn_error: PgnError) -> Self {
+		ChessError::PgnError { pgn_error }
+	}
+}
+
+impl From<SetupError> for ChessError {
+	fn from(setup_error: SetupError) -> Self {
+		ChessError::SetupError { setup_error }
+	}
+}
+
+impl From<FenError> for TunerError {
+	fn from(fen_error: FenError) -> Self {
+		TunerError::FenError { fen_error }
+	}
+}
+
+impl fmt::Display for IllegalMoveReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			IllegalMoveReason::GameIsFinished => write!(f, "the game is finished"),
+			IllegalMoveReason::NotASquareOnBoard { square } => write!(f, "{} is not a square on the board", square),
+			IllegalMoveReason::NoPieceOnSquare { square } => write!(f, "there is no piece on {}", convert_square_to_string(*square)),
+			IllegalMoveReason::WrongSide { expected_side, found_side } => write!(f, "it's {} to move, that is a {} piece", expected_side, found_side),
+			IllegalMoveReason::PieceMismatch { found_piece } => write!(f, "the piece there is a {} {}", found_piece.side, found_piece.piece),
+			IllegalMoveReason::CapturesOwnPiece { square } => write!(f, "{} is occupied by your own piece", convert_square_to_string(*square)),
+			IllegalMoveReason::InvalidMovement => write!(f, "the piece doesn't move that way"),
+			IllegalMoveReason::PathBlocked { square } => write!(f, "the way is blocked on {}", convert_square_to_string(*square)),
+			IllegalMoveReason::NothingToCapture => write!(f, "pawns only move diagonally to capture"),
+			IllegalMoveReason::MissingPromotion => write!(f, "the pawn has to promote"),
+			IllegalMoveReason::InvalidPromotion { piece } => write!(f, "the pawn can't promote to a {}", piece),
+			IllegalMoveReason::MoveKindMismatch { expected_kind } => match expected_kind {
+				MoveKind::Quiet => write!(f, "the move has to be a quiet move"),
+				MoveKind::Capture => write!(f, "the move has to be a capture"),
+				MoveKind::DoublePawnPush { .. } => write!(f, "the move has to be a double pawn push"),
+				MoveKind::EnPassant { .. } => write!(f, "the move has to be an en passant capture"),
+				MoveKind::Promotion { .. } => write!(f, "the move has to be a promotion"),
+				MoveKind::Castling { .. } => write!(f, "the move has to be castling"),
+			},
+			IllegalMoveReason::PinnedToKing { pinner } => write!(f, "the piece is pinned to the king by {}", convert_square_to_string(*pinner)),
+			IllegalMoveReason::LeavesKingInCheck { attackers } => write!(f, "the king stays in check from {}", square_list(attackers)),
+			IllegalMoveReason::MovesIntoCheck { attackers } => write!(f, "the king would be in check from {}", square_list(attackers)),
+			IllegalMoveReason::CastlingRightsLost => write!(f, "the castling rights for that side are lost"),
+			IllegalMoveReason::CastlingOutOfCheck { attackers } => write!(f, "you can't castle out of check from {}", square_list(attackers)),
+			IllegalMoveReason::CastlingThroughCheck { square, attackers } => {
+				write!(f, "the king can't castle through {}, it's attacked from {}", convert_square_to_string(*square), square_list(attackers))
+			}
+		}
+	}
+}
+
+impl FenError {
+	/// `kind` found in `field` of a whole FEN, at character `index`
+	pub(crate) fn located(kind: FenErrorKind, field: FenField, index: usize) -> FenError {
+		FenError { kind, location: Some((field, index)) }
+	}
+
+	pub fn kind(&self) -> &FenErrorKind {
+		&self.kind
+	}
+
+	/// The field of the FEN the error is in, `None` for errors of the FEN as a whole
+	pub fn field(&self) -> Option<FenField> {
+		self.location.map(|(field, _)| field)
+	}
+
+	/// Index of the character where the error was found
+	pub fn index(&self) -> Option<usize> {
+		self.location.map(|(_, index)| index)
+	}
+}
+
+impl fmt::Display for ChessError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ChessError::NotImplemented => write!(f, "not implemented"),
+			ChessError::GameIsFinished => write!(f, "the game is finished"),
+			ChessError::NotAValidMove => write!(f, "not a valid move"),
+			ChessError::NothingToUndo => write!(f, "there is no move to undo"),
+			ChessError::NothingToRedo => write!(f, "there is no move to redo"),
+			ChessError::PlyOutOfRange { ply } => write!(f, "ply {} is beyond the moves of the game", ply),
+			ChessError::NoDrawOffered => write!(f, "no draw has been offered"),
+			ChessError::CantAcceptYourOwnDraw => write!(f, "you can't accept your own draw offer"),
+			ChessError::FenError { fen_error } => write!(f, "invalid FEN: {}", fen_error),
+			ChessError::EncodingError { encoding_error } => write!(f, "invalid encoding: {}", encoding_error),
+			ChessError::PgnError { pgn_error } => write!(f, "invalid PGN: {}", pgn_error),
+			ChessError::NoSuchNode { node_id } => write!(f, "there is no node {} in the game tree", node_id),
+			ChessError::CantDeleteRoot => write!(f, "the root of the game tree can't be deleted"),
+			ChessError::InvalidPromotionPiece { piece } => write!(f, "a pawn can't promote to a {}", piece),
+			ChessError::NotASquareOnBoard { square } => write!(f, "{} is not a square on the board", square),
+			ChessError::NoPieceOnSquare { square } => write!(f, "there is no piece on {}", convert_square_to_string(*square)),
+			ChessError::KingIsAttacked { squares } => write!(f, "the king is attacked from {}", square_list(squares)),
+			ChessError::WrongPieceType { expected_piece, found_piece } => write!(f, "expected a {}, found a {}", expected_piece, found_piece),
+			ChessError::WrongSide { expected_side, found_side } => write!(f, "expected a {} piece, found a {} piece", expected_side, found_side),
+			ChessError::SetupError { setup_error } => write!(f, "invalid position: {}", setup_error),
+		}
+	}
+}
+
+impl std::error::Error for ChessError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			ChessError::FenError { fen_error } => Some(fen_error),
+			ChessError::EncodingError { encoding_error } => Some(encoding_error),
+			ChessError::PgnError { pgn_error } => Some(pgn_error),
+			ChessError::SetupError { setup_error } => Some(setup_error),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for FenError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.location {
+			Some((field, index)) => write!(f, "{}, character {}: {}", field, index + 1, self.kind),
+			None => write!(f, "{}", self.kind),
+		}
+	}
+}
+
+impl std::error::Error for FenError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		self.location.map(|_| &self.kind as &(dyn std::error::Error + 'static))
+	}
+}
+
+impl fmt::Display for FenErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			FenErrorKind::InvalidPieceChar(piece_char) => write!(f, "'{}' is not a piece", piece_char),
+			FenErrorKind::SquareLenghtIsnt2Wide(length) => write!(f, "a square has 2 characters, not {}", length),
+			FenErrorKind::OutOfBounds(square) => write!(f, "square {} is off the board", square),
+			FenErrorKind::InvalidFile(file) => write!(f, "'{}' is not a file", file),
+			FenErrorKind::InvalidRank(rank) => write!(f, "'{}' is not a rank", rank),
+			FenErrorKind::InvalidCastlingRights(castling_char) => write!(f, "'{}' is not a castling right", castling_char),
+			FenErrorKind::MissingFenParts => write!(f, "a FEN has 6 space separated fields"),
+			FenErrorKind::NotAValideSide => write!(f, "the side to move is w or b"),
+			FenErrorKind::InvalidNumber(number) => write!(f, "'{}' is not a number", number),
+			FenErrorKind::InvalidRankCount(count) => write!(f, "the board has 8 ranks, not {}", count),
+			FenErrorKind::InvalidRankLength(rank) => write!(f, "the rank '{}' is longer than 8 squares", rank),
+		}
+	}
+}
+
+impl std::error::Error for FenErrorKind {}
+
+impl fmt::Display for FenField {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			FenField::PiecePlacement => write!(f, "piece placement"),
+			FenField::SideToMove => write!(f, "side to move"),
+			FenField::Castling => write!(f, "castling rights"),
+			FenField::EnPassant => write!(f, "en passant square"),
+			FenField::HalfmoveClock => write!(f, "halfmove clock"),
+			FenField::FullmoveNumber => write!(f, "fullmove number"),
+		}
+	}
+}
+
+impl fmt::Display for SetupError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SetupError::NotASquareOnBoard { square } => write!(f, "{} is not a square on the board", square),
+			SetupError::MissingKing { side } => write!(f, "{} has no king", side),
+			SetupError::TooManyKings { side } => write!(f, "{} has more than one king", side),
+			SetupError::TooManyPieces { side, count } => write!(f, "{} has {} pieces, at most 16 are possible", side, count),
+			SetupError::TooManyPawns { side, count } => write!(f, "{} has {} pawns, at most 8 are possible", side, count),
+			SetupError::TooManyPromotedPieces { side } => write!(f, "{} has more promoted pieces than missing pawns", side),
+			SetupError::PawnOnBackRank { square } => write!(f, "there is a pawn on {}", convert_square_to_string(*square)),
+			SetupError::KingsAreAdjacent => write!(f, "the kings stand next to each other"),
+			SetupError::OpponentInCheck { side } => write!(f, "{} is in check but not to move", side),
+			SetupError::TooManyCheckers { count } => write!(f, "the king is checked by {} pieces, at most 2 are possible", count),
+			SetupError::InvalidCastlingRights { side, king_side: true } => write!(f, "{} can't castle king side without the king and the rook on their squares", side),
+			SetupError::InvalidCastlingRights { side, king_side: false } => write!(f, "{} can't castle queen side without the king and the rook on their squares", side),
+			SetupError::InvalidEnPassant { square } => write!(f, "{} is not behind a pawn that just moved two squares", convert_square_to_string(*square)),
+			SetupError::InvalidFullmoveNumber => write!(f, "the fullmove number starts at 1"),
+		}
+	}
+}
+
+impl std::error::Error for SetupError {}
+
+impl fmt::Display for EncodingError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			EncodingError::TooManyPieces(count) => write!(f, "{} pieces don't fit in a record", count),
+			EncodingError::InvalidPieceCode(code) => write!(f, "{} is not a piece code", code),
+			EncodingError::InvalidEnPassantSquare(square) => write!(f, "{} is not an en passant square", square),
+			EncodingError::InvalidKingCount(side, count) => write!(f, "{} has {} kings instead of one", side, count),
+			EncodingError::InvalidMoveFlag(flag) => write!(f, "{} is not a move flag", flag),
+			EncodingError::InvalidPromotionPiece(piece) => write!(f, "a pawn can't promote to a {}", piece),
+			EncodingError::NoPieceOnSquare(square) => write!(f, "there is no piece on {}", convert_square_to_string(*square)),
+			EncodingError::InvalidGameResult(result) => write!(f, "{} is not a game result", result),
+			EncodingError::InvalidLength(length) => write!(f, "{} bytes are not a whole number of records", length),
+		}
+	}
+}
+
+impl std::error::Error for EncodingError {}
+
+impl fmt::Display for PgnError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			PgnError::InvalidSan(san) => write!(f, "'{}' is not a move in SAN", san),
+			PgnError::AmbiguousSan(san) => write!(f, "'{}' matches more than one move", san),
+			PgnError::IllegalMove(san) => write!(f, "{} is illegal", san),
+			PgnError::InvalidTag(tag) => write!(f, "invalid tag {}", tag),
+			PgnError::InvalidNag(nag) => write!(f, "invalid annotation glyph {}", nag),
+			PgnError::InvalidAnnotation(annotation) => write!(f, "invalid annotation {}", annotation),
+			PgnError::UnterminatedComment => write!(f, "a comment is never closed"),
+			PgnError::UnbalancedVariation => write!(f, "the parentheses of the variations don't match"),
+			PgnError::VariationWithoutMove => write!(f, "a variation starts before the first move"),
+		}
+	}
+}
+
+impl std::error::Error for PgnError {}
+
+fn square_list(squares: &[Square]) -> String {
+	squares.iter().map(|&square| convert_square_to_string(square)).collect::<Vec<_>>().join(", ")
+}
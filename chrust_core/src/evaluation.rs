@@ -0,0 +1,191 @@
+use crate::{Piece, Side, position::Position};
+
+/// Game phase of the starting position, knights and bishops count 1, rooks 2 and queens 4
+pub(crate) const MAX_PHASE: i32 = 24;
+
+// Piece square tables from whites point of view, rank 8 first so they read like a board
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+	  0,   0,   0,   0,   0,   0,   0,   0,
+	 50,  50,  50,  50,  50,  50,  50,  50,
+	 10,  10,  20,  30,  30,  20,  10,  10,
+	  5,   5,  10,  25,  25,  10,   5,   5,
+	  0,   0,   0,  20,  20,   0,   0,   0,
+	  5,  -5, -10,   0,   0, -10,  -5,   5,
+	  5,  10,  10, -20, -20,  10,  10,   5,
+	  0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+	-50, -40, -30, -30, -30, -30, -40, -50,
+	-40, -20,   0,   0,   0,   0, -20, -40,
+	-30,   0,  10,  15,  15,  10,   0, -30,
+	-30,   5,  15,  20,  20,  15,   5, -30,
+	-30,   0,  15,  20,  20,  15,   0, -30,
+	-30,   5,  10,  15,  15,  10,   5, -30,
+	-40, -20,   0,   5,   5,   0, -20, -40,
+	-50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+	-20, -10, -10, -10, -10, -10, -10, -20,
+	-10,   0,   0,   0,   0,   0,   0, -10,
+	-10,   0,   5,  10,  10,   5,   0, -10,
+	-10,   5,   5,  10,  10,   5,   5, -10,
+	-10,   0,  10,  10,  10,  10,   0, -10,
+	-10,  10,  10,  10,  10,  10,  10, -10,
+	-10,   5,   0,   0,   0,   0,   5, -10,
+	-20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+	  0,   0,   0,   0,   0,   0,   0,   0,
+	  5,  10,  10,  10,  10,  10,  10,   5,
+	 -5,   0,   0,   0,   0,   0,   0,  -5,
+	 -5,   0,   0,   0,   0,   0,   0,  -5,
+	 -5,   0,   0,   0,   0,   0,   0,  -5,
+	 -5,   0,   0,   0,   0,   0,   0,  -5,
+	 -5,   0,   0,   0,   0,   0,   0,  -5,
+	  0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+	-20, -10, -10,  -5,  -5, -10, -10, -20,
+	-10,   0,   0,   0,   0,   0,   0, -10,
+	-10,   0,   5,   5,   5,   5,   0, -10,
+	 -5,   0,   5,   5,   5,   5,   0,  -5,
+	 -5,   0,   5,   5,   5,   5,   0,  -5,
+	-10,   0,   5,   5,   5,   5,   0, -10,
+	-10,   0,   0,   0,   0,   0,   0, -10,
+	-20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_MIDDLEGAME_TABLE: [i32; 64] = [
+	-30, -40, -40, -50, -50, -40, -40, -30,
+	-30, -40, -40, -50, -50, -40, -40, -30,
+	-30, -40, -40, -50, -50, -40, -40, -30,
+	-30, -40, -40, -50, -50, -40, -40, -30,
+	-20, -30, -30, -40, -40, -30, -30, -20,
+	-10, -20, -20, -20, -20, -20, -20, -10,
+	 20,  20,   0,   0,   0,   0,  20,  20,
+	 20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: [i32; 64] = [
+	-50, -40, -30, -20, -20, -30, -40, -50,
+	-30, -20, -10,   0,   0, -10, -20, -30,
+	-30, -10,  20,  30,  30,  20, -10, -30,
+	-30, -10,  30,  40,  40,  30, -10, -30,
+	-30, -10,  30,  40,  40,  30, -10, -30,
+	-30, -10,  20,  30,  30,  20, -10, -30,
+	-30, -30,   0,   0,   0,   0, -30, -30,
+	-50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+/// Weights of the static evaluation, see `tuner` for fitting them to game results
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalParams {
+	/// Material values of pawn, knight, bishop, rook and queen in centipawns
+	pub piece_values: [i32; 5],
+	/// Piece square tables in the same order as `piece_values`, from whites point of view with rank 8 first
+	pub piece_tables: [[i32; 64]; 5],
+	pub king_middlegame_table: [i32; 64],
+	pub king_endgame_table: [i32; 64],
+}
+
+pub const DEFAULT_EVAL_PARAMS: EvalParams = EvalParams {
+	piece_values: [100, 320, 330, 500, 900],
+	piece_tables: [
+		PAWN_TABLE,
+		KNIGHT_TABLE,
+		BISHOP_TABLE,
+		ROOK_TABLE,
+		QUEEN_TABLE,
+	],
+	king_middlegame_table: KING_MIDDLEGAME_TABLE,
+	king_endgame_table: KING_ENDGAME_TABLE,
+};
+
+/// Material value in centipawns
+pub fn piece_value(piece: Piece) -> i32 {
+	match piece_param_index(piece) {
+		Some(index) => DEFAULT_EVAL_PARAMS.piece_values[index],
+		None => 0,
+	}
+}
+
+/// Index of `piece` into `EvalParams::piece_values` and `piece_tables`, `None` for the king
+pub(crate) fn piece_param_index(piece: Piece) -> Option<usize> {
+	match piece {
+		Piece::Pawn => Some(0),
+		Piece::Knight => Some(1),
+		Piece::Bishop => Some(2),
+		Piece::Rook => Some(3),
+		Piece::Queen => Some(4),
+		Piece::King => None,
+	}
+}
+
+pub(crate) fn phase_weight(piece: Piece) -> i32 {
+	match piece {
+		Piece::Knight | Piece::Bishop => 1,
+		Piece::Rook => 2,
+		Piece::Queen => 4,
+		_ => 0,
+	}
+}
+
+impl Position {
+	/// Static evaluation in centipawns from the point of view of the side to move. Material and piece
+	/// square tables, the king table is tapered between middlegame and endgame by the remaining material.
+	pub fn evaluate(&self) -> i32 {
+		self.evaluate_with(&DEFAULT_EVAL_PARAMS)
+	}
+
+	/// Same as `evaluate` with other weights
+	pub fn evaluate_with(&self, params: &EvalParams) -> i32 {
+		let mut score = 0;
+		let mut king_middlegame = 0;
+		let mut king_endgame = 0;
+		let mut phase = 0;
+
+		for (square, colored_piece) in self.board.iter().enumerate() {
+			let Some(colored_piece) = colored_piece else {
+				continue;
+			};
+
+			// Tables are written rank 8 first, so white squares have to be flipped
+			let (table_index, sign) = match colored_piece.side {
+				Side::White => (square ^ 56, 1),
+				Side::Black => (square, -1),
+			};
+
+			phase += phase_weight(colored_piece.piece);
+
+			match piece_param_index(colored_piece.piece) {
+				Some(index) => score += sign * (params.piece_values[index] + params.piece_tables[index][table_index]),
+				None => {
+					king_middlegame += sign * params.king_middlegame_table[table_index];
+					king_endgame += sign * params.king_endgame_table[table_index];
+				}
+			}
+		}
+
+		let phase = phase.min(MAX_PHASE);
+		score += (king_middlegame * phase + king_endgame * (MAX_PHASE - phase)) / MAX_PHASE;
+
+		match self.side_to_move {
+			Side::White => score,
+			Side::Black => -score,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+use crate::position::{STARTING_FEN, load_position_from_fen};
+
+fn evaluate_fen(fen: &str) -> i32 {
+	load_position_from_fen(fen).unwrap().evaluate()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Evaluation tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn starting_position_is_balanced() {
+	assert_eq!(evaluate_fen(STARTING_FEN), 0);
+}
+
+#[test]
+fn score_is_from_side_to_move() {
+	let white = evaluate_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1");
+	let black = evaluate_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1");
+
+	assert!(white > 800);
+	assert_eq!(white, -black);
+}
+
+#[test]
+fn mirrored_position_scores_the_same() {
+	let white = evaluate_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+	let black = evaluate_fen("rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3");
+
+	assert_eq!(white, black);
+}
+
+#[test]
+fn central_knight_beats_rim_knight() {
+	let center = evaluate_fen("4k3/8/8/8/4N3/8/8/4K3 w - - 0 1");
+	let rim = evaluate_fen("4k3/8/8/8/7N/8/8/4K3 w - - 0 1");
+
+	assert!(center > rim);
+}
+
+#[test]
+fn king_prefers_center_in_endgame() {
+	let center = evaluate_fen("4k3/p7/8/8/4K3/8/8/8 w - - 0 1");
+	let corner = evaluate_fen("4k3/p7/8/8/8/8/8/K7 w - - 0 1");
+
+	assert!(center > corner);
+}
+
+#[test]
+fn king_prefers_shelter_in_middlegame() {
+	let castled = evaluate_fen("r2qk2r/8/8/8/8/8/8/R2Q1RK1 w - - 0 1");
+	let center = evaluate_fen("r2qk2r/8/8/8/4K3/8/8/R2Q1R2 w - - 0 1");
+
+	assert!(castled > center);
+}
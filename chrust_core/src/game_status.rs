@@ -1,12 +1,13 @@
 use crate::{
-	Piece, Side, Square,
+	ColoredPiece, Piece, Side, Square,
 	errors::ChessError,
-	helper::file_rank,
+	helper::{file_diff, file_rank, in_bounds},
 	moves::make_move::Move,
 	position::{Game, Position},
 };
 
 #[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameStatus {
 	Playing,
 	InCheck,
@@ -16,6 +17,9 @@ pub enum GameStatus {
 	DrawByFiftyMoves,
 	DrawByRepetition,
 	DrawByInsufficientMaterial,
+	TimeoutForSide(Side),
+	DrawByTimeoutVsInsufficientMaterial,
+	ResignationForSide(Side),
 }
 
 impl Game {
@@ -55,6 +59,37 @@ impl Game {
 		Ok(())
 	}
 
+	/// Ends the game after `flagged_side` ran out of time. The opponent only wins if they still have
+	/// mating material, otherwise the game is drawn.
+	pub fn flag_fall(&mut self, flagged_side: Side) -> Result<(), ChessError> {
+		if !self.is_legal_game_state() {
+			return Err(ChessError::GameIsFinished);
+		}
+
+		let winner = flagged_side.opponent();
+		if self.position.has_mating_material(winner) {
+			self.game_status = GameStatus::TimeoutForSide(winner);
+		} else {
+			self.game_status = GameStatus::DrawByTimeoutVsInsufficientMaterial;
+		}
+
+		Ok(())
+	}
+
+	/// Ends the game with a win for the opponent of `resigning_side`
+	pub fn resign(&mut self, resigning_side: Side) -> Result<(), ChessError> {
+		if !self.is_legal_game_state() {
+			return Err(ChessError::GameIsFinished);
+		}
+
+		self.game_status = GameStatus::ResignationForSide(resigning_side.opponent());
+		if let Some(clock) = self.clock.as_mut() {
+			clock.stop();
+		}
+
+		Ok(())
+	}
+
 	pub(crate) fn is_draw_by_repetition(&self) -> bool {
 		let current_hash = self.position.zobrist_hash;
 		let lookback = self.position.halfmove_clock as usize;
@@ -117,75 +152,171 @@ impl Position {
 	}
 
 	pub(crate) fn is_insufficient_material(&self) -> bool {
-		let mut white_knights: u8 = 0;
-		let mut black_knights: u8 = 0;
-		let mut white_bishop_color: Option<u8> = None;
-		let mut black_bishop_color: Option<u8> = None;
+		!self.has_mating_material(Side::White) && !self.has_mating_material(Side::Black)
+	}
+
+	/// Returns whether `side` could deliver checkmate by any series of legal moves, even with the
+	/// opponent cooperating (FIDE "no possible mate"). Used to adjudicate a flag fall: a side without
+	/// mating material can't win on time.
+	pub fn has_mating_material(&self, side: Side) -> bool {
+		let mut own_knights: u8 = 0;
+		let mut own_bishop_colors: [bool; 2] = [false; 2];
+		let mut opponent_has_blocker = false;
+		let mut opponent_bishop_colors: [bool; 2] = [false; 2];
 
 		for (i, square) in self.board.iter().enumerate() {
 			let Some(colored_piece) = square else {
 				continue;
 			};
 
-			match colored_piece.piece {
-				Piece::King => {}
-				Piece::Bishop => {
-					let bishop_color = {
-						let (file, rank) = file_rank(i as u8);
-						(file + rank) % 2
-					};
-					match colored_piece.side {
-						Side::White => {
-							if white_bishop_color.is_some() {
-								return false;
-							}
-							white_bishop_color = Some(bishop_color);
-						}
-						Side::Black => {
-							if black_bishop_color.is_some() {
-								return false;
-							}
-							black_bishop_color = Some(bishop_color);
-						}
-					}
+			let (file, rank) = file_rank(i as u8);
+			let square_color = ((file + rank) % 2) as usize;
+
+			if colored_piece.side == side {
+				match colored_piece.piece {
+					Piece::King => {}
+					Piece::Knight => own_knights += 1,
+					Piece::Bishop => own_bishop_colors[square_color] = true,
+					_ => return true,
+				}
+			} else {
+				match colored_piece.piece {
+					Piece::King => {}
+					Piece::Bishop => opponent_bishop_colors[square_color] = true,
+					_ => opponent_has_blocker = true,
 				}
-				Piece::Knight => match colored_piece.side {
-					Side::White => {
-						if white_knights > 0 {
-							return false;
-						}
-						white_knights += 1;
-					}
-					Side::Black => {
-						if black_knights > 0 {
-							return false;
-						}
-						black_knights += 1;
-					}
-				},
-				_ => return false,
 			}
 		}
 
-		let total_knights = white_knights + black_knights;
+		let own_bishops_on_both_colors = own_bishop_colors[0] && own_bishop_colors[1];
+
+		// Two knights, knight + bishop or bishops on both colors can mate a lone king
+		if own_knights >= 2 || (own_knights == 1 && own_bishop_colors.contains(&true)) || own_bishops_on_both_colors {
+			return true;
+		}
+
+		// A single knight mates with the help of any piece blocking the opponents king
+		if own_knights == 1 {
+			return opponent_has_blocker || opponent_bishop_colors.contains(&true);
+		}
+
+		// Bishops on one color only mate if the opponent has a piece that can block a square of the other color
+		if let Some(own_color) = own_bishop_colors.iter().position(|&has_bishop| has_bishop) {
+			return opponent_has_blocker || opponent_bishop_colors[1 - own_color];
+		}
+
+		false
+	}
+
+	/// Opt-in check for dead positions beyond insufficient material: both sides only have kings and
+	/// pawns, every pawn is permanently blocked and no king can ever reach an enemy pawn. In that case
+	/// no capture or pawn move can happen again and the game can't end in checkmate.
+	pub fn is_dead_position(&self) -> bool {
+		if self.is_insufficient_material() {
+			return true;
+		}
 
-		// 2 knights of any side are sufficient
-		if total_knights >= 2 {
+		if self.board.iter().flatten().any(|p| p.piece != Piece::King && p.piece != Piece::Pawn) {
 			return false;
 		}
 
-		// Knight + any bishop = sufficient
-		if total_knights != 0 && (white_bishop_color.is_some() || black_bishop_color.is_some()) {
+		if !self.are_all_pawns_blocked() {
 			return false;
 		}
 
-		// K+B vs K+B: only insufficient if bishops are on the same square color
-		if let (Some(wc), Some(bc)) = (white_bishop_color, black_bishop_color) {
-			return wc == bc;
+		[Side::White, Side::Black].iter().all(|&side| !self.can_king_reach_enemy_pawn(side))
+	}
+
+	fn are_all_pawns_blocked(&self) -> bool {
+		for (i, square) in self.board.iter().enumerate() {
+			let Some(colored_piece) = square else {
+				continue;
+			};
+
+			if colored_piece.piece != Piece::Pawn {
+				continue;
+			}
+
+			let (push_offset, capture_offsets): (i16, [i16; 2]) = match colored_piece.side {
+				Side::White => (8, [7, 9]),
+				Side::Black => (-8, [-7, -9]),
+			};
+
+			// Only a ram with an enemy pawn keeps a pawn blocked forever
+			let front = i as i16 + push_offset;
+			if !in_bounds(front)
+				|| self.board[front as usize]
+					!= Some(ColoredPiece {
+						piece: Piece::Pawn,
+						side: colored_piece.side.opponent(),
+					}) {
+				return false;
+			}
+
+			for capture_offset in capture_offsets {
+				let target = i as i16 + capture_offset;
+				if !in_bounds(target) || file_diff(target, i as u8) != 1 {
+					continue;
+				}
+
+				if self.board[target as usize].is_some_and(|p| p.side != colored_piece.side) {
+					return false;
+				}
+			}
 		}
 
 		true
 	}
+
+	/// Flood fills the squares the king of `side` can walk to without stepping on pawns or squares
+	/// attacked by enemy pawns, and checks whether any of them touches an enemy pawn.
+	fn can_king_reach_enemy_pawn(&self, side: Side) -> bool {
+		let king_square = match side {
+			Side::White => self.king_squares[0],
+			Side::Black => self.king_squares[1],
+		};
+		let enemy_pawn = ColoredPiece {
+			piece: Piece::Pawn,
+			side: side.opponent(),
+		};
+		let enemy_pawn_attack_offsets: [i16; 2] = match side {
+			Side::White => [-7, -9],
+			Side::Black => [7, 9],
+		};
+
+		let is_attacked_by_enemy_pawn = |square: i16| {
+			enemy_pawn_attack_offsets.iter().any(|&offset| {
+				let pawn_square = square - offset;
+				in_bounds(pawn_square) && file_diff(pawn_square, square as u8) == 1 && self.board[pawn_square as usize] == Some(enemy_pawn)
+			})
+		};
+
+		let mut visited = [false; 64];
+		let mut stack: Vec<Square> = vec![king_square];
+		visited[king_square as usize] = true;
+
+		while let Some(current) = stack.pop() {
+			for direction in [-9, -8, -7, -1, 1, 7, 8, 9] {
+				let candidate = current as i16 + direction;
+				if !in_bounds(candidate) || file_diff(candidate, current) > 1 || visited[candidate as usize] {
+					continue;
+				}
+
+				match self.board[candidate as usize] {
+					Some(p) if p == enemy_pawn => return true,
+					Some(p) if p.piece == Piece::Pawn => continue,
+					_ => {}
+				}
+
+				visited[candidate as usize] = true;
+				if !is_attacked_by_enemy_pawn(candidate) {
+					stack.push(candidate as u8);
+				}
+			}
+		}
+
+		false
+	}
 }
 
 #[cfg(test)]
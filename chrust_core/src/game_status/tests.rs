@@ -1,6 +1,7 @@
 use super::*;
+use crate::errors::ChessError;
 use crate::moves::make_move::MoveKind;
-use crate::position::Game;
+use crate::position::{Game, load_position_from_fen};
 use crate::test_common::empty_position;
 use crate::{ColoredPiece, Piece, Side};
 
@@ -816,7 +817,9 @@ fn empty_game_with_hash(hash: u64, halfmove_clock: u32) -> Game {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 		draw_offer: None,
 	}
 }
@@ -1177,7 +1180,9 @@ fn update_game_status_playing() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1202,7 +1207,9 @@ fn update_game_status_in_check() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1231,7 +1238,9 @@ fn update_game_status_checkmate_black_wins() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1259,7 +1268,9 @@ fn update_game_status_checkmate_white_wins() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1284,7 +1295,9 @@ fn update_game_status_stalemate() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1309,7 +1322,9 @@ fn update_game_status_draw_by_repetition() {
 		hash_history: vec![0x1111, hash, 0x2222, hash, 0x3333, 0x4444],
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1336,7 +1351,9 @@ fn update_game_status_draw_by_fifty_moves() {
 		hash_history: (0..100).map(|i| i as u64).collect(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1359,7 +1376,9 @@ fn update_game_status_draw_by_insufficient_material_kings_only() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1383,7 +1402,9 @@ fn update_game_status_draw_by_insufficient_material_kb_vs_k() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1407,7 +1428,9 @@ fn update_game_status_draw_by_insufficient_material_kn_vs_k() {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
@@ -1438,10 +1461,209 @@ fn update_game_status_repetition_has_priority_over_fifty_moves() {
 		],
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	};
 
 	game.update_game_status().expect("update_game_status failed");
 
 	assert!(matches!(game.game_status, GameStatus::DrawByRepetition), "repetition check runs first and should set DrawByRepetition");
 }
+
+// ══════════════════════════════════════════════════════════════════════════════
+// has_mating_material tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn has_mating_material_lone_king() {
+	let pos = load_position_from_fen("4k3/8/8/8/8/8/8/4K2Q w - - 0 1").unwrap();
+
+	assert!(!pos.has_mating_material(Side::Black), "lone king can never mate");
+	assert!(pos.has_mating_material(Side::White), "queen can mate");
+}
+
+#[test]
+fn has_mating_material_single_minor_vs_lone_king() {
+	let knight = load_position_from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+	let bishop = load_position_from_fen("4k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(!knight.has_mating_material(Side::White), "K+N vs K has no possible mate");
+	assert!(!bishop.has_mating_material(Side::White), "K+B vs K has no possible mate");
+}
+
+#[test]
+fn has_mating_material_knight_vs_knight() {
+	let pos = load_position_from_fen("4kn2/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+
+	assert!(pos.has_mating_material(Side::White), "K+N vs K+N: the enemy knight can block its own king");
+	assert!(pos.has_mating_material(Side::Black), "K+N vs K+N: the enemy knight can block its own king");
+}
+
+#[test]
+fn has_mating_material_bishop_vs_knight() {
+	let pos = load_position_from_fen("4kn2/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(pos.has_mating_material(Side::White), "K+B vs K+N: the knight can block a flight square");
+	assert!(pos.has_mating_material(Side::Black), "K+N vs K+B: the bishop can block a flight square");
+}
+
+#[test]
+fn has_mating_material_bishop_vs_pawn() {
+	let pos = load_position_from_fen("4k3/p7/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(pos.has_mating_material(Side::White), "K+B vs K+P: the pawn can block a flight square");
+	assert!(pos.has_mating_material(Side::Black), "pawn can promote");
+}
+
+#[test]
+fn has_mating_material_bishops_same_color() {
+	// f1 and c8 are both light squares
+	let pos = load_position_from_fen("2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(!pos.has_mating_material(Side::White), "bishops on the same color can never mate");
+	assert!(!pos.has_mating_material(Side::Black), "bishops on the same color can never mate");
+}
+
+#[test]
+fn has_mating_material_bishops_opposite_color() {
+	// f1 is light, f8 is dark
+	let pos = load_position_from_fen("4kb2/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(pos.has_mating_material(Side::White), "opposite colored bishops can block for each other");
+	assert!(pos.has_mating_material(Side::Black), "opposite colored bishops can block for each other");
+}
+
+#[test]
+fn has_mating_material_two_knights_vs_lone_king() {
+	let pos = load_position_from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1").unwrap();
+
+	assert!(pos.has_mating_material(Side::White), "K+N+N vs K can mate with cooperation");
+}
+
+#[test]
+fn is_insufficient_material_bishops_all_on_one_color() {
+	// c1 and e3 are both dark squares
+	let pos = load_position_from_fen("4k3/8/8/8/8/4B3/8/2B1K3 w - - 0 1").unwrap();
+
+	assert!(pos.is_insufficient_material(), "bishops on one color only should be insufficient material");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// is_dead_position tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn is_dead_position_insufficient_material() {
+	let pos = load_position_from_fen("4k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+
+	assert!(pos.is_dead_position(), "insufficient material is a dead position");
+}
+
+#[test]
+fn is_dead_position_blocked_pawn_fortress() {
+	// Locked pawn chain, neither king can cross it
+	let pos = load_position_from_fen("4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4K3 w - - 0 1").unwrap();
+
+	assert!(pos.is_dead_position(), "locked pawn chain without king access should be dead");
+}
+
+#[test]
+fn is_dead_position_king_can_reach_pawn() {
+	// The h-file is open, the white king can walk around the chain
+	let pos = load_position_from_fen("4k3/8/8/p1p1p3/P1P1P3/8/8/4K3 w - - 0 1").unwrap();
+
+	assert!(!pos.is_dead_position(), "king can reach enemy pawns");
+}
+
+#[test]
+fn is_dead_position_pawn_can_move() {
+	let pos = load_position_from_fen("4k3/8/8/p1p1p1p1/P1P1P1P1/8/7P/4K3 w - - 0 1").unwrap();
+
+	assert!(!pos.is_dead_position(), "free pawn can still promote");
+}
+
+#[test]
+fn is_dead_position_pawn_can_capture() {
+	let pos = load_position_from_fen("4k3/8/8/p1p1pp1p/P1P1P1PP/8/8/4K3 w - - 0 1").unwrap();
+
+	assert!(!pos.is_dead_position(), "pawn captures are still possible");
+}
+
+#[test]
+fn is_dead_position_starting_position() {
+	let pos = load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+
+	assert!(!pos.is_dead_position(), "starting position is not dead");
+}
+
+#[test]
+fn update_game_status_ignores_dead_position() {
+	let mut game = Game::try_from_fen("4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4K3 w - - 0 1").unwrap();
+
+	game.update_game_status().expect("update_game_status failed");
+
+	assert!(matches!(game.game_status, GameStatus::Playing), "dead position detection is opt-in");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// flag_fall tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn flag_fall_opponent_with_mating_material_wins() {
+	let mut game = Game::try_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+	game.flag_fall(Side::Black).expect("flag_fall failed");
+
+	assert!(matches!(game.game_status, GameStatus::TimeoutForSide(Side::White)), "white has a rook and should win on time");
+}
+
+#[test]
+fn flag_fall_vs_lone_king_is_draw() {
+	let mut game = Game::try_from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").unwrap();
+
+	game.flag_fall(Side::White).expect("flag_fall failed");
+
+	assert!(matches!(game.game_status, GameStatus::DrawByTimeoutVsInsufficientMaterial), "lone king can't win on time");
+}
+
+#[test]
+fn flag_fall_knight_vs_queen_wins() {
+	let mut game = Game::try_from_fen("4kn2/8/8/8/8/8/8/Q3K3 w - - 0 1").unwrap();
+
+	game.flag_fall(Side::White).expect("flag_fall failed");
+
+	assert!(matches!(game.game_status, GameStatus::TimeoutForSide(Side::Black)), "a knight can mate with the queen blocking");
+}
+
+#[test]
+fn flag_fall_on_finished_game_fails() {
+	let mut game = Game::try_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+	game.game_status = GameStatus::DrawByAgreement;
+
+	assert_eq!(game.flag_fall(Side::Black), Err(ChessError::GameIsFinished));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// resign tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn resign_wins_for_the_opponent() {
+	let mut game = Game::try_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+	game.resign(Side::White).expect("resign failed");
+
+	assert!(matches!(game.game_status, GameStatus::ResignationForSide(Side::Black)));
+	assert!(!game.is_legal_game_state());
+	assert!(game.export_pgn().unwrap().contains("[Result \"0-1\"]"));
+}
+
+#[test]
+fn resign_on_finished_game_fails() {
+	let mut game = Game::try_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+	game.game_status = GameStatus::DrawByAgreement;
+
+	assert_eq!(game.resign(Side::Black), Err(ChessError::GameIsFinished));
+}
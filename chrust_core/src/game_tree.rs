@@ -0,0 +1,299 @@
+use crate::{
+	Square,
+	errors::ChessError,
+	moves::make_move::Move,
+	position::{Game, Position, load_position_from_fen},
+};
+
+pub type NodeId = usize;
+
+pub const ROOT_NODE: NodeId = 0;
+
+/// A game with variations. Every node holds the position after its move, the root holds the
+/// starting position. `children[0]` of a node is its mainline continuation, every other child is a
+/// variation. Node ids stay valid until the node is deleted.
+pub struct GameTree {
+	pub tags: Vec<(String, String)>,
+	nodes: Vec<Option<Node>>,
+	current: NodeId,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+	pub mv: Option<Move>,
+	pub parent: Option<NodeId>,
+	pub children: Vec<NodeId>,
+	pub position: Position,
+	pub comment: Option<String>,
+	pub nags: Vec<u8>,
+	pub annotations: Vec<Annotation>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Annotation {
+	Arrow {
+		from_square: Square,
+		to_square: Square,
+		color: AnnotationColor,
+	},
+	Square {
+		square: Square,
+		color: AnnotationColor,
+	},
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AnnotationColor {
+	Red,
+	Green,
+	Blue,
+	Yellow,
+}
+
+impl GameTree {
+	pub fn new(position: Position) -> GameTree {
+		GameTree {
+			tags: Vec::new(),
+			nodes: vec![Some(Node::new(None, None, position))],
+			current: ROOT_NODE,
+		}
+	}
+
+	pub fn try_from_fen(fen: &str) -> Result<GameTree, ChessError> {
+		Ok(GameTree::new(load_position_from_fen(fen)?))
+	}
+
+	/// The moves of `game` as the mainline, including taken back moves that can still be redone. The
+	/// current node is the position on the board of the game.
+	pub fn from_game(game: &Game) -> Result<GameTree, ChessError> {
+		let mut start = game.position;
+		for (mv, undo) in game.move_history.iter().zip(&game.undo_history).rev() {
+			start.undo_move(*undo, *mv)?;
+		}
+
+		let mut tree = GameTree::new(start);
+		for mv in &game.move_history {
+			tree.add_move(mv)?;
+		}
+		let current = tree.current;
+		for mv in game.redo_history.iter().rev() {
+			tree.add_move(mv)?;
+		}
+		tree.current = current;
+
+		Ok(tree)
+	}
+
+	pub fn current(&self) -> NodeId {
+		self.current
+	}
+
+	pub fn position(&self) -> &Position {
+		&self.current_node().position
+	}
+
+	pub fn node(&self, node_id: NodeId) -> Result<&Node, ChessError> {
+		self.nodes.get(node_id).and_then(|node| node.as_ref()).ok_or(ChessError::NoSuchNode { node_id })
+	}
+
+	fn node_mut(&mut self, node_id: NodeId) -> Result<&mut Node, ChessError> {
+		self.nodes.get_mut(node_id).and_then(|node| node.as_mut()).ok_or(ChessError::NoSuchNode { node_id })
+	}
+
+	fn current_node(&self) -> &Node {
+		self.nodes[self.current].as_ref().expect("game_tree.rs: current node was deleted")
+	}
+
+	/// Plays `mv` from the current node. If the move already exists as a child it is reused,
+	/// otherwise it's added as a new variation (or as the mainline if there are no children yet).
+	pub fn add_move(&mut self, mv: &Move) -> Result<NodeId, ChessError> {
+		let node_id = self.add_child(self.current, mv)?;
+		self.current = node_id;
+
+		Ok(node_id)
+	}
+
+	pub fn add_move_from_san(&mut self, san: &str) -> Result<NodeId, ChessError> {
+		let mv = self.position().san_to_move(san)?;
+
+		self.add_move(&mv)
+	}
+
+	pub(crate) fn add_child(&mut self, parent_id: NodeId, mv: &Move) -> Result<NodeId, ChessError> {
+		let parent = self.node(parent_id)?;
+
+		if let Some(&existing) = parent.children.iter().find(|&&child| self.nodes[child].as_ref().and_then(|c| c.mv) == Some(*mv)) {
+			return Ok(existing);
+		}
+
+		let mut position = parent.position;
+		let legal_moves = position.get_legal_moves(mv.from_square, position.side_to_move)?;
+		if !legal_moves.contains(mv) {
+			return Err(ChessError::NotAValidMove);
+		}
+		position.make_move_unvalidated(*mv)?;
+
+		let node_id = self.nodes.len();
+		self.nodes.push(Some(Node::new(Some(*mv), Some(parent_id), position)));
+		self.node_mut(parent_id)?.children.push(node_id);
+
+		Ok(node_id)
+	}
+
+	/// Follows the mainline continuation of the current node. Returns false at the end of a line.
+	pub fn go_forward(&mut self) -> bool {
+		match self.current_node().children.first() {
+			Some(&child) => {
+				self.current = child;
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Steps back to the parent of the current node. Returns false at the root.
+	pub fn go_back(&mut self) -> bool {
+		match self.current_node().parent {
+			Some(parent) => {
+				self.current = parent;
+				true
+			}
+			None => false,
+		}
+	}
+
+	pub fn go_to(&mut self, node_id: NodeId) -> Result<(), ChessError> {
+		self.node(node_id)?;
+		self.current = node_id;
+
+		Ok(())
+	}
+
+	pub fn go_to_start(&mut self) {
+		self.current = ROOT_NODE;
+	}
+
+	/// Follows the mainline from the current node to the end of the line
+	pub fn go_to_end(&mut self) {
+		while self.go_forward() {}
+	}
+
+	/// Moves from the root to `node_id`
+	pub fn line_to(&self, node_id: NodeId) -> Result<Vec<Move>, ChessError> {
+		let mut moves = Vec::new();
+		let mut node = self.node(node_id)?;
+
+		while let Some(mv) = node.mv {
+			moves.push(mv);
+			node = self.node(node.parent.expect("game_tree.rs: node with a move has no parent"))?;
+		}
+		moves.reverse();
+
+		Ok(moves)
+	}
+
+	pub fn mainline(&self) -> Vec<Move> {
+		let mut moves = Vec::new();
+		let mut node = self.node(ROOT_NODE).expect("game_tree.rs: root node is missing");
+
+		while let Some(&child) = node.children.first() {
+			node = self.nodes[child].as_ref().expect("game_tree.rs: child node was deleted");
+			moves.extend(node.mv);
+		}
+
+		moves
+	}
+
+	/// Makes the line leading to `node_id` the mainline at every branching point above it
+	pub fn promote_variation(&mut self, node_id: NodeId) -> Result<(), ChessError> {
+		let mut child_id = node_id;
+		let mut parent_id = self.node(node_id)?.parent;
+
+		while let Some(id) = parent_id {
+			let parent = self.node_mut(id)?;
+			if let Some(index) = parent.children.iter().position(|&c| c == child_id) {
+				let child = parent.children.remove(index);
+				parent.children.insert(0, child);
+			}
+
+			child_id = id;
+			parent_id = parent.parent;
+		}
+
+		Ok(())
+	}
+
+	/// Removes `node_id` and everything after it. If the current node is removed the tree moves
+	/// back to the parent of the deleted node.
+	pub fn delete_variation(&mut self, node_id: NodeId) -> Result<(), ChessError> {
+		let parent_id = self.node(node_id)?.parent.ok_or(ChessError::CantDeleteRoot)?;
+
+		self.node_mut(parent_id)?.children.retain(|&c| c != node_id);
+
+		let mut stack = vec![node_id];
+		while let Some(id) = stack.pop() {
+			if let Some(node) = self.nodes[id].take() {
+				stack.extend(node.children);
+			}
+			if id == self.current {
+				self.current = parent_id;
+			}
+		}
+
+		Ok(())
+	}
+
+	pub fn set_comment(&mut self, node_id: NodeId, comment: Option<String>) -> Result<(), ChessError> {
+		self.node_mut(node_id)?.comment = comment;
+
+		Ok(())
+	}
+
+	/// Adds a numeric annotation glyph, e.g. 1 for `!` or 4 for `??`
+	pub fn add_nag(&mut self, node_id: NodeId, nag: u8) -> Result<(), ChessError> {
+		let node = self.node_mut(node_id)?;
+		if !node.nags.contains(&nag) {
+			node.nags.push(nag);
+		}
+
+		Ok(())
+	}
+
+	pub fn remove_nag(&mut self, node_id: NodeId, nag: u8) -> Result<(), ChessError> {
+		self.node_mut(node_id)?.nags.retain(|&n| n != nag);
+
+		Ok(())
+	}
+
+	pub fn add_annotation(&mut self, node_id: NodeId, annotation: Annotation) -> Result<(), ChessError> {
+		let node = self.node_mut(node_id)?;
+		if !node.annotations.contains(&annotation) {
+			node.annotations.push(annotation);
+		}
+
+		Ok(())
+	}
+
+	pub fn clear_annotations(&mut self, node_id: NodeId) -> Result<(), ChessError> {
+		self.node_mut(node_id)?.annotations.clear();
+
+		Ok(())
+	}
+}
+
+impl Node {
+	fn new(mv: Option<Move>, parent: Option<NodeId>, position: Position) -> Node {
+		Node {
+			mv,
+			parent,
+			children: Vec::new(),
+			position,
+			comment: None,
+			nags: Vec::new(),
+			annotations: Vec::new(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
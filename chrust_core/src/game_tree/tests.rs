@@ -0,0 +1,198 @@
+use super::*;
+use crate::errors::ChessError;
+use crate::position::{Game, STARTING_FEN};
+
+fn tree_with_moves(sans: &[&str]) -> GameTree {
+	let mut tree = GameTree::try_from_fen(STARTING_FEN).unwrap();
+	for san in sans {
+		tree.add_move_from_san(san).unwrap();
+	}
+	tree
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// add_move tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn add_move_builds_mainline() {
+	let tree = tree_with_moves(&["e4", "e5", "Nf3"]);
+
+	let mainline: Vec<String> = tree.mainline().iter().map(|m| m.colored_piece.piece.to_string()).collect();
+	assert_eq!(mainline, ["Pawn", "Pawn", "Knight"]);
+	assert_eq!(tree.position().export_position_to_fen().unwrap(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+}
+
+#[test]
+fn add_move_existing_move_is_reused() {
+	let mut tree = tree_with_moves(&["e4"]);
+	let first = tree.current();
+	tree.go_back();
+
+	let again = tree.add_move_from_san("e4").unwrap();
+
+	assert_eq!(again, first);
+	assert_eq!(tree.node(ROOT_NODE).unwrap().children.len(), 1);
+}
+
+#[test]
+fn add_move_creates_variation() {
+	let mut tree = tree_with_moves(&["e4"]);
+	tree.go_back();
+
+	let d4 = tree.add_move_from_san("d4").unwrap();
+
+	let root = tree.node(ROOT_NODE).unwrap();
+	assert_eq!(root.children.len(), 2);
+	assert_eq!(root.children[1], d4);
+}
+
+#[test]
+fn from_game_keeps_moves_that_can_be_redone() {
+	let mut game = Game::try_from_fen(STARTING_FEN).unwrap();
+	for uci in ["e2e4", "e7e5", "g1f3"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+	game.undo_last_move().unwrap();
+
+	let mut tree = GameTree::from_game(&game).unwrap();
+
+	assert_eq!(tree.position(), &game.position);
+	assert_eq!(tree.mainline().len(), 3);
+	tree.go_to_start();
+	assert_eq!(tree.position().export_position_to_fen().unwrap(), STARTING_FEN);
+}
+
+#[test]
+fn add_move_illegal_fails() {
+	let mut tree = tree_with_moves(&["e4", "e5"]);
+	let mut mv = tree.position().san_to_move("Ke2").unwrap();
+	mv.to_square = 20;
+
+	assert_eq!(tree.add_move(&mv), Err(ChessError::NotAValidMove));
+	assert_eq!(tree.node(tree.current()).unwrap().children.len(), 0);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Navigation tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn go_back_and_forward() {
+	let mut tree = tree_with_moves(&["e4", "e5"]);
+	let end = tree.current();
+
+	assert!(tree.go_back());
+	assert!(tree.go_back());
+	assert!(!tree.go_back(), "root has no parent");
+	assert_eq!(tree.position().export_position_to_fen().unwrap(), STARTING_FEN);
+
+	assert!(tree.go_forward());
+	assert!(tree.go_forward());
+	assert!(!tree.go_forward(), "end of the mainline");
+	assert_eq!(tree.current(), end);
+}
+
+#[test]
+fn go_to_start_and_end() {
+	let mut tree = tree_with_moves(&["e4", "e5", "Nf3"]);
+	let end = tree.current();
+
+	tree.go_to_start();
+	assert_eq!(tree.current(), ROOT_NODE);
+
+	tree.go_to_end();
+	assert_eq!(tree.current(), end);
+}
+
+#[test]
+fn go_to_jumps_into_variation() {
+	let mut tree = tree_with_moves(&["e4", "e5"]);
+	tree.go_back();
+	let c5 = tree.add_move_from_san("c5").unwrap();
+	tree.go_to_start();
+
+	tree.go_to(c5).unwrap();
+
+	assert_eq!(tree.position().export_position_to_fen().unwrap(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2");
+	assert_eq!(tree.line_to(c5).unwrap().len(), 2);
+}
+
+#[test]
+fn go_to_unknown_node_fails() {
+	let mut tree = tree_with_moves(&[]);
+
+	assert_eq!(tree.go_to(42), Err(ChessError::NoSuchNode { node_id: 42 }));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Variation editing tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn promote_variation_to_mainline() {
+	let mut tree = tree_with_moves(&["e4", "e5", "Nf3"]);
+	tree.go_to_start();
+	tree.add_move_from_san("e4").unwrap();
+	tree.add_move_from_san("c5").unwrap();
+	let nf3 = tree.add_move_from_san("Nf3").unwrap();
+	tree.go_back();
+	let nc3 = tree.add_move_from_san("Nc3").unwrap();
+
+	tree.promote_variation(nc3).unwrap();
+
+	assert_eq!(tree.mainline(), tree.line_to(nc3).unwrap());
+	assert!(tree.node(tree.node(nc3).unwrap().parent.unwrap()).unwrap().children.contains(&nf3));
+}
+
+#[test]
+fn delete_variation_removes_subtree() {
+	let mut tree = tree_with_moves(&["e4", "e5"]);
+	tree.go_back();
+	let c5 = tree.add_move_from_san("c5").unwrap();
+	let nf3 = tree.add_move_from_san("Nf3").unwrap();
+
+	tree.delete_variation(c5).unwrap();
+
+	assert!(tree.node(c5).is_err());
+	assert!(tree.node(nf3).is_err());
+	assert_eq!(tree.node(tree.current()).unwrap().children.len(), 1, "current moves back to the parent of the deleted node");
+	assert_eq!(tree.mainline().len(), 2);
+}
+
+#[test]
+fn delete_root_fails() {
+	let mut tree = tree_with_moves(&["e4"]);
+
+	assert_eq!(tree.delete_variation(ROOT_NODE), Err(ChessError::CantDeleteRoot));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Annotation tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn comments_nags_and_annotations() {
+	let mut tree = tree_with_moves(&["e4"]);
+	let node = tree.current();
+	let arrow = Annotation::Arrow {
+		from_square: 6,
+		to_square: 21,
+		color: AnnotationColor::Green,
+	};
+
+	tree.set_comment(node, Some("best by test".to_string())).unwrap();
+	tree.add_nag(node, 1).unwrap();
+	tree.add_nag(node, 1).unwrap();
+	tree.add_annotation(node, arrow).unwrap();
+
+	let n = tree.node(node).unwrap();
+	assert_eq!(n.comment.as_deref(), Some("best by test"));
+	assert_eq!(n.nags, [1]);
+	assert_eq!(n.annotations, [arrow]);
+
+	tree.remove_nag(node, 1).unwrap();
+	tree.clear_annotations(node).unwrap();
+	assert!(tree.node(node).unwrap().nags.is_empty());
+	assert!(tree.node(node).unwrap().annotations.is_empty());
+}
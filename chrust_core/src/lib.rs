@@ -1,23 +1,44 @@
 use core::fmt;
 
+pub mod attacks;
+pub mod clock;
 pub mod converter;
+pub mod datagen;
+pub mod encoding;
 pub mod errors;
+pub mod evaluation;
 pub mod game_status;
+pub mod game_tree;
 pub mod helper;
+pub mod mate_solver;
+pub mod motifs;
 pub mod moves;
+#[cfg(feature = "nnue")]
+pub mod nnue;
+pub mod perft;
+pub mod pgn;
 pub mod position;
+pub mod position_builder;
+pub mod san;
+pub mod search;
+pub mod time_manager;
+pub mod transform;
+pub mod transposition_table;
+pub mod tuner;
 pub mod zobrist;
 
 #[cfg(test)]
 mod test_common;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
 	White,
 	Black,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Piece {
 	King,
 	Queen,
@@ -28,6 +49,7 @@ pub enum Piece {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColoredPiece {
 	pub piece: Piece,
 	pub side: Side,
@@ -0,0 +1,168 @@
+//! Proof search for forced mates, for chess problems. Unlike the engine search it proves or refutes
+//! a mate exactly, without evaluation or pruning.
+
+use std::collections::HashMap;
+
+use crate::{errors::ChessError, moves::make_move::Move, position::Position};
+
+/// A forced mate found by `find_mate`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MateSolution {
+	/// Length of the shortest mate, in moves of the mating side
+	pub moves: u32,
+	/// Every first move that mates within the number of moves asked for
+	pub mating_moves: Vec<Move>,
+	/// The shortest mate against the longest defence
+	pub main_line: Vec<Move>,
+}
+
+/// Proves that the side to move mates in at most `moves` moves, `None` if it can't
+pub fn find_mate(position: &Position, moves: u32) -> Result<Option<MateSolution>, ChessError> {
+	if moves == 0 {
+		return Ok(None);
+	}
+
+	let mut solver = MateSolver::default();
+
+	let mut mating_moves = Vec::new();
+	for (mv, after, _) in successors(position)? {
+		if solver.is_mated_within(&after, moves - 1)? {
+			mating_moves.push(mv);
+		}
+	}
+	if mating_moves.is_empty() {
+		return Ok(None);
+	}
+
+	let shortest = solver.shortest_mate(position, moves)?.unwrap_or(moves);
+	let main_line = solver.main_line(position, shortest)?;
+
+	Ok(Some(MateSolution { moves: shortest, mating_moves, main_line }))
+}
+
+/// Whether the side to move gets mated in at most `moves` moves of the opponent, zero meaning it is mated
+pub(crate) fn is_mated_within(position: &Position, moves: u32) -> Result<bool, ChessError> {
+	MateSolver::default().is_mated_within(position, moves)
+}
+
+#[derive(Default)]
+struct MateSolver {
+	/// Results of `mates_within` by position hash and number of moves
+	proven: HashMap<(u64, u32), bool>,
+}
+
+impl MateSolver {
+	/// Whether the side to move mates in at most `moves` moves
+	fn mates_within(&mut self, position: &Position, moves: u32) -> Result<bool, ChessError> {
+		if moves == 0 {
+			return Ok(false);
+		}
+		if let Some(&mates) = self.proven.get(&(position.zobrist_hash, moves)) {
+			return Ok(mates);
+		}
+
+		let mut mates = false;
+		for (_, after, gives_check) in successors(position)? {
+			// The last move has to give check
+			if moves == 1 && !gives_check {
+				break;
+			}
+			if self.is_mated_within(&after, moves - 1)? {
+				mates = true;
+				break;
+			}
+		}
+
+		self.proven.insert((position.zobrist_hash, moves), mates);
+		Ok(mates)
+	}
+
+	fn is_mated_within(&mut self, position: &Position, moves: u32) -> Result<bool, ChessError> {
+		let replies = successors(position)?;
+		if replies.is_empty() {
+			return Ok(position.is_king_in_check(position.side_to_move)?.is_some());
+		}
+		if moves == 0 {
+			return Ok(false);
+		}
+
+		for (_, after, _) in replies {
+			if !self.mates_within(&after, moves)? {
+				return Ok(false);
+			}
+		}
+
+		Ok(true)
+	}
+
+	/// Smallest number of moves up to `moves` the side to move mates in
+	fn shortest_mate(&mut self, position: &Position, moves: u32) -> Result<Option<u32>, ChessError> {
+		for shortest in 1..=moves {
+			if self.mates_within(position, shortest)? {
+				return Ok(Some(shortest));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Follows a mate in exactly `moves` moves, answering each move with the reply that delays mate the longest
+	fn main_line(&mut self, position: &Position, moves: u32) -> Result<Vec<Move>, ChessError> {
+		let mut line = Vec::new();
+		let mut position = *position;
+		let mut moves = moves;
+
+		while moves > 0 {
+			let mut mating_move = None;
+			for (mv, after, _) in successors(&position)? {
+				if self.is_mated_within(&after, moves - 1)? {
+					mating_move = Some((mv, after));
+					break;
+				}
+			}
+			let Some((mv, after)) = mating_move else {
+				break;
+			};
+			line.push(mv);
+			position = after;
+			moves -= 1;
+
+			let mut longest_defence = None;
+			for (reply, after, _) in successors(&position)? {
+				let mate = self.shortest_mate(&after, moves)?.unwrap_or(moves);
+				if longest_defence.is_none_or(|(_, _, longest)| mate > longest) {
+					longest_defence = Some((reply, after, mate));
+				}
+			}
+			let Some((reply, after, mate)) = longest_defence else {
+				break;
+			};
+			line.push(reply);
+			position = after;
+			moves = mate;
+		}
+
+		Ok(line)
+	}
+}
+
+/// Legal moves with the positions they lead to and whether they give check. Checks come first,
+/// then captures.
+fn successors(position: &Position) -> Result<Vec<(Move, Position, bool)>, ChessError> {
+	let mut position = *position;
+	let side = position.side_to_move;
+
+	let mut successors = Vec::new();
+	for mv in position.get_all_legal_moves_for_side(side)? {
+		let mut after = position;
+		after.make_move_unvalidated(mv)?;
+		let gives_check = after.is_king_in_check(side.opponent())?.is_some();
+		successors.push((mv, after, gives_check));
+	}
+	successors.sort_by_key(|(mv, _, gives_check)| (!gives_check, position.board[mv.to_square as usize].is_none()));
+
+	Ok(successors)
+}
+
+#[cfg(test)]
+mod tests;
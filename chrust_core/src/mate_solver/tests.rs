@@ -0,0 +1,98 @@
+use crate::{
+	mate_solver::find_mate,
+	moves::make_move::Move,
+	position::{Position, STARTING_FEN, load_position_from_fen},
+};
+
+fn position(fen: &str) -> Position {
+	load_position_from_fen(fen).unwrap()
+}
+
+fn ucis(moves: &[Move]) -> Vec<String> {
+	moves.iter().map(Move::to_uci).collect()
+}
+
+/// Plays `line` and checks that it ends in mate
+fn assert_mates(position: &Position, line: &[Move]) {
+	let mut position = *position;
+	for mv in line {
+		position.make_move_unvalidated(*mv).unwrap();
+	}
+
+	assert!(position.is_checkmate_for_side(position.side_to_move).unwrap(), "{:?} doesn't mate", ucis(line));
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Proving mates
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn finds_a_mate_in_one() {
+	let solution = find_mate(&position("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1"), 1).unwrap().unwrap();
+
+	assert_eq!(solution.moves, 1);
+	assert_eq!(ucis(&solution.mating_moves), vec!["a1a8"]);
+	assert_eq!(ucis(&solution.main_line), vec!["a1a8"]);
+}
+
+#[test]
+fn finds_every_key_of_a_mate_in_two() {
+	let position = position("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+	let solution = find_mate(&position, 2).unwrap().unwrap();
+
+	assert_eq!(solution.moves, 2);
+	assert!(ucis(&solution.mating_moves).contains(&"c6b6".to_string()));
+	assert!(ucis(&solution.mating_moves).contains(&"c6c7".to_string()));
+	assert_eq!(solution.main_line.len(), 3);
+	assert_mates(&position, &solution.main_line);
+}
+
+#[test]
+fn reports_the_shortest_mate_within_the_limit() {
+	let position = position("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+	let in_two = find_mate(&position, 2).unwrap().unwrap();
+	let in_three = find_mate(&position, 3).unwrap().unwrap();
+
+	assert_eq!(in_three.moves, 2);
+	assert_eq!(in_three.main_line, in_two.main_line);
+	// Slower mates count as well
+	assert!(in_three.mating_moves.len() > in_two.mating_moves.len());
+}
+
+#[test]
+fn main_line_takes_the_longest_defence() {
+	let position = position("k7/8/8/3K4/8/8/8/7R w - - 0 1");
+
+	assert_eq!(find_mate(&position, 2).unwrap(), None);
+	let solution = find_mate(&position, 3).unwrap().unwrap();
+	assert_eq!(solution.moves, 3);
+	assert_eq!(solution.main_line.len(), 5);
+	assert_mates(&position, &solution.main_line);
+}
+
+#[test]
+fn black_mates_too() {
+	let solution = find_mate(&position("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1"), 1).unwrap().unwrap();
+
+	assert_eq!(ucis(&solution.main_line), vec!["a8a1"]);
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Refuting mates
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn no_mate_in_the_starting_position() {
+	assert_eq!(find_mate(&position(STARTING_FEN), 2).unwrap(), None);
+}
+
+#[test]
+fn stalemate_is_not_mate() {
+	// Qb6 takes every square from the king, but it stalemates
+	assert_eq!(find_mate(&position("k7/8/8/1Q6/8/8/8/7K w - - 0 1"), 1).unwrap(), None);
+}
+
+#[test]
+fn zero_moves_never_mate() {
+	assert_eq!(find_mate(&position("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1"), 0).unwrap(), None);
+}
@@ -0,0 +1,252 @@
+//! Tactical motifs in positions and moves, for annotating games.
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	attacks::{AttackMap, DiscoveredAttack, Pin, moves_along},
+	errors::ChessError,
+	evaluation::piece_value,
+	helper::rank,
+	mate_solver::{find_mate, is_mated_within},
+	moves::make_move::Move,
+	position::Position,
+};
+
+/// Longest mate looked for, in moves of the mating side
+pub const MATE_SEARCH_DEPTH: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Motif {
+	/// A piece attacking enemy pieces that are worth more than it or undefended, or the king
+	Fork {
+		piece: Square,
+		targets: Vec<Square>,
+	},
+	/// A piece pinned to its king
+	Pin(Pin),
+	/// A slider attacking an enemy piece with a less valuable one behind it
+	Skewer {
+		attacker: Square,
+		front: Square,
+		behind: Square,
+	},
+	/// Moving a piece away attacks an enemy piece
+	DiscoveredAttack(DiscoveredAttack),
+	/// Moving `piece` away gives check with `attacker`
+	DiscoveredCheck {
+		piece: Square,
+		attacker: Square,
+	},
+	DoubleCheck {
+		checkers: Vec<Square>,
+	},
+	/// `side` mates with a rook or queen on the back rank of the opponent if it gets to move
+	BackRankMateThreat {
+		side: Side,
+		mating_move: Move,
+	},
+	HangingPiece {
+		square: Square,
+	},
+	/// The side to move, or the side that made the move, mates in `moves` moves
+	MateIn {
+		moves: u32,
+	},
+}
+
+impl Position {
+	/// Motifs on the board for both sides, those of the side to move first
+	pub fn motifs(&self) -> Result<Vec<Motif>, ChessError> {
+		let mut motifs = Vec::new();
+
+		if let Some(checkers) = self.is_king_in_check(self.side_to_move)?
+			&& checkers.len() > 1
+		{
+			motifs.push(Motif::DoubleCheck { checkers });
+		}
+
+		for side in [self.side_to_move, self.side_to_move.opponent()] {
+			let attacks = self.attack_map(side);
+			let defence = self.attack_map(side.opponent());
+
+			for square in (0..64).filter(|&square| self.board[square as usize].is_some_and(|piece| piece.side == side)) {
+				if let Some(fork) = self.fork(square, &attacks, &defence) {
+					motifs.push(fork);
+				}
+				motifs.extend(self.skewers(square));
+			}
+			motifs.extend(self.pinned_pieces(side.opponent()).into_iter().map(Motif::Pin));
+			motifs.extend(self.discovered_attack_candidates(side).into_iter().map(Motif::DiscoveredAttack));
+			motifs.extend(self.hanging_pieces(side.opponent()).into_iter().map(|square| Motif::HangingPiece { square }));
+			motifs.extend(self.back_rank_mates(side)?.into_iter().map(|mating_move| Motif::BackRankMateThreat { side, mating_move }));
+		}
+
+		if let Some(solution) = find_mate(self, MATE_SEARCH_DEPTH)? {
+			motifs.push(Motif::MateIn { moves: solution.moves });
+		}
+
+		Ok(motifs)
+	}
+
+	/// Motifs that `mv` creates for the side making it, and pieces it leaves hanging on either side
+	pub fn move_motifs(&self, mv: &Move) -> Result<Vec<Motif>, ChessError> {
+		let side = mv.colored_piece.side;
+		let opponent = side.opponent();
+		let mut after = *self;
+		after.make_move_unvalidated(*mv)?;
+
+		let mut motifs = Vec::new();
+		let attacks = after.attack_map(side);
+
+		if let Some(fork) = after.fork(mv.to_square, &attacks, &after.attack_map(opponent)) {
+			motifs.push(fork);
+		}
+		motifs.extend(after.pinned_pieces(opponent).into_iter().filter(|pin| pin.pinner == mv.to_square).map(Motif::Pin));
+		motifs.extend(after.skewers(mv.to_square));
+
+		for candidate in self.discovered_attack_candidates(side) {
+			if candidate.piece != mv.from_square || candidate.target == mv.to_square || !attacks.attackers(candidate.target).contains(&candidate.attacker) {
+				continue;
+			}
+
+			if candidate.target == self.king_square(opponent) {
+				motifs.push(Motif::DiscoveredCheck {
+					piece: candidate.piece,
+					attacker: candidate.attacker,
+				});
+			} else {
+				motifs.push(Motif::DiscoveredAttack(candidate));
+			}
+		}
+
+		if let Some(checkers) = after.is_king_in_check(opponent)?
+			&& checkers.len() > 1
+		{
+			motifs.push(Motif::DoubleCheck { checkers });
+		}
+
+		for hanging_side in [side, opponent] {
+			let before = self.hanging_pieces(hanging_side);
+			motifs.extend(after.hanging_pieces(hanging_side).into_iter().filter(|square| !before.contains(square)).map(|square| Motif::HangingPiece { square }));
+		}
+
+		let mut mate = None;
+		for moves in 1..=MATE_SEARCH_DEPTH {
+			if is_mated_within(&after, moves - 1)? {
+				mate = Some(moves);
+				break;
+			}
+		}
+		match mate {
+			Some(moves) => motifs.push(Motif::MateIn { moves }),
+			None => motifs.extend(after.back_rank_mates(side)?.into_iter().map(|mating_move| Motif::BackRankMateThreat { side, mating_move })),
+		}
+
+		Ok(motifs)
+	}
+
+	/// The fork of the piece on `square`, given the attacks of its side and the defence of the opponent
+	fn fork(&self, square: Square, attacks: &AttackMap, defence: &AttackMap) -> Option<Motif> {
+		let piece = self.board[square as usize]?;
+
+		let targets: Vec<Square> = (0..64)
+			.filter(|&target| {
+				self.board[target as usize].is_some_and(|target_piece| {
+					target_piece.side != piece.side
+						&& attacks.attackers(target).contains(&square)
+						&& (target_piece.piece == Piece::King || worth(target_piece) > worth(piece) || !defence.is_attacked(target))
+				})
+			})
+			.collect();
+
+		(targets.len() > 1).then_some(Motif::Fork { piece: square, targets })
+	}
+
+	/// Skewers by the slider on `square`
+	fn skewers(&self, square: Square) -> Vec<Motif> {
+		let mut skewers = Vec::new();
+		let Some(piece) = self.board[square as usize] else {
+			return skewers;
+		};
+
+		for direction in [-8, 8, -1, 1, -7, 7, -9, 9] {
+			let is_rook_ray = direction == 8 || direction == -8 || direction == 1 || direction == -1;
+			if !moves_along(piece, is_rook_ray) {
+				continue;
+			}
+
+			let mut front: Option<(Square, ColoredPiece)> = None;
+			self.slide_ray(square, direction, |target, occupant| {
+				let Some(occupant) = occupant else {
+					return true;
+				};
+				if occupant.side == piece.side {
+					return false;
+				}
+
+				match front {
+					None => {
+						front = Some((target, occupant));
+						true
+					}
+					Some((front_square, front_piece)) => {
+						if worth(occupant) < worth(front_piece) {
+							skewers.push(Motif::Skewer {
+								attacker: square,
+								front: front_square,
+								behind: target,
+							});
+						}
+						false
+					}
+				}
+			});
+		}
+
+		skewers
+	}
+
+	/// Rook and queen moves of `side` onto the back rank of the opponent that mate. If the opponent
+	/// is to move and not in check, these are the mates it has to prevent.
+	fn back_rank_mates(&self, side: Side) -> Result<Vec<Move>, ChessError> {
+		let mut position = *self;
+		if position.side_to_move != side {
+			if position.is_king_in_check(position.side_to_move)?.is_some() {
+				return Ok(Vec::new());
+			}
+			position.side_to_move = side;
+			position.en_passant = None;
+			position.zobrist_hash = position.compute_hash();
+		}
+
+		let back_rank = match side {
+			Side::White => 7,
+			Side::Black => 0,
+		};
+
+		let mut mates = Vec::new();
+		for mv in position.get_all_legal_moves_for_side(side)? {
+			if !matches!(mv.colored_piece.piece, Piece::Rook | Piece::Queen) || rank(mv.to_square) != back_rank {
+				continue;
+			}
+
+			let mut after = position;
+			after.make_move_unvalidated(mv)?;
+			if after.is_checkmate_for_side(side.opponent())? {
+				mates.push(mv);
+			}
+		}
+
+		Ok(mates)
+	}
+}
+
+/// Material value with the king above everything
+fn worth(piece: ColoredPiece) -> i32 {
+	match piece.piece {
+		Piece::King => i32::MAX,
+		piece => piece_value(piece),
+	}
+}
+
+#[cfg(test)]
+mod tests;
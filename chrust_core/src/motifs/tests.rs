@@ -0,0 +1,124 @@
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	attacks::{DiscoveredAttack, Pin},
+	motifs::Motif,
+	moves::make_move::{Move, MoveKind},
+	position::{Position, load_position_from_fen},
+};
+
+fn position(fen: &str) -> Position {
+	load_position_from_fen(fen).unwrap()
+}
+
+fn quiet(from_square: Square, to_square: Square, piece: Piece) -> Move {
+	Move {
+		from_square,
+		to_square,
+		move_kind: MoveKind::Quiet,
+		colored_piece: ColoredPiece { piece, side: Side::White },
+	}
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Move motifs
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn knight_forks_king_and_rook() {
+	let motifs = position("r3k3/8/8/1N6/8/8/8/4K3 w - - 0 1").move_motifs(&quiet(33, 50, Piece::Knight)).unwrap();
+
+	assert_eq!(
+		motifs,
+		vec![
+			Motif::Fork { piece: 50, targets: vec![56, 60] },
+			Motif::HangingPiece { square: 56 }
+		]
+	);
+}
+
+#[test]
+fn bishop_pins_a_knight_to_the_king() {
+	let motifs = position("4k3/3n4/8/8/8/8/8/4KB2 w - - 0 1").move_motifs(&quiet(5, 33, Piece::Bishop)).unwrap();
+
+	assert_eq!(motifs, vec![Motif::Pin(Pin { pinned: 51, pinner: 33 })]);
+}
+
+#[test]
+fn rook_skewers_king_and_queen() {
+	let motifs = position("4q3/8/8/4k3/8/8/R7/6K1 w - - 0 1").move_motifs(&quiet(8, 12, Piece::Rook)).unwrap();
+
+	assert_eq!(motifs, vec![Motif::Skewer { attacker: 12, front: 36, behind: 60 }]);
+}
+
+#[test]
+fn moving_a_blocker_discovers_an_attack() {
+	// Rook and queen attack each other once the knight is gone, and the knight lands on a square the queen attacks
+	let motifs = position("4k3/8/8/4q3/8/8/4N3/4R1K1 w - - 0 1").move_motifs(&quiet(12, 18, Piece::Knight)).unwrap();
+
+	assert_eq!(
+		motifs,
+		vec![
+			Motif::DiscoveredAttack(DiscoveredAttack { piece: 12, attacker: 4, target: 36 }),
+			Motif::HangingPiece { square: 4 },
+			Motif::HangingPiece { square: 18 },
+			Motif::HangingPiece { square: 36 },
+		]
+	);
+}
+
+#[test]
+fn discovered_and_double_checks() {
+	let position = position("4k3/8/8/8/8/8/4B3/4R1K1 w - - 0 1");
+
+	assert_eq!(position.move_motifs(&quiet(12, 40, Piece::Bishop)).unwrap(), vec![Motif::DiscoveredCheck { piece: 12, attacker: 4 }]);
+
+	let motifs = position.move_motifs(&quiet(12, 33, Piece::Bishop)).unwrap();
+	assert!(motifs.contains(&Motif::DiscoveredCheck { piece: 12, attacker: 4 }));
+	assert!(motifs.iter().any(|motif| matches!(motif, Motif::DoubleCheck { checkers } if checkers.len() == 2)));
+}
+
+#[test]
+fn opening_a_file_threatens_back_rank_mate() {
+	let motifs = position("6k1/5ppp/8/8/8/8/4K3/7R w - - 0 1").move_motifs(&quiet(7, 0, Piece::Rook)).unwrap();
+
+	assert_eq!(
+		motifs,
+		vec![Motif::BackRankMateThreat {
+			side: Side::White,
+			mating_move: quiet(0, 56, Piece::Rook)
+		}]
+	);
+}
+
+#[test]
+fn king_move_starts_a_mate_in_two() {
+	let motifs = position("k7/8/2K5/8/8/8/8/7R w - - 0 1").move_motifs(&quiet(42, 41, Piece::King)).unwrap();
+
+	assert_eq!(motifs, vec![Motif::MateIn { moves: 2 }]);
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Position motifs
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn position_motifs_cover_both_sides() {
+	let motifs = position("r3k3/2N5/8/8/8/8/8/4K3 b - - 0 1").motifs().unwrap();
+	assert!(motifs.contains(&Motif::Fork { piece: 50, targets: vec![56, 60] }));
+
+	let motifs = position("4k3/3n4/8/1B6/8/8/8/4K3 b - - 0 1").motifs().unwrap();
+	assert_eq!(motifs, vec![Motif::Pin(Pin { pinned: 51, pinner: 33 })]);
+}
+
+#[test]
+fn position_motifs_find_the_shortest_mate() {
+	let motifs = position("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").motifs().unwrap();
+	assert!(motifs.contains(&Motif::BackRankMateThreat {
+		side: Side::White,
+		mating_move: quiet(0, 56, Piece::Rook)
+	}));
+	assert_eq!(motifs.last(), Some(&Motif::MateIn { moves: 1 }));
+
+	let motifs = position("k7/8/2K5/8/8/8/8/7R w - - 0 1").motifs().unwrap();
+	assert_eq!(motifs.last(), Some(&Motif::MateIn { moves: 2 }));
+}
@@ -0,0 +1,193 @@
+//! Legality of a single move, and why a move is illegal
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	errors::IllegalMoveReason,
+	helper::{file, rank, square},
+	moves::make_move::{Move, MoveKind},
+	position::{Game, Position},
+};
+
+impl Position {
+	/// Whether `mv` is legal for the side to move, only the moves of the moving piece are generated
+	pub fn is_legal(&self, mv: &Move) -> bool {
+		if mv.from_square > 63 || mv.to_square > 63 || self.board[mv.from_square as usize] != Some(mv.colored_piece) || mv.colored_piece.side != self.side_to_move {
+			return false;
+		}
+
+		match self.get_pseduo_legal_moves(mv.from_square, mv.colored_piece) {
+			Ok(moves) if moves.contains(mv) => self.attackers_after(mv).is_some_and(|attackers| attackers.is_empty()),
+			_ => false,
+		}
+	}
+
+	/// Why `mv` can't be played by the side to move, nothing if it is legal
+	pub fn explain_illegal_move(&self, mv: &Move) -> Option<IllegalMoveReason> {
+		for square in [mv.from_square, mv.to_square] {
+			if square > 63 {
+				return Some(IllegalMoveReason::NotASquareOnBoard { square });
+			}
+		}
+
+		let Some(piece) = self.board[mv.from_square as usize] else {
+			return Some(IllegalMoveReason::NoPieceOnSquare { square: mv.from_square });
+		};
+		if piece.side != self.side_to_move {
+			return Some(IllegalMoveReason::WrongSide {
+				expected_side: self.side_to_move,
+				found_side: piece.side,
+			});
+		}
+		if piece != mv.colored_piece {
+			return Some(IllegalMoveReason::PieceMismatch { found_piece: piece });
+		}
+
+		if piece.piece == Piece::King
+			&& rank(mv.from_square) == rank(mv.to_square)
+			&& file(mv.from_square).abs_diff(file(mv.to_square)) == 2
+			&& let Some(reason) = self.explain_castling(mv)
+		{
+			return Some(reason);
+		}
+		if self.board[mv.to_square as usize].is_some_and(|target| target.side == piece.side) {
+			return Some(IllegalMoveReason::CapturesOwnPiece { square: mv.to_square });
+		}
+
+		let pseudo_moves = self.get_pseduo_legal_moves(mv.from_square, piece).unwrap_or_default();
+		if pseudo_moves.contains(mv) {
+			return self.explain_check(mv);
+		}
+
+		// The piece reaches the square, but not with this kind of move
+		if let Some(candidate) = pseudo_moves.iter().find(|candidate| candidate.to_square == mv.to_square) {
+			return Some(match (candidate.move_kind, mv.move_kind) {
+				(_, MoveKind::Promotion { promotion_piece }) => IllegalMoveReason::InvalidPromotion { piece: promotion_piece },
+				(MoveKind::Promotion { .. }, _) => IllegalMoveReason::MissingPromotion,
+				(expected_kind, _) => IllegalMoveReason::MoveKindMismatch { expected_kind },
+			});
+		}
+
+		Some(self.explain_movement(mv, piece))
+	}
+
+	/// Castling needs the rights, the rook, empty squares up to the rook and a king that is never
+	/// attacked on its way. Nothing if all of that holds.
+	fn explain_castling(&self, mv: &Move) -> Option<IllegalMoveReason> {
+		let side = mv.colored_piece.side;
+		let home_rank = match side {
+			Side::White => 0,
+			Side::Black => 7,
+		};
+		if mv.from_square != square(4, home_rank) {
+			return Some(IllegalMoveReason::InvalidMovement);
+		}
+
+		let is_king_side = mv.to_square > mv.from_square;
+		let (rights, rook_from, between): (bool, Square, &[u8]) = match (side, is_king_side) {
+			(Side::White, true) => (self.castle[0], square(7, home_rank), &[5, 6]),
+			(Side::White, false) => (self.castle[1], square(0, home_rank), &[3, 2, 1]),
+			(Side::Black, true) => (self.castle[2], square(7, home_rank), &[5, 6]),
+			(Side::Black, false) => (self.castle[3], square(0, home_rank), &[3, 2, 1]),
+		};
+
+		let rook = ColoredPiece { piece: Piece::Rook, side };
+		if !rights || self.board[rook_from as usize] != Some(rook) {
+			return Some(IllegalMoveReason::CastlingRightsLost);
+		}
+		if let Some(&blocked_file) = between.iter().find(|&&between_file| self.board[square(between_file, home_rank) as usize].is_some()) {
+			return Some(IllegalMoveReason::PathBlocked { square: square(blocked_file, home_rank) });
+		}
+		if let Ok(Some(attackers)) = self.is_king_in_check(side) {
+			return Some(IllegalMoveReason::CastlingOutOfCheck { attackers });
+		}
+
+		let travel_square = square(between[0], home_rank);
+		if let Ok(Some(attackers)) = self.is_square_attacked(travel_square, side.opponent()) {
+			return Some(IllegalMoveReason::CastlingThroughCheck { square: travel_square, attackers });
+		}
+		if let Ok(Some(attackers)) = self.is_square_attacked(mv.to_square, side.opponent()) {
+			return Some(IllegalMoveReason::MovesIntoCheck { attackers });
+		}
+
+		None
+	}
+
+	/// Why a pseudo legal move is illegal: it has to leave the own king unattacked
+	fn explain_check(&self, mv: &Move) -> Option<IllegalMoveReason> {
+		let attackers = self.attackers_after(mv)?;
+		if attackers.is_empty() {
+			return None;
+		}
+		if mv.colored_piece.piece == Piece::King {
+			return Some(IllegalMoveReason::MovesIntoCheck { attackers });
+		}
+
+		// An attacker that only appears once the piece has moved away pinned it
+		let attackers_before = self.is_king_in_check(mv.colored_piece.side).ok().flatten().unwrap_or_default();
+		match attackers.iter().find(|attacker| !attackers_before.contains(attacker)) {
+			Some(&pinner) => Some(IllegalMoveReason::PinnedToKing { pinner }),
+			None => Some(IllegalMoveReason::LeavesKingInCheck { attackers }),
+		}
+	}
+
+	/// Why the piece can't reach the target square, the move isn't one of its pseudo legal moves
+	fn explain_movement(&self, mv: &Move, piece: ColoredPiece) -> IllegalMoveReason {
+		let file_delta = file(mv.to_square) as i8 - file(mv.from_square) as i8;
+		let rank_delta = rank(mv.to_square) as i8 - rank(mv.from_square) as i8;
+		let is_diagonal = file_delta.abs() == rank_delta.abs() && file_delta != 0;
+		let is_straight = (file_delta == 0) != (rank_delta == 0);
+
+		match piece.piece {
+			Piece::Bishop if is_diagonal => self.first_blocker(mv.from_square, file_delta, rank_delta),
+			Piece::Rook if is_straight => self.first_blocker(mv.from_square, file_delta, rank_delta),
+			Piece::Queen if is_diagonal || is_straight => self.first_blocker(mv.from_square, file_delta, rank_delta),
+			Piece::Pawn => {
+				let (forward, start_rank) = match piece.side {
+					Side::White => (1, 1),
+					Side::Black => (-1, 6),
+				};
+
+				match (file_delta.abs(), rank_delta * forward) {
+					(0, 1) => IllegalMoveReason::PathBlocked { square: mv.to_square },
+					(0, 2) if rank(mv.from_square) == start_rank => self.first_blocker(mv.from_square, file_delta, rank_delta),
+					(1, 1) if self.board[mv.to_square as usize].is_none() => IllegalMoveReason::NothingToCapture,
+					_ => IllegalMoveReason::InvalidMovement,
+				}
+			}
+			_ => IllegalMoveReason::InvalidMovement,
+		}
+	}
+
+	/// The first occupied square on the way from `from` to `from + delta`, the target included since
+	/// pawns can't capture straight ahead
+	fn first_blocker(&self, from: Square, file_delta: i8, rank_delta: i8) -> IllegalMoveReason {
+		let steps = file_delta.abs().max(rank_delta.abs());
+
+		(1..=steps)
+			.map(|step| square((file(from) as i8 + file_delta.signum() * step) as u8, (rank(from) as i8 + rank_delta.signum() * step) as u8))
+			.find(|&between| self.board[between as usize].is_some())
+			.map_or(IllegalMoveReason::InvalidMovement, |square| IllegalMoveReason::PathBlocked { square })
+	}
+
+	/// Squares attacking the own king once `mv` is played, nothing if it can't be played
+	fn attackers_after(&self, mv: &Move) -> Option<Vec<Square>> {
+		let mut position = *self;
+		position.make_move_unvalidated(*mv).ok()?;
+
+		Some(position.is_king_in_check(mv.colored_piece.side).ok()?.unwrap_or_default())
+	}
+}
+
+impl Game {
+	/// Why `mv` can't be played in this game, nothing if it is legal
+	pub fn explain_illegal_move(&self, mv: &Move) -> Option<IllegalMoveReason> {
+		if !self.is_legal_game_state() {
+			return Some(IllegalMoveReason::GameIsFinished);
+		}
+
+		self.position.explain_illegal_move(mv)
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,213 @@
+use crate::{
+	ColoredPiece, Piece, Side,
+	errors::IllegalMoveReason,
+	moves::make_move::{Move, MoveKind},
+	position::{Game, STARTING_FEN},
+	test_common::TEST_FENS,
+};
+
+fn game(fen: &str) -> Game {
+	Game::try_from_fen(fen).unwrap()
+}
+
+fn explain(fen: &str, uci: &str) -> Option<IllegalMoveReason> {
+	let game = game(fen);
+	let mv = game.convert_uci_to_move(uci).unwrap();
+
+	game.explain_illegal_move(&mv)
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// is_legal
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn is_legal_agrees_with_move_generation_on_every_test_position() {
+	for fen in TEST_FENS {
+		let mut position = game(fen).position;
+		let side = position.side_to_move;
+		let legal_moves = position.get_all_legal_moves_for_side(side).unwrap();
+
+		for (from, piece) in position.board.iter().enumerate() {
+			let Some(piece) = piece.filter(|piece| piece.side == side) else {
+				continue;
+			};
+
+			for mv in position.get_pseduo_legal_moves(from as u8, piece).unwrap() {
+				assert_eq!(position.is_legal(&mv), legal_moves.contains(&mv), "{} in {}", mv.to_uci(), fen);
+				assert_eq!(position.explain_illegal_move(&mv).is_none(), legal_moves.contains(&mv), "{} in {}", mv.to_uci(), fen);
+			}
+		}
+	}
+}
+
+#[test]
+fn is_legal_rejects_moves_of_the_wrong_piece_or_side() {
+	let position = game(STARTING_FEN).position;
+	let knight_as_bishop = Move {
+		from_square: 6,
+		to_square: 21,
+		move_kind: MoveKind::Quiet,
+		colored_piece: ColoredPiece { piece: Piece::Bishop, side: Side::White },
+	};
+	let black_pawn = Move {
+		from_square: 52,
+		to_square: 36,
+		move_kind: MoveKind::DoublePawnPush { passed_square: 44 },
+		colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::Black },
+	};
+
+	assert!(!position.is_legal(&knight_as_bishop));
+	assert!(!position.is_legal(&black_pawn));
+	assert!(!position.is_legal(&Move { to_square: 64, ..knight_as_bishop }));
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Reasons
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn legal_move_has_no_reason() {
+	assert_eq!(explain(STARTING_FEN, "e2e4"), None);
+	assert_eq!(explain(STARTING_FEN, "g1f3"), None);
+}
+
+#[test]
+fn empty_square_and_wrong_side_are_explained() {
+	assert_eq!(
+		explain(STARTING_FEN, "e7e5"),
+		Some(IllegalMoveReason::WrongSide {
+			expected_side: Side::White,
+			found_side: Side::Black
+		})
+	);
+
+	let game = game(STARTING_FEN);
+	let mut mv = game.convert_uci_to_move("e2e4").unwrap();
+	mv.from_square = 28;
+	assert_eq!(game.explain_illegal_move(&mv), Some(IllegalMoveReason::NoPieceOnSquare { square: 28 }));
+}
+
+#[test]
+fn piece_mismatch_is_explained() {
+	let game = game(STARTING_FEN);
+	let mut mv = game.convert_uci_to_move("g1f3").unwrap();
+	mv.colored_piece.piece = Piece::Bishop;
+
+	assert_eq!(
+		game.explain_illegal_move(&mv),
+		Some(IllegalMoveReason::PieceMismatch {
+			found_piece: ColoredPiece { piece: Piece::Knight, side: Side::White }
+		})
+	);
+}
+
+#[test]
+fn own_piece_on_target_is_explained() {
+	assert_eq!(explain(STARTING_FEN, "d1d2"), Some(IllegalMoveReason::CapturesOwnPiece { square: 11 }));
+}
+
+#[test]
+fn blocked_slider_names_the_first_blocker() {
+	assert_eq!(explain(STARTING_FEN, "a1a5"), Some(IllegalMoveReason::PathBlocked { square: 8 }));
+	assert_eq!(explain(STARTING_FEN, "f1b5"), Some(IllegalMoveReason::PathBlocked { square: 12 }));
+}
+
+#[test]
+fn impossible_geometry_is_invalid_movement() {
+	assert_eq!(explain(STARTING_FEN, "g1g3"), Some(IllegalMoveReason::InvalidMovement));
+	assert_eq!(explain(STARTING_FEN, "c1c3"), Some(IllegalMoveReason::InvalidMovement));
+	assert_eq!(explain(STARTING_FEN, "e2e5"), Some(IllegalMoveReason::InvalidMovement));
+}
+
+#[test]
+fn blocked_pawn_pushes_are_explained() {
+	let fen = "4k3/8/8/8/8/4p3/4P3/4K3 w - - 0 1";
+	assert_eq!(explain(fen, "e2e3"), Some(IllegalMoveReason::PathBlocked { square: 20 }));
+	assert_eq!(explain(fen, "e2e4"), Some(IllegalMoveReason::PathBlocked { square: 20 }));
+}
+
+#[test]
+fn diagonal_pawn_move_without_capture_is_explained() {
+	assert_eq!(explain(STARTING_FEN, "e2d3"), Some(IllegalMoveReason::NothingToCapture));
+}
+
+#[test]
+fn promotion_problems_are_explained() {
+	let fen = "7k/P7/8/8/8/8/8/7K w - - 0 1";
+	assert_eq!(explain(fen, "a7a8"), Some(IllegalMoveReason::MissingPromotion));
+	assert_eq!(explain(fen, "a7a8k"), Some(IllegalMoveReason::InvalidPromotion { piece: Piece::King }));
+	assert_eq!(explain(STARTING_FEN, "e2e3q"), Some(IllegalMoveReason::InvalidPromotion { piece: Piece::Queen }));
+	assert_eq!(explain(fen, "a7a8q"), None);
+}
+
+#[test]
+fn wrong_move_kind_is_explained() {
+	let game = game(STARTING_FEN);
+	let mut mv = game.convert_uci_to_move("g1f3").unwrap();
+	mv.move_kind = MoveKind::Capture;
+
+	assert_eq!(game.explain_illegal_move(&mv), Some(IllegalMoveReason::MoveKindMismatch { expected_kind: MoveKind::Quiet }));
+}
+
+#[test]
+fn pinned_piece_names_the_pinner() {
+	// The e2 rook is pinned by the e8 rook
+	let fen = "k3r3/8/8/8/8/8/4R3/4K3 w - - 0 1";
+	assert_eq!(explain(fen, "e2d2"), Some(IllegalMoveReason::PinnedToKing { pinner: 60 }));
+	assert_eq!(explain(fen, "e2e8"), None);
+}
+
+#[test]
+fn en_passant_discovering_a_rank_attack_is_a_pin() {
+	let fen = "8/8/8/K2pP2r/8/8/8/7k w - d6 0 1";
+	assert_eq!(explain(fen, "e5d6"), Some(IllegalMoveReason::PinnedToKing { pinner: 39 }));
+}
+
+#[test]
+fn ignoring_a_check_is_explained() {
+	let fen = "4k3/8/8/7R/8/8/8/4K2r w - - 0 1";
+	assert_eq!(explain(fen, "h5h6"), Some(IllegalMoveReason::LeavesKingInCheck { attackers: vec![7] }));
+	assert_eq!(explain(fen, "h5h1"), None);
+}
+
+#[test]
+fn king_moving_into_check_is_explained() {
+	let fen = "4k3/8/8/8/8/8/3r4/4K3 w - - 0 1";
+	assert_eq!(explain(fen, "e1f2"), Some(IllegalMoveReason::MovesIntoCheck { attackers: vec![11] }));
+}
+
+#[test]
+fn castling_problems_are_explained() {
+	assert_eq!(explain("4k3/8/8/8/8/8/8/4K2R w Q - 0 1", "e1g1"), Some(IllegalMoveReason::CastlingRightsLost));
+	assert_eq!(explain("4k3/8/8/8/8/8/8/R3K2R w K - 0 1", "e1c1"), Some(IllegalMoveReason::CastlingRightsLost));
+	assert_eq!(explain("4k3/8/8/8/8/8/8/RN2K2R w KQ - 0 1", "e1c1"), Some(IllegalMoveReason::PathBlocked { square: 1 }));
+	assert_eq!(explain("4k3/8/8/8/8/8/8/R3K1NR w KQ - 0 1", "e1g1"), Some(IllegalMoveReason::PathBlocked { square: 6 }));
+	assert_eq!(explain("4r1k1/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"), Some(IllegalMoveReason::CastlingOutOfCheck { attackers: vec![60] }));
+	assert_eq!(explain("5rk1/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"), Some(IllegalMoveReason::CastlingThroughCheck { square: 5, attackers: vec![61] }));
+	assert_eq!(explain("6rk/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"), Some(IllegalMoveReason::MovesIntoCheck { attackers: vec![62] }));
+	assert_eq!(explain("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1"), None);
+	assert_eq!(explain("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1c1"), None);
+}
+
+#[test]
+fn queen_side_castling_only_cares_about_the_kings_path() {
+	// b1 is attacked, but only the rook passes it
+	assert_eq!(explain("1r2k3/8/8/8/8/8/8/R3K3 w Q - 0 1", "e1c1"), None);
+}
+
+#[test]
+fn finished_game_is_explained() {
+	let mut game = game(STARTING_FEN);
+	game.resign(Side::White).unwrap();
+	let mv = game.convert_uci_to_move("e2e4").unwrap();
+
+	assert_eq!(game.explain_illegal_move(&mv), Some(IllegalMoveReason::GameIsFinished));
+}
+
+#[test]
+fn reasons_read_as_sentences() {
+	assert_eq!(IllegalMoveReason::PinnedToKing { pinner: 60 }.to_string(), "the piece is pinned to the king by e8");
+	assert_eq!(IllegalMoveReason::CastlingThroughCheck { square: 5, attackers: vec![61, 37] }.to_string(), "the king can't castle through f1, it's attacked from f8, f5");
+	assert_eq!(IllegalMoveReason::MoveKindMismatch { expected_kind: MoveKind::Quiet }.to_string(), "the move has to be a quiet move");
+}
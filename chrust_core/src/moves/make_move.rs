@@ -2,12 +2,13 @@ use crate::{
 	ColoredPiece, Piece, Side, Square,
 	errors::ChessError,
 	game_status::GameStatus,
-	helper::{is_square_on_board, is_valid_promomotion_piece},
+	helper::{is_right_piece_side, is_square_on_board, is_valid_promomotion_piece},
 	position::{Game, Position, Undo},
 	zobrist::{ZobristTable, piece_index, zobrist},
 };
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
 	pub from_square: Square,
 	pub to_square: Square,
@@ -15,7 +16,8 @@ pub struct Move {
 	pub colored_piece: ColoredPiece,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveKind {
 	Quiet,
 	Capture,
@@ -48,11 +50,34 @@ impl Game {
 			return Err(ChessError::GameIsFinished);
 		}
 
-		let legal_moves = self.position.get_legal_moves(mv.from_square, self.position.side_to_move)?;
+		if self.check_flag()? {
+			return Err(ChessError::GameIsFinished);
+		}
+
+		self.play_move(mv)?;
+
+		let is_finished = !self.is_legal_game_state();
+		if let Some(clock) = self.clock.as_mut() {
+			clock.press(mv.colored_piece.side);
+			if is_finished {
+				clock.stop();
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Plays a legal move and records it, without touching the clock
+	fn play_move(&mut self, mv: &Move) -> Result<(), ChessError> {
+		if !self.is_legal_game_state() {
+			return Err(ChessError::GameIsFinished);
+		}
+
+		is_right_piece_side(self.position.get_piece_from_square(mv.from_square)?, self.position.side_to_move)?;
 
 		is_square_on_board(mv.to_square)?;
 
-		if !legal_moves.contains(mv) {
+		if !self.position.is_legal(mv) {
 			return Err(ChessError::NotAValidMove);
 		}
 
@@ -63,6 +88,13 @@ impl Game {
 		self.undo_history.push(undo);
 		self.move_history.push(*mv);
 
+		// Replaying the next undone move keeps the rest of the redo line, any other move discards it
+		if self.redo_history.last() == Some(mv) {
+			self.redo_history.pop();
+		} else {
+			self.redo_history.clear();
+		}
+
 		if self.draw_offer == Some(self.position.side_to_move) {
 			self.draw_offer = None;
 		}
@@ -79,11 +111,50 @@ impl Game {
 
 		self.position.undo_move(undo, mv)?;
 		self.draw_offer = undo.previous_draw_offer;
+		self.redo_history.push(mv);
+		if let Some(clock) = self.clock.as_mut() {
+			clock.restore(self.move_history.len(), self.position.side_to_move);
+		}
 
 		self.update_game_status()?;
 		Ok(())
 	}
 
+	/// Replays the last undone move. Like undoing, this only navigates the game: the clock goes back
+	/// to the times recorded after the move instead of being pressed, and the flag isn't checked.
+	pub fn redo(&mut self) -> Result<(), ChessError> {
+		let mv = *self.redo_history.last().ok_or(ChessError::NothingToRedo)?;
+
+		self.play_move(&mv)?;
+
+		let is_finished = !self.is_legal_game_state();
+		if let Some(clock) = self.clock.as_mut() {
+			clock.restore(self.move_history.len(), self.position.side_to_move);
+			if is_finished {
+				clock.stop();
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Undoes or redoes moves until `ply` half moves of the game have been played
+	pub fn goto_ply(&mut self, ply: usize) -> Result<(), ChessError> {
+		if ply > self.move_history.len() + self.redo_history.len() {
+			return Err(ChessError::PlyOutOfRange { ply });
+		}
+
+		while self.move_history.len() > ply {
+			self.undo_last_move()?;
+		}
+
+		while self.move_history.len() < ply {
+			self.redo()?;
+		}
+
+		Ok(())
+	}
+
 	pub fn offer_draw(&mut self) -> Result<(), ChessError> {
 		if !self.is_legal_game_state() {
 			return Err(ChessError::GameIsFinished);
@@ -115,7 +186,8 @@ impl Game {
 }
 
 impl Position {
-	pub(crate) fn make_move_unvalidated(&mut self, mv: Move) -> Result<Undo, ChessError> {
+	/// Plays `mv` without checking that it is legal. The returned undo takes it back with `undo_move`.
+	pub fn make_move_unvalidated(&mut self, mv: Move) -> Result<Undo, ChessError> {
 		let piece = self.get_piece_from_square(mv.from_square)?;
 		let mut undo = self.build_undo();
 
@@ -134,7 +206,7 @@ impl Position {
 		Ok(undo)
 	}
 
-	pub(crate) fn undo_move(&mut self, undo: Undo, mv: Move) -> Result<(), ChessError> {
+	pub fn undo_move(&mut self, undo: Undo, mv: Move) -> Result<(), ChessError> {
 		let zobrist = zobrist();
 
 		self.update_zobrist_en_pasant_and_castling(zobrist);
@@ -149,6 +221,38 @@ impl Position {
 		Ok(())
 	}
 
+	/// Passes the turn without moving a piece, used for null move pruning. Clears en passant and
+	/// flips the side to move, the hash is updated like for a real move.
+	pub(crate) fn make_null_move(&mut self) -> Undo {
+		let zobrist = zobrist();
+		let undo = self.build_undo();
+
+		self.update_zobrist_en_pasant_and_castling(zobrist);
+
+		self.en_passant = None;
+		self.halfmove_clock += 1;
+		if self.side_to_move == Side::Black {
+			self.fullmove_counter += 1;
+		}
+		self.side_to_move = self.side_to_move.opponent();
+		self.zobrist_hash ^= zobrist.side;
+
+		self.update_zobrist_en_pasant_and_castling(zobrist);
+
+		undo
+	}
+
+	pub(crate) fn undo_null_move(&mut self, undo: Undo) {
+		let zobrist = zobrist();
+
+		self.update_zobrist_en_pasant_and_castling(zobrist);
+
+		self.apply_undo(undo);
+		self.zobrist_hash ^= zobrist.side;
+
+		self.update_zobrist_en_pasant_and_castling(zobrist);
+	}
+
 	pub(crate) fn build_undo(&self) -> Undo {
 		Undo {
 			captured_piece: None,
@@ -335,3 +439,6 @@ impl Position {
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(test)]
+mod property_tests;
@@ -0,0 +1,178 @@
+//! Randomized playouts from the shared test positions, checking the position invariants after
+//! every move. Attacks are compared against a deliberately naive implementation written here.
+
+use proptest::prelude::*;
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	game_status::GameStatus,
+	helper::{file, rank, square},
+	position::{Game, Position, STARTING_FEN, load_position_from_fen},
+	test_common::TEST_FENS,
+};
+
+/// Whether `attacking_side` attacks `target` by looking at every piece and its geometry
+fn naive_is_attacked(position: &Position, target: Square, attacking_side: Side) -> bool {
+	position.board.iter().enumerate().any(|(from, piece)| match piece {
+		Some(piece) if piece.side == attacking_side => naive_attacks(position, *piece, from as Square, target),
+		_ => false,
+	})
+}
+
+fn naive_attacks(position: &Position, piece: ColoredPiece, from: Square, target: Square) -> bool {
+	let file_delta = file(target) as i8 - file(from) as i8;
+	let rank_delta = rank(target) as i8 - rank(from) as i8;
+	let forward = match piece.side {
+		Side::White => 1,
+		Side::Black => -1,
+	};
+	let diagonal = file_delta.abs() == rank_delta.abs() && file_delta != 0;
+	let straight = (file_delta == 0) != (rank_delta == 0);
+
+	match piece.piece {
+		Piece::Pawn => rank_delta == forward && file_delta.abs() == 1,
+		Piece::Knight => matches!((file_delta.abs(), rank_delta.abs()), (1, 2) | (2, 1)),
+		Piece::King => file_delta.abs().max(rank_delta.abs()) == 1,
+		Piece::Bishop => diagonal && is_path_empty(position, from, file_delta, rank_delta),
+		Piece::Rook => straight && is_path_empty(position, from, file_delta, rank_delta),
+		Piece::Queen => (diagonal || straight) && is_path_empty(position, from, file_delta, rank_delta),
+	}
+}
+
+/// Whether every square strictly between `from` and `from + delta` is empty
+fn is_path_empty(position: &Position, from: Square, file_delta: i8, rank_delta: i8) -> bool {
+	let steps = file_delta.abs().max(rank_delta.abs());
+
+	(1..steps).all(|step| {
+		let between_file = file(from) as i8 + file_delta.signum() * step;
+		let between_rank = rank(from) as i8 + rank_delta.signum() * step;
+		position.board[square(between_file as u8, between_rank as u8) as usize].is_none()
+	})
+}
+
+fn king_square(position: &Position, side: Side) -> Square {
+	position.board.iter().position(|piece| *piece == Some(ColoredPiece { piece: Piece::King, side })).expect("property_tests.rs: side without a king") as Square
+}
+
+/// Every invariant of a position that has been reached by playing legal moves
+fn check_position(position: &Position) -> Result<(), TestCaseError> {
+	let fen = position.export_position_to_fen().unwrap();
+
+	prop_assert_eq!(position.zobrist_hash, position.compute_hash(), "incremental hash differs in {}", fen);
+	prop_assert_eq!(
+		position.king_squares,
+		[
+			king_square(position, Side::White),
+			king_square(position, Side::Black)
+		],
+		"king squares are stale in {}",
+		fen
+	);
+	prop_assert_eq!(load_position_from_fen(&fen), Ok(*position), "FEN round trip changed {}", fen);
+
+	for side in [Side::White, Side::Black] {
+		let naive = naive_is_attacked(position, king_square(position, side), side.opponent());
+		prop_assert_eq!(position.is_king_in_check(side).unwrap().is_some(), naive, "check detection for {:?} differs in {}", side, fen);
+	}
+
+	Ok(())
+}
+
+/// Every legal move is undone exactly, and a pseudo legal move is legal iff it doesn't leave the
+/// own king attacked
+fn check_moves(position: &Position) -> Result<(), TestCaseError> {
+	let fen = position.export_position_to_fen().unwrap();
+	let side = position.side_to_move;
+	let mut scratch = *position;
+	let legal_moves = scratch.get_all_legal_moves_for_side(side).unwrap();
+
+	for (from, piece) in position.board.iter().enumerate() {
+		let Some(piece) = piece.filter(|piece| piece.side == side) else {
+			continue;
+		};
+
+		for mv in position.get_pseduo_legal_moves(from as Square, piece).unwrap() {
+			let undo = scratch.make_move_unvalidated(mv).unwrap();
+			let leaves_king_attacked = naive_is_attacked(&scratch, king_square(&scratch, side), side.opponent());
+			scratch.undo_move(undo, mv).unwrap();
+
+			prop_assert_eq!(&scratch, position, "make/undo of {} changed {}", mv.to_uci(), fen);
+			prop_assert_eq!(legal_moves.contains(&mv), !leaves_king_attacked, "legality of {} is wrong in {}", mv.to_uci(), fen);
+		}
+	}
+
+	Ok(())
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(64))]
+
+	#[test]
+	fn random_playouts_keep_the_invariants(fen_index in 0..TEST_FENS.len(), choices in prop::collection::vec(any::<u16>(), 1..60)) {
+		let mut game = Game::try_from_fen(TEST_FENS[fen_index]).unwrap();
+		let start = game.position;
+		// Some test positions leave the side that just moved in check, the king could be taken there
+		let opponent = start.side_to_move.opponent();
+		prop_assume!(!naive_is_attacked(&start, king_square(&start, opponent), opponent.opponent()));
+
+		for choice in choices {
+			check_position(&game.position)?;
+			check_moves(&game.position)?;
+
+			let side = game.position.side_to_move;
+			if matches!(game.game_status, GameStatus::Playing | GameStatus::InCheck) {
+				let naive = naive_is_attacked(&game.position, king_square(&game.position, side), side.opponent());
+				prop_assert_eq!(game.game_status == GameStatus::InCheck, naive);
+			}
+
+			if !game.is_legal_game_state() {
+				break;
+			}
+
+			let legal_moves = game.position.get_all_legal_moves_for_side(side).unwrap();
+			let mv = legal_moves[choice as usize % legal_moves.len()];
+			game.make_move(&mv).unwrap();
+		}
+
+		while !game.move_history.is_empty() {
+			game.undo_last_move().unwrap();
+		}
+		prop_assert_eq!(game.position, start);
+		prop_assert!(game.hash_history.is_empty());
+	}
+
+	#[test]
+	fn fen_parsing_never_panics(board in "[pnbrqkPNBRQK1-9/]{0,72}", side in "[wb]", castling in "-|K?Q?k?q?", en_passant in "-|[a-h][1-8]") {
+		let fen = format!("{} {} {} {} 0 1", board, side, castling, en_passant);
+		if let Ok(position) = load_position_from_fen(&fen) {
+			prop_assert_eq!(position.zobrist_hash, position.compute_hash());
+		}
+	}
+
+	#[test]
+	fn arbitrary_text_is_no_fen(fen in "\\PC*") {
+		let _ = load_position_from_fen(&fen);
+	}
+
+	#[test]
+	fn malformed_uci_is_rejected(uci in "\\PC{0,6}") {
+		let mut game = Game::try_from_fen(STARTING_FEN).unwrap();
+
+		if game.make_move_from_uci(&uci).is_ok() {
+			prop_assert_eq!(game.move_history.last().map(|mv| mv.to_uci()), Some(uci));
+		}
+	}
+
+	#[test]
+	fn uci_input_never_corrupts_the_game(moves in prop::collection::vec("[a-h][1-8][a-h][1-8][qrbnk]?", 1..40)) {
+		let mut game = Game::try_from_fen(STARTING_FEN).unwrap();
+
+		for uci in moves {
+			let before = game.position;
+			match game.make_move_from_uci(&uci) {
+				Ok(()) => check_position(&game.position)?,
+				Err(_) => prop_assert_eq!(game.position, before, "rejected move {} changed the position", uci),
+			}
+		}
+	}
+}
@@ -1,7 +1,8 @@
 use super::*;
 use crate::game_status::GameStatus;
 use crate::position::Position;
-use crate::test_common::{empty_game, empty_position, game_from_fen};
+use crate::position::load_position_from_fen;
+use crate::test_common::{TEST_FENS, empty_game, empty_position, game_from_fen};
 use crate::zobrist::zobrist;
 use crate::{ColoredPiece, Piece, Side, errors::ChessError};
 
@@ -89,6 +90,27 @@ fn make_move_errors_if_move_not_in_legal_list() {
 	assert!(matches!(game.make_move(&illegal), Err(ChessError::NotAValidMove)));
 }
 
+#[test]
+fn make_move_errors_if_piece_belongs_to_the_opponent() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let black_pawn = game.position.board[51].unwrap();
+
+	let mv = Move {
+		from_square: 51,
+		to_square: 35,
+		move_kind: MoveKind::DoublePawnPush { passed_square: 43 },
+		colored_piece: black_pawn,
+	};
+	assert_eq!(
+		game.make_move(&mv),
+		Err(ChessError::WrongSide {
+			expected_side: Side::White,
+			found_side: Side::Black
+		})
+	);
+	assert_eq!(game.position.board[51], Some(black_pawn));
+}
+
 #[test]
 fn promotion_errors_if_promotion_piece_is_none() {
 	let mut game = empty_game();
@@ -2495,3 +2517,186 @@ fn draw_offer_cleared_after_decline_cannot_be_accepted() {
 	// Now White tries to accept — there is no pending offer.
 	assert!(matches!(game.accept_draw(), Err(ChessError::NoDrawOffered)));
 }
+
+// ══════════════════════════════════════════════════════════════════════════════
+// redo / goto_ply tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+fn game_after(moves: &[&str]) -> crate::position::Game {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	for uci in moves {
+		game.make_move_from_uci(uci).unwrap();
+	}
+	game
+}
+
+#[test]
+fn redo_replays_undone_move() {
+	let mut game = game_after(&["e2e4", "e7e5"]);
+	let position_after = game.position;
+	let hashes_after = game.hash_history.clone();
+
+	game.undo_last_move().unwrap();
+	game.redo().unwrap();
+
+	assert_eq!(game.position, position_after);
+	assert_eq!(game.hash_history, hashes_after);
+	assert!(game.redo_history.is_empty());
+}
+
+#[test]
+fn redo_multiple_moves_in_order() {
+	let mut game = game_after(&["e2e4", "e7e5", "g1f3"]);
+	let position_after = game.position;
+
+	game.undo_last_move().unwrap();
+	game.undo_last_move().unwrap();
+	game.undo_last_move().unwrap();
+	game.redo().unwrap();
+	game.redo().unwrap();
+	game.redo().unwrap();
+
+	assert_eq!(game.position, position_after);
+	assert_eq!(game.move_history.len(), 3);
+}
+
+#[test]
+fn redo_with_nothing_undone_fails() {
+	let mut game = game_after(&["e2e4"]);
+
+	assert_eq!(game.redo(), Err(ChessError::NothingToRedo));
+}
+
+#[test]
+fn divergent_move_clears_redo_history() {
+	let mut game = game_after(&["e2e4", "e7e5"]);
+	game.undo_last_move().unwrap();
+
+	game.make_move_from_uci("c7c5").unwrap();
+
+	assert!(game.redo_history.is_empty());
+	assert_eq!(game.redo(), Err(ChessError::NothingToRedo));
+}
+
+#[test]
+fn replaying_the_undone_move_keeps_redo_history() {
+	let mut game = game_after(&["e2e4", "e7e5", "g1f3"]);
+	game.undo_last_move().unwrap();
+	game.undo_last_move().unwrap();
+
+	game.make_move_from_uci("e7e5").unwrap();
+
+	assert_eq!(game.redo_history.len(), 1);
+	game.redo().unwrap();
+	assert_eq!(game.move_history.len(), 3);
+}
+
+#[test]
+fn redo_restores_draw_offer() {
+	let mut game = game_after(&["e2e4"]);
+	game.offer_draw().unwrap();
+	game.make_move_from_uci("e7e5").unwrap();
+	let offer_after = game.draw_offer;
+
+	game.undo_last_move().unwrap();
+	assert_eq!(game.draw_offer, Some(Side::Black));
+	game.redo().unwrap();
+
+	assert_eq!(game.draw_offer, offer_after);
+}
+
+#[test]
+fn redo_restores_checkmate_status() {
+	let mut game = game_after(&["f2f3", "e7e5", "g2g4", "d8h4"]);
+	assert!(game.game_status == GameStatus::CheckmateForSide(Side::Black));
+
+	game.undo_last_move().unwrap();
+	assert!(game.game_status == GameStatus::Playing);
+	game.redo().unwrap();
+
+	assert!(game.game_status == GameStatus::CheckmateForSide(Side::Black));
+}
+
+#[test]
+fn goto_ply_back_and_forward() {
+	let mut game = game_after(&["e2e4", "e7e5", "g1f3", "b8c6"]);
+	let final_position = game.position;
+
+	game.goto_ply(0).unwrap();
+	assert_eq!(game.position.export_position_to_fen().unwrap(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	assert!(game.hash_history.is_empty());
+	assert_eq!(game.redo_history.len(), 4);
+
+	game.goto_ply(2).unwrap();
+	assert_eq!(game.position.export_position_to_fen().unwrap(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+	assert_eq!(game.hash_history.len(), 2);
+
+	game.goto_ply(4).unwrap();
+	assert_eq!(game.position, final_position);
+}
+
+#[test]
+fn goto_ply_out_of_range_fails() {
+	let mut game = game_after(&["e2e4", "e7e5"]);
+	game.undo_last_move().unwrap();
+
+	assert_eq!(game.goto_ply(3), Err(ChessError::PlyOutOfRange { ply: 3 }));
+	assert_eq!(game.move_history.len(), 1, "a failed goto_ply must not move");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Null move
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn null_move_flips_side_and_clears_en_passant() {
+	let mut pos = load_position_from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2").unwrap();
+
+	pos.make_null_move();
+
+	assert_eq!(pos.side_to_move, Side::Black);
+	assert_eq!(pos.en_passant, None);
+	assert_eq!(pos.halfmove_clock, 1);
+	assert_eq!(pos.zobrist_hash, pos.compute_hash());
+}
+
+#[test]
+fn null_move_by_black_increments_fullmove_counter() {
+	let mut pos = load_position_from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+
+	pos.make_null_move();
+
+	assert_eq!(pos.fullmove_counter, 2);
+	assert_eq!(pos.zobrist_hash, pos.compute_hash());
+}
+
+#[test]
+fn undo_null_move_restores_position() {
+	for fen in TEST_FENS {
+		let mut pos = load_position_from_fen(fen).unwrap();
+		let before = pos;
+
+		let undo = pos.make_null_move();
+		pos.undo_null_move(undo);
+
+		assert_eq!(pos, before, "{}", fen);
+	}
+}
+
+#[test]
+fn null_move_then_moves_keep_hash_consistent() {
+	let mut pos = load_position_from_fen("r3k2r/pppq1ppp/2n2n2/3pp3/3PP3/2N2N2/PPPQ1PPP/R3K2R w KQkq d6 0 7").unwrap();
+
+	let undo = pos.make_null_move();
+	let moves = pos.get_all_legal_moves_for_side(Side::Black).unwrap();
+	assert!(!moves.is_empty());
+
+	for mv in moves {
+		let move_undo = pos.make_move_unvalidated(mv).unwrap();
+		assert_eq!(pos.zobrist_hash, pos.compute_hash());
+		pos.undo_move(move_undo, mv).unwrap();
+	}
+
+	pos.undo_null_move(undo);
+	assert_eq!(pos.zobrist_hash, pos.compute_hash());
+}
@@ -1,2 +1,3 @@
+pub mod legality;
 pub mod make_move;
 pub(crate) mod move_gen;
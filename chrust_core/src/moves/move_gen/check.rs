@@ -33,6 +33,12 @@ impl Position {
 				continue;
 			}
 
+			// Offsets of 7 and 9 wrap around from the a to the h file and back
+			let file_diff = (file_rank(attack_square as u8).0 as i16 - file_rank(from_square).0 as i16).abs();
+			if file_diff != 1 {
+				continue;
+			}
+
 			let Some(target) = self.board[attack_square as usize] else {
 				continue;
 			};
@@ -94,6 +94,20 @@ fn is_square_attacked_king_does_not_wrap_board_edge() {
 	assert_eq!(pos.is_square_attacked(7, Side::White), Ok(None));
 }
 
+#[test]
+fn is_square_attacked_pawn_does_not_wrap_board_edge() {
+	let mut pos = empty_position();
+
+	// A white pawn on a7 attacks b8 only, not h7 one square to the "left" of a8
+	pos.board[48] = Some(ColoredPiece { piece: Piece::Pawn, side: Side::White });
+	pos.board[15] = Some(ColoredPiece { piece: Piece::Pawn, side: Side::Black });
+
+	assert_eq!(pos.is_square_attacked(55, Side::White), Ok(None));
+	assert_eq!(pos.is_square_attacked(57, Side::White), Ok(Some(vec![48])));
+	assert_eq!(pos.is_square_attacked(0, Side::Black), Ok(None));
+	assert_eq!(pos.is_square_attacked(6, Side::Black), Ok(Some(vec![15])));
+}
+
 #[test]
 fn is_square_attacked_by_knight_l_shape() {
 	let mut pos = empty_position();
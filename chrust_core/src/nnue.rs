@@ -0,0 +1,418 @@
+use std::{path::Path, sync::Arc};
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	errors::NnueError,
+	moves::make_move::{Move, MoveKind},
+	position::{Position, Undo},
+	search::MATE_THRESHOLD,
+};
+
+const MAGIC: &[u8; 8] = b"CHRNNUE1";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 16;
+const SIMD_WIDTH: usize = 16;
+
+/// HalfKA inputs: own king square, piece (own pieces first) and piece square, all seen from the
+/// perspectives side of the board
+pub const INPUT_SIZE: usize = 64 * 12 * 64;
+
+/// Clipped ReLU ceiling of the quantized hidden layer
+const QA: i32 = 255;
+const QB: i32 = 64;
+const EVAL_SCALE: i32 = 400;
+/// Evaluations stay below the mate scores of the search
+const MAX_EVAL: i32 = MATE_THRESHOLD - 1;
+
+/// A quantized `INPUT_SIZE -> hidden_size (x2 perspectives) -> 1` network.
+///
+/// File layout, all little endian: `CHRNNUE1`, version `u32`, hidden size `u32`, feature weights
+/// `i16` (input major), feature biases `i16`, output weights `i16` (side to move first), output bias `i32`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Network {
+	hidden_size: usize,
+	feature_weights: Vec<i16>,
+	feature_biases: Vec<i16>,
+	output_weights: Vec<i16>,
+	output_bias: i32,
+}
+
+/// Keeps one accumulator per ply so moves can be applied and taken back without recomputing the
+/// hidden layer. Only a king move refreshes the accumulator of its own side.
+///
+/// The evaluator doesn't see the position change: whoever makes and takes back moves has to call
+/// `push_move` and `pop` along, as the search does. Moves made directly with
+/// `Position::make_move_unvalidated` or `Game::make_move` leave it behind until `reset`.
+pub struct NnueEvaluator {
+	network: Arc<Network>,
+	stack: Vec<Accumulator>,
+	current: usize,
+}
+
+#[derive(Clone)]
+struct Accumulator {
+	values: [Vec<i16>; 2],
+}
+
+impl Network {
+	pub fn new(hidden_size: usize, feature_weights: Vec<i16>, feature_biases: Vec<i16>, output_weights: Vec<i16>, output_bias: i32) -> Result<Network, NnueError> {
+		if hidden_size == 0 || !hidden_size.is_multiple_of(SIMD_WIDTH) {
+			return Err(NnueError::InvalidHiddenSize(hidden_size as u32));
+		}
+
+		for (expected, found) in [
+			(INPUT_SIZE * hidden_size, feature_weights.len()),
+			(hidden_size, feature_biases.len()),
+			(2 * hidden_size, output_weights.len()),
+		] {
+			if expected != found {
+				return Err(NnueError::InvalidFileSize { expected, found });
+			}
+		}
+
+		Ok(Network {
+			hidden_size,
+			feature_weights,
+			feature_biases,
+			output_weights,
+			output_bias,
+		})
+	}
+
+	pub fn load(path: impl AsRef<Path>) -> Result<Network, NnueError> {
+		let bytes = std::fs::read(path).map_err(|e| NnueError::Io(e.kind()))?;
+
+		Network::from_bytes(&bytes)
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Network, NnueError> {
+		if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+			return Err(NnueError::InvalidMagic);
+		}
+
+		let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+		if version != VERSION {
+			return Err(NnueError::UnsupportedVersion(version));
+		}
+
+		let hidden_size = u32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+		if hidden_size == 0 || !hidden_size.is_multiple_of(SIMD_WIDTH) {
+			return Err(NnueError::InvalidHiddenSize(hidden_size as u32));
+		}
+
+		let expected = file_size(hidden_size);
+		if bytes.len() != expected {
+			return Err(NnueError::InvalidFileSize { expected, found: bytes.len() });
+		}
+
+		let mut offset = HEADER_SIZE;
+		let mut read_i16s = |count: usize| {
+			let values = bytes[offset..offset + 2 * count].chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect::<Vec<i16>>();
+			offset += 2 * count;
+			values
+		};
+
+		let feature_weights = read_i16s(INPUT_SIZE * hidden_size);
+		let feature_biases = read_i16s(hidden_size);
+		let output_weights = read_i16s(2 * hidden_size);
+		let output_bias = i32::from_le_bytes(bytes[expected - 4..].try_into().unwrap());
+
+		Network::new(hidden_size, feature_weights, feature_biases, output_weights, output_bias)
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(file_size(self.hidden_size));
+
+		bytes.extend_from_slice(MAGIC);
+		bytes.extend_from_slice(&VERSION.to_le_bytes());
+		bytes.extend_from_slice(&(self.hidden_size as u32).to_le_bytes());
+		for value in self.feature_weights.iter().chain(&self.feature_biases).chain(&self.output_weights) {
+			bytes.extend_from_slice(&value.to_le_bytes());
+		}
+		bytes.extend_from_slice(&self.output_bias.to_le_bytes());
+
+		bytes
+	}
+
+	pub fn hidden_size(&self) -> usize {
+		self.hidden_size
+	}
+
+	/// Evaluates from scratch, in centipawns from the point of view of the side to move
+	pub fn evaluate(&self, position: &Position) -> i32 {
+		let accumulator = Accumulator {
+			values: [
+				self.refresh(position, Side::White),
+				self.refresh(position, Side::Black),
+			],
+		};
+
+		self.output(&accumulator, position.side_to_move)
+	}
+
+	fn refresh(&self, position: &Position, perspective: Side) -> Vec<i16> {
+		let mut values = self.feature_biases.clone();
+		let king_square = position.king_squares[side_index(perspective)];
+
+		for (square, colored_piece) in position.board.iter().enumerate() {
+			if let Some(colored_piece) = colored_piece {
+				kernels::add(&mut values, self.feature_column(feature_index(perspective, king_square, *colored_piece, square as Square)));
+			}
+		}
+
+		values
+	}
+
+	fn output(&self, accumulator: &Accumulator, side_to_move: Side) -> i32 {
+		let us = &accumulator.values[side_index(side_to_move)];
+		let them = &accumulator.values[side_index(side_to_move.opponent())];
+		let sum = kernels::output(us, them, &self.output_weights) as i64;
+
+		let eval = (sum + self.output_bias as i64) * EVAL_SCALE as i64 / (QA * QB) as i64;
+		eval.clamp(-MAX_EVAL as i64, MAX_EVAL as i64) as i32
+	}
+
+	fn feature_column(&self, feature: usize) -> &[i16] {
+		&self.feature_weights[feature * self.hidden_size..(feature + 1) * self.hidden_size]
+	}
+}
+
+impl NnueEvaluator {
+	pub fn new(network: Arc<Network>, position: &Position) -> NnueEvaluator {
+		let mut evaluator = NnueEvaluator { network, stack: Vec::new(), current: 0 };
+		evaluator.reset(position);
+
+		evaluator
+	}
+
+	/// Throws away the move stack and starts over from `position`
+	pub fn reset(&mut self, position: &Position) {
+		let accumulator = Accumulator {
+			values: [
+				self.network.refresh(position, Side::White),
+				self.network.refresh(position, Side::Black),
+			],
+		};
+
+		self.stack.clear();
+		self.stack.push(accumulator);
+		self.current = 0;
+	}
+
+	/// Applies `mv`. `position` is the position after the move and `undo` the undo returned by it.
+	pub fn push_move(&mut self, position: &Position, mv: &Move, undo: &Undo) {
+		self.push_copy();
+
+		for perspective in [Side::White, Side::Black] {
+			let index = side_index(perspective);
+
+			if mv.colored_piece.piece == Piece::King && mv.colored_piece.side == perspective {
+				self.stack[self.current].values[index] = self.network.refresh(position, perspective);
+				continue;
+			}
+
+			let king_square = position.king_squares[index];
+			let network = &self.network;
+			let values = &mut self.stack[self.current].values[index];
+			let column = |piece: ColoredPiece, square: Square| network.feature_column(feature_index(perspective, king_square, piece, square));
+
+			kernels::sub(values, column(mv.colored_piece, mv.from_square));
+			if let Some(moved) = position.board[mv.to_square as usize] {
+				kernels::add(values, column(moved, mv.to_square));
+			}
+
+			if let Some(captured) = undo.captured_piece {
+				let capture_square = match mv.move_kind {
+					MoveKind::EnPassant { capture_square } => capture_square,
+					_ => mv.to_square,
+				};
+				kernels::sub(values, column(captured, capture_square));
+			}
+
+			if let MoveKind::Castling { rook_from, rook_to } = mv.move_kind {
+				let rook = ColoredPiece {
+					piece: Piece::Rook,
+					side: mv.colored_piece.side,
+				};
+				kernels::sub(values, column(rook, rook_from));
+				kernels::add(values, column(rook, rook_to));
+			}
+		}
+	}
+
+	/// Passing the turn doesn't change any feature
+	pub fn push_null_move(&mut self) {
+		self.push_copy();
+	}
+
+	pub fn pop(&mut self) {
+		self.current = self.current.saturating_sub(1);
+	}
+
+	/// Evaluates the current position in centipawns from the point of view of `side_to_move`
+	pub fn evaluate(&self, side_to_move: Side) -> i32 {
+		self.network.output(&self.stack[self.current], side_to_move)
+	}
+
+	// Reuses the accumulators above the current one so pushing doesn't allocate after warming up
+	fn push_copy(&mut self) {
+		if self.current + 1 == self.stack.len() {
+			self.stack.push(self.stack[self.current].clone());
+		} else {
+			let (below, above) = self.stack.split_at_mut(self.current + 1);
+			for (target, source) in above[0].values.iter_mut().zip(&below[self.current].values) {
+				target.copy_from_slice(source);
+			}
+		}
+		self.current += 1;
+	}
+}
+
+fn file_size(hidden_size: usize) -> usize {
+	HEADER_SIZE + 2 * (INPUT_SIZE * hidden_size + hidden_size + 2 * hidden_size) + 4
+}
+
+fn side_index(side: Side) -> usize {
+	match side {
+		Side::White => 0,
+		Side::Black => 1,
+	}
+}
+
+/// Black sees the board flipped, so both perspectives share the same weights
+fn feature_index(perspective: Side, king_square: Square, colored_piece: ColoredPiece, square: Square) -> usize {
+	let (king_square, square) = match perspective {
+		Side::White => (king_square, square),
+		Side::Black => (king_square ^ 56, square ^ 56),
+	};
+
+	let piece = match colored_piece.piece {
+		Piece::Pawn => 0,
+		Piece::Knight => 1,
+		Piece::Bishop => 2,
+		Piece::Rook => 3,
+		Piece::Queen => 4,
+		Piece::King => 5,
+	} + if colored_piece.side == perspective {
+		0
+	} else {
+		6
+	};
+
+	(king_square as usize * 12 + piece) * 64 + square as usize
+}
+
+/// Accumulator and output layer loops, with AVX2 versions picked at runtime where the CPU has it
+pub(crate) mod kernels {
+	pub(crate) fn add(values: &mut [i16], weights: &[i16]) {
+		#[cfg(target_arch = "x86_64")]
+		if std::is_x86_feature_detected!("avx2") {
+			// SAFETY: AVX2 support was checked right above
+			return unsafe { avx2::add(values, weights) };
+		}
+
+		scalar::add(values, weights);
+	}
+
+	pub(crate) fn sub(values: &mut [i16], weights: &[i16]) {
+		#[cfg(target_arch = "x86_64")]
+		if std::is_x86_feature_detected!("avx2") {
+			// SAFETY: AVX2 support was checked right above
+			return unsafe { avx2::sub(values, weights) };
+		}
+
+		scalar::sub(values, weights);
+	}
+
+	/// Dot product of the clipped hidden layer, side to move first, with the output weights
+	pub(crate) fn output(us: &[i16], them: &[i16], weights: &[i16]) -> i32 {
+		#[cfg(target_arch = "x86_64")]
+		if std::is_x86_feature_detected!("avx2") {
+			// SAFETY: AVX2 support was checked right above
+			return unsafe { avx2::output(us, them, weights) };
+		}
+
+		scalar::output(us, them, weights)
+	}
+
+	pub(crate) mod scalar {
+		use super::super::QA;
+
+		pub(crate) fn add(values: &mut [i16], weights: &[i16]) {
+			for (value, weight) in values.iter_mut().zip(weights) {
+				*value = value.wrapping_add(*weight);
+			}
+		}
+
+		pub(crate) fn sub(values: &mut [i16], weights: &[i16]) {
+			for (value, weight) in values.iter_mut().zip(weights) {
+				*value = value.wrapping_sub(*weight);
+			}
+		}
+
+		pub(crate) fn output(us: &[i16], them: &[i16], weights: &[i16]) -> i32 {
+			us.iter().chain(them).zip(weights).map(|(value, weight)| (*value as i32).clamp(0, QA) * *weight as i32).sum()
+		}
+	}
+
+	#[cfg(target_arch = "x86_64")]
+	pub(crate) mod avx2 {
+		use std::arch::x86_64::*;
+
+		use super::super::{QA, SIMD_WIDTH};
+
+		/// # Safety
+		/// The CPU has to support AVX2
+		#[target_feature(enable = "avx2")]
+		pub(crate) unsafe fn add(values: &mut [i16], weights: &[i16]) {
+			for (value, weight) in values.chunks_exact_mut(SIMD_WIDTH).zip(weights.chunks_exact(SIMD_WIDTH)) {
+				// SAFETY: both chunks are exactly 16 i16 long, unaligned loads and stores are used
+				unsafe {
+					let sum = _mm256_add_epi16(_mm256_loadu_si256(value.as_ptr().cast()), _mm256_loadu_si256(weight.as_ptr().cast()));
+					_mm256_storeu_si256(value.as_mut_ptr().cast(), sum);
+				}
+			}
+		}
+
+		/// # Safety
+		/// The CPU has to support AVX2
+		#[target_feature(enable = "avx2")]
+		pub(crate) unsafe fn sub(values: &mut [i16], weights: &[i16]) {
+			for (value, weight) in values.chunks_exact_mut(SIMD_WIDTH).zip(weights.chunks_exact(SIMD_WIDTH)) {
+				// SAFETY: both chunks are exactly 16 i16 long, unaligned loads and stores are used
+				unsafe {
+					let difference = _mm256_sub_epi16(_mm256_loadu_si256(value.as_ptr().cast()), _mm256_loadu_si256(weight.as_ptr().cast()));
+					_mm256_storeu_si256(value.as_mut_ptr().cast(), difference);
+				}
+			}
+		}
+
+		/// # Safety
+		/// The CPU has to support AVX2
+		#[target_feature(enable = "avx2")]
+		pub(crate) unsafe fn output(us: &[i16], them: &[i16], weights: &[i16]) -> i32 {
+			let zero = _mm256_setzero_si256();
+			let ceiling = _mm256_set1_epi16(QA as i16);
+			let mut sum = _mm256_setzero_si256();
+
+			let (us_weights, them_weights) = weights.split_at(us.len());
+			for (values, weights) in [(us, us_weights), (them, them_weights)] {
+				for (value, weight) in values.chunks_exact(SIMD_WIDTH).zip(weights.chunks_exact(SIMD_WIDTH)) {
+					// SAFETY: both chunks are exactly 16 i16 long and loaded unaligned
+					let (value, weight) = unsafe { (_mm256_loadu_si256(value.as_ptr().cast()), _mm256_loadu_si256(weight.as_ptr().cast())) };
+					let clipped = _mm256_min_epi16(_mm256_max_epi16(value, zero), ceiling);
+					sum = _mm256_add_epi32(sum, _mm256_madd_epi16(clipped, weight));
+				}
+			}
+
+			let mut lanes = [0i32; 8];
+			// SAFETY: `lanes` is 32 bytes long
+			unsafe { _mm256_storeu_si256(lanes.as_mut_ptr().cast(), sum) };
+
+			lanes.iter().sum()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
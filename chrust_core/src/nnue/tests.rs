@@ -0,0 +1,208 @@
+use super::*;
+use crate::{
+	position::load_position_from_fen,
+	search::{Search, SearchOptions, mate_in},
+	test_common::TEST_FENS,
+	time_manager::SearchLimits,
+};
+
+/// Small network with deterministic pseudo random weights
+fn test_network(hidden_size: usize) -> Network {
+	let mut state = 0x2545_F491_4F6C_DD1Du64;
+	let mut next = |range: i16| {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		(state % (2 * range as u64 + 1)) as i16 - range
+	};
+
+	let feature_weights = (0..INPUT_SIZE * hidden_size).map(|_| next(40)).collect();
+	let feature_biases = (0..hidden_size).map(|_| next(100)).collect();
+	let output_weights = (0..2 * hidden_size).map(|_| next(64)).collect();
+
+	Network::new(hidden_size, feature_weights, feature_biases, output_weights, 1234).unwrap()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Network file tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn network_round_trips_through_bytes() {
+	let network = test_network(16);
+
+	assert_eq!(Network::from_bytes(&network.to_bytes()), Ok(network));
+}
+
+#[test]
+fn network_loads_from_file() {
+	let network = test_network(32);
+	let path = std::env::temp_dir().join(format!("chrust_nnue_test_{}.bin", std::process::id()));
+	std::fs::write(&path, network.to_bytes()).unwrap();
+
+	let loaded = Network::load(&path);
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(loaded, Ok(network));
+}
+
+#[test]
+fn missing_file_is_io_error() {
+	assert_eq!(Network::load("/nonexistent/chrust.nnue"), Err(NnueError::Io(std::io::ErrorKind::NotFound)));
+}
+
+#[test]
+fn invalid_files_are_rejected() {
+	let bytes = test_network(16).to_bytes();
+
+	assert_eq!(Network::from_bytes(b"NOTANNUE"), Err(NnueError::InvalidMagic));
+
+	let mut wrong_version = bytes.clone();
+	wrong_version[8] = 9;
+	assert_eq!(Network::from_bytes(&wrong_version), Err(NnueError::UnsupportedVersion(9)));
+
+	let mut wrong_hidden_size = bytes.clone();
+	wrong_hidden_size[12] = 15;
+	assert_eq!(Network::from_bytes(&wrong_hidden_size), Err(NnueError::InvalidHiddenSize(15)));
+
+	assert_eq!(
+		Network::from_bytes(&bytes[..bytes.len() - 1]),
+		Err(NnueError::InvalidFileSize {
+			expected: bytes.len(),
+			found: bytes.len() - 1
+		})
+	);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Evaluation tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn incremental_updates_match_full_refresh() {
+	let network = Arc::new(test_network(16));
+
+	for fen in TEST_FENS {
+		let mut position = load_position_from_fen(fen).unwrap();
+		let mut evaluator = NnueEvaluator::new(Arc::clone(&network), &position);
+		let root_eval = evaluator.evaluate(position.side_to_move);
+
+		for mv in position.get_all_legal_moves_for_side(position.side_to_move).unwrap() {
+			let undo = position.make_move_unvalidated(mv).unwrap();
+			evaluator.push_move(&position, &mv, &undo);
+			assert_eq!(evaluator.evaluate(position.side_to_move), network.evaluate(&position), "{} {:?}", fen, mv);
+
+			// One more ply so the stack is reused
+			if let Some(reply) = position.get_all_legal_moves_for_side(position.side_to_move).unwrap().first().copied() {
+				let reply_undo = position.make_move_unvalidated(reply).unwrap();
+				evaluator.push_move(&position, &reply, &reply_undo);
+				assert_eq!(evaluator.evaluate(position.side_to_move), network.evaluate(&position), "{} {:?} {:?}", fen, mv, reply);
+				evaluator.pop();
+				position.undo_move(reply_undo, reply).unwrap();
+			}
+
+			evaluator.pop();
+			position.undo_move(undo, mv).unwrap();
+		}
+
+		assert_eq!(evaluator.evaluate(position.side_to_move), root_eval, "{}", fen);
+	}
+}
+
+#[test]
+fn null_move_keeps_features() {
+	let network = Arc::new(test_network(16));
+	let mut position = load_position_from_fen("r3k2r/pppq1ppp/2n2n2/3pp3/3PP3/2N2N2/PPPQ1PPP/R3K2R w KQkq d6 0 7").unwrap();
+	let mut evaluator = NnueEvaluator::new(Arc::clone(&network), &position);
+
+	let undo = position.make_null_move();
+	evaluator.push_null_move();
+	assert_eq!(evaluator.evaluate(position.side_to_move), network.evaluate(&position));
+
+	evaluator.pop();
+	position.undo_null_move(undo);
+	assert_eq!(evaluator.evaluate(position.side_to_move), network.evaluate(&position));
+}
+
+#[test]
+fn large_output_bias_is_clamped_below_mate_scores() {
+	let mut network = test_network(16);
+	let position = load_position_from_fen("r3k2r/pppq1ppp/2n2n2/3pp3/3PP3/2N2N2/PPPQ1PPP/R3K2R w KQkq d6 0 7").unwrap();
+
+	network.output_bias = 1_000_000_000;
+	assert_eq!(network.evaluate(&position), MAX_EVAL);
+
+	network.output_bias = -1_000_000_000;
+	assert_eq!(network.evaluate(&position), -MAX_EVAL);
+}
+
+#[test]
+fn mirrored_position_evaluates_the_same() {
+	let network = test_network(16);
+	let white = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let black = load_position_from_fen("rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3").unwrap();
+
+	assert_eq!(network.evaluate(&white), network.evaluate(&black));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn simd_kernels_match_scalar() {
+	if !std::is_x86_feature_detected!("avx2") {
+		return;
+	}
+
+	let network = test_network(32);
+	let weights = &network.feature_weights[..64];
+	let mut scalar_values: Vec<i16> = (0..32).map(|i| i * 37 - 500).collect();
+	let mut simd_values = scalar_values.clone();
+
+	kernels::scalar::add(&mut scalar_values, &weights[..32]);
+	kernels::scalar::sub(&mut scalar_values, &weights[32..]);
+	// SAFETY: AVX2 support was checked above
+	unsafe {
+		kernels::avx2::add(&mut simd_values, &weights[..32]);
+		kernels::avx2::sub(&mut simd_values, &weights[32..]);
+	}
+	assert_eq!(scalar_values, simd_values);
+
+	let them: Vec<i16> = (0..32).map(|i| 300 - i * 13).collect();
+	// SAFETY: AVX2 support was checked above
+	let simd_output = unsafe { kernels::avx2::output(&simd_values, &them, &network.output_weights) };
+	assert_eq!(kernels::scalar::output(&scalar_values, &them, &network.output_weights), simd_output);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Search tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn search_with_network_finds_mate() {
+	let position = load_position_from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+	let mut search = Search::new(SearchOptions::default());
+	search.set_network(Some(Arc::new(test_network(16))));
+
+	let limits = SearchLimits { depth: Some(3), ..Default::default() };
+	let result = search.search(&position, &[], &limits).unwrap();
+
+	assert_eq!(mate_in(result.score), Some(1));
+	assert_eq!(result.best_move.map(|mv| mv.to_square), Some(56));
+}
+
+#[test]
+fn search_backend_changes_score() {
+	let position = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let limits = SearchLimits { depth: Some(1), ..Default::default() };
+	let mut search = Search::new(SearchOptions::default());
+
+	let handcrafted = search.search(&position, &[], &limits).unwrap();
+	search.set_network(Some(Arc::new(test_network(16))));
+	search.new_game();
+	let nnue = search.search(&position, &[], &limits).unwrap();
+	search.set_network(None);
+	search.new_game();
+	let back = search.search(&position, &[], &limits).unwrap();
+
+	assert!(handcrafted.score != nnue.score);
+	assert_eq!(handcrafted, back);
+}
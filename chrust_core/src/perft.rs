@@ -0,0 +1,156 @@
+//! Move generation testing: counts the leaf nodes of the legal move tree to a fixed depth.
+
+use std::{
+	sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+	thread,
+};
+
+use crate::{errors::ChessError, moves::make_move::Move, position::Position};
+
+const ENTRY_SIZE: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PerftOptions {
+	pub threads: usize,
+	/// Size of the table caching subtree counts by position, 0 disables it
+	pub hash_size_mb: usize,
+}
+
+impl Default for PerftOptions {
+	fn default() -> Self {
+		PerftOptions { threads: 1, hash_size_mb: 0 }
+	}
+}
+
+/// Node counts by position and depth, shared between threads without locks the same way as the
+/// transposition table
+pub struct PerftTable {
+	slots: Vec<Slot>,
+}
+
+struct Slot {
+	key: AtomicU64,
+	data: AtomicU64,
+}
+
+impl PerftTable {
+	pub fn new(size_mb: usize) -> PerftTable {
+		let count = (size_mb * 1024 * 1024 / ENTRY_SIZE).max(1);
+
+		PerftTable {
+			slots: (0..count)
+				.map(|_| Slot {
+					key: AtomicU64::new(0),
+					data: AtomicU64::new(0),
+				})
+				.collect(),
+		}
+	}
+
+	pub fn probe(&self, hash: u64, depth: u8) -> Option<u64> {
+		let slot = self.slot(hash);
+		let data = slot.data.load(Ordering::Relaxed);
+
+		if data == 0 || slot.key.load(Ordering::Relaxed) ^ data != hash || (data & 0xFF) as u8 != depth {
+			return None;
+		}
+
+		Some(data >> 8)
+	}
+
+	pub fn store(&self, hash: u64, depth: u8, nodes: u64) {
+		let slot = self.slot(hash);
+		let data = nodes << 8 | depth as u64;
+
+		slot.key.store(hash ^ data, Ordering::Relaxed);
+		slot.data.store(data, Ordering::Relaxed);
+	}
+
+	fn slot(&self, hash: u64) -> &Slot {
+		let index = ((hash as u128 * self.slots.len() as u128) >> 64) as usize;
+
+		&self.slots[index]
+	}
+}
+
+impl Position {
+	/// Number of leaf nodes of the legal move tree `depth` plies deep
+	pub fn perft(&self, depth: u8) -> Result<u64, ChessError> {
+		let mut position = *self;
+
+		perft_node(&mut position, depth, None)
+	}
+
+	/// Node count below every legal root move, in move generation order
+	pub fn divide(&self, depth: u8, options: &PerftOptions) -> Result<Vec<(Move, u64)>, ChessError> {
+		let mut position = *self;
+		if depth == 0 {
+			return Ok(Vec::new());
+		}
+
+		let root_moves = position.get_all_legal_moves_for_side(position.side_to_move)?;
+		let table = (options.hash_size_mb > 0).then(|| PerftTable::new(options.hash_size_mb));
+		let counts: Vec<AtomicU64> = root_moves.iter().map(|_| AtomicU64::new(0)).collect();
+		let next_move = AtomicUsize::new(0);
+
+		// Threads take the next unsearched root move until none are left
+		thread::scope(|scope| {
+			let workers: Vec<_> = (0..options.threads.clamp(1, root_moves.len().max(1)))
+				.map(|_| {
+					scope.spawn(|| -> Result<(), ChessError> {
+						let mut position = *self;
+
+						loop {
+							let index = next_move.fetch_add(1, Ordering::Relaxed);
+							let Some(&mv) = root_moves.get(index) else {
+								return Ok(());
+							};
+
+							let undo = position.make_move_unvalidated(mv)?;
+							let nodes = perft_node(&mut position, depth - 1, table.as_ref())?;
+							position.undo_move(undo, mv)?;
+
+							counts[index].store(nodes, Ordering::Relaxed);
+						}
+					})
+				})
+				.collect();
+
+			workers.into_iter().try_for_each(|worker| worker.join().expect("perft.rs: perft thread panicked"))
+		})?;
+
+		Ok(root_moves.into_iter().zip(counts).map(|(mv, count)| (mv, count.into_inner())).collect())
+	}
+}
+
+fn perft_node(position: &mut Position, depth: u8, table: Option<&PerftTable>) -> Result<u64, ChessError> {
+	if depth == 0 {
+		return Ok(1);
+	}
+
+	if let Some(nodes) = table.and_then(|table| table.probe(position.zobrist_hash, depth)) {
+		return Ok(nodes);
+	}
+
+	let moves = position.get_all_legal_moves_for_side(position.side_to_move)?;
+	// Bulk counting: the leaves are the legal moves themselves
+	if depth == 1 {
+		return Ok(moves.len() as u64);
+	}
+
+	let mut nodes = 0;
+	for mv in moves {
+		let undo = position.make_move_unvalidated(mv)?;
+		nodes += perft_node(position, depth - 1, table)?;
+		position.undo_move(undo, mv)?;
+	}
+
+	if let Some(table) = table {
+		table.store(position.zobrist_hash, depth, nodes);
+	}
+
+	Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+use crate::position::{STARTING_FEN, load_position_from_fen};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn perft(fen: &str, depth: u8) -> u64 {
+	load_position_from_fen(fen).unwrap().perft(depth).unwrap()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// perft tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn perft_starting_position() {
+	assert_eq!(perft(STARTING_FEN, 0), 1);
+	assert_eq!(perft(STARTING_FEN, 1), 20);
+	assert_eq!(perft(STARTING_FEN, 2), 400);
+	assert_eq!(perft(STARTING_FEN, 3), 8_902);
+}
+
+#[test]
+fn perft_kiwipete() {
+	assert_eq!(perft(KIWIPETE_FEN, 1), 48);
+	assert_eq!(perft(KIWIPETE_FEN, 2), 2_039);
+	assert_eq!(perft(KIWIPETE_FEN, 3), 97_862);
+}
+
+#[test]
+fn perft_en_passant_and_promotions() {
+	assert_eq!(perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4), 43_238);
+	assert_eq!(perft("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 3), 9_467);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// divide tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn divide_sums_to_perft() {
+	let position = load_position_from_fen(KIWIPETE_FEN).unwrap();
+	let divide = position.divide(3, &PerftOptions::default()).unwrap();
+
+	assert_eq!(divide.len(), 48);
+	assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 97_862);
+	assert!(divide.iter().any(|(mv, nodes)| mv.to_uci() == "e1g1" && *nodes == 2_059));
+}
+
+#[test]
+fn threads_and_hash_table_dont_change_the_counts() {
+	let position = load_position_from_fen(KIWIPETE_FEN).unwrap();
+	let options = PerftOptions { threads: 4, hash_size_mb: 1 };
+
+	assert_eq!(position.divide(3, &options).unwrap(), position.divide(3, &PerftOptions::default()).unwrap());
+}
+
+#[test]
+fn divide_at_depth_zero_is_empty() {
+	let position = load_position_from_fen(STARTING_FEN).unwrap();
+
+	assert!(position.divide(0, &PerftOptions::default()).unwrap().is_empty());
+}
+
+#[test]
+fn perft_table_checks_the_depth() {
+	let table = PerftTable::new(1);
+	table.store(0xDEAD_BEEF, 3, 97_862);
+
+	assert_eq!(table.probe(0xDEAD_BEEF, 3), Some(97_862));
+	assert_eq!(table.probe(0xDEAD_BEEF, 2), None);
+	assert_eq!(table.probe(0xBEEF, 3), None);
+}
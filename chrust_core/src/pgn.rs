@@ -0,0 +1,426 @@
+use std::time::Duration;
+
+use crate::{
+	Side,
+	converter::{convert_square_to_string, convert_string_to_square},
+	errors::{ChessError, PgnError},
+	game_status::GameStatus,
+	game_tree::{Annotation, AnnotationColor, GameTree, Node, NodeId, ROOT_NODE},
+	position::{Game, STARTING_FEN},
+};
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+const SUFFIX_GLYPHS: [(&str, u8); 6] = [
+	("!", 1),
+	("?", 2),
+	("!!", 3),
+	("??", 4),
+	("!?", 5),
+	("?!", 6),
+];
+
+impl GameTree {
+	/// Exports the tree as PGN. Variations are written as RAVs, annotations as `[%csl]`/`[%cal]`
+	/// commands inside the comment of their node.
+	pub fn export_pgn(&self) -> Result<String, ChessError> {
+		let mut pgn = String::new();
+		let root = self.node(ROOT_NODE)?;
+
+		let mut tags = self.tags.clone();
+		let fen = root.position.export_position_to_fen()?;
+		if fen != STARTING_FEN && !tags.iter().any(|(name, _)| name == "FEN") {
+			tags.push(("SetUp".to_string(), "1".to_string()));
+			tags.push(("FEN".to_string(), fen));
+		}
+
+		for (name, value) in &tags {
+			pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+		}
+		if !tags.is_empty() {
+			pgn.push('\n');
+		}
+
+		let mut movetext = String::new();
+		if let Some(comment) = comment_token(root) {
+			push_token(&mut movetext, &comment);
+		}
+		if let Some(&first) = root.children.first() {
+			self.write_line(first, true, &mut movetext)?;
+		}
+
+		let result = tags.iter().find(|(name, _)| name == "Result").map(|(_, value)| value.as_str()).unwrap_or("*");
+		push_token(&mut movetext, result);
+
+		pgn.push_str(&movetext);
+		pgn.push('\n');
+
+		Ok(pgn)
+	}
+
+	/// The move leading to `node_id` in SAN, after its move number as in movetext. Black moves only
+	/// get a number if `force_number` is set.
+	pub fn numbered_san(&self, node_id: NodeId, force_number: bool) -> Result<String, ChessError> {
+		let node = self.node(node_id)?;
+		let (Some(parent), Some(mv)) = (node.parent, node.mv) else {
+			return Err(ChessError::NoSuchNode { node_id });
+		};
+		let before = &self.node(parent)?.position;
+		let san = before.move_to_san(&mv)?;
+
+		Ok(match before.side_to_move {
+			Side::White => format!("{}. {}", before.fullmove_counter, san),
+			Side::Black if force_number => format!("{}... {}", before.fullmove_counter, san),
+			Side::Black => san,
+		})
+	}
+
+	fn write_line(&self, start: NodeId, mut force_number: bool, movetext: &mut String) -> Result<(), ChessError> {
+		let mut node_id = start;
+
+		loop {
+			let node = self.node(node_id)?;
+			let parent = self.node(node.parent.expect("pgn.rs: write_line reached the root node"))?;
+			push_token(movetext, &self.numbered_san(node_id, force_number)?);
+
+			for nag in &node.nags {
+				push_token(movetext, &format!("${}", nag));
+			}
+
+			force_number = false;
+			if let Some(comment) = comment_token(node) {
+				push_token(movetext, &comment);
+				force_number = true;
+			}
+
+			// Alternatives to this move are written right after it
+			if parent.children.first() == Some(&node_id) {
+				for &variation in &parent.children[1..] {
+					push_token(movetext, "(");
+					self.write_line(variation, true, movetext)?;
+					push_token(movetext, ")");
+					force_number = true;
+				}
+			}
+
+			match node.children.first() {
+				Some(&next) => node_id = next,
+				None => return Ok(()),
+			}
+		}
+	}
+
+	/// Imports the first game of a PGN string. A `FEN` tag sets the starting position.
+	pub fn from_pgn(pgn: &str) -> Result<GameTree, ChessError> {
+		let chars: Vec<char> = pgn.chars().collect();
+		let mut i = 0;
+
+		let mut tags: Vec<(String, String)> = Vec::new();
+		loop {
+			while i < chars.len() && chars[i].is_whitespace() {
+				i += 1;
+			}
+			if i >= chars.len() || chars[i] != '[' {
+				break;
+			}
+
+			let end = find_tag_end(&chars, i).ok_or_else(|| PgnError::InvalidTag(chars[i..].iter().collect()))?;
+			tags.push(parse_tag(&chars[i + 1..end].iter().collect::<String>())?);
+			i = end + 1;
+		}
+
+		let fen = tags.iter().find(|(name, _)| name == "FEN").map(|(_, value)| value.clone()).unwrap_or(STARTING_FEN.to_string());
+		let mut tree = GameTree::try_from_fen(&fen)?;
+		tree.tags = tags;
+
+		let mut current = ROOT_NODE;
+		let mut variation_stack: Vec<NodeId> = Vec::new();
+
+		while i < chars.len() {
+			match chars[i] {
+				c if c.is_whitespace() => i += 1,
+				'{' => {
+					let end = find_char(&chars, i, '}').ok_or(PgnError::UnterminatedComment)?;
+					tree.append_comment(current, &chars[i + 1..end].iter().collect::<String>())?;
+					i = end + 1;
+				}
+				';' => {
+					let end = find_char(&chars, i, '\n').unwrap_or(chars.len());
+					tree.append_comment(current, &chars[i + 1..end].iter().collect::<String>())?;
+					i = end;
+				}
+				'(' => {
+					let parent = tree.node(current)?.parent.ok_or(PgnError::VariationWithoutMove)?;
+					variation_stack.push(current);
+					current = parent;
+					i += 1;
+				}
+				')' => {
+					current = variation_stack.pop().ok_or(PgnError::UnbalancedVariation)?;
+					i += 1;
+				}
+				'$' => {
+					let end = (i + 1..chars.len()).find(|&j| !chars[j].is_ascii_digit()).unwrap_or(chars.len());
+					let nag_string: String = chars[i..end].iter().collect();
+					let nag = nag_string[1..].parse::<u8>().map_err(|_| PgnError::InvalidNag(nag_string.clone()))?;
+					tree.add_nag(current, nag)?;
+					i = end;
+				}
+				_ => {
+					let end = (i..chars.len()).find(|&j| chars[j].is_whitespace() || "(){};$".contains(chars[j])).unwrap_or(chars.len());
+					let word: String = chars[i..end].iter().collect();
+					i = end;
+
+					if RESULTS.contains(&word.as_str()) {
+						break;
+					}
+
+					let san = strip_move_number(&word);
+					if san.is_empty() {
+						continue;
+					}
+
+					let glyph_start = san.find(['!', '?']).unwrap_or(san.len());
+					let (san, glyphs) = san.split_at(glyph_start);
+
+					let mv = tree.node(current)?.position.san_to_move(san)?;
+					current = tree.add_child(current, &mv)?;
+
+					if !glyphs.is_empty() {
+						let nag = SUFFIX_GLYPHS.iter().find(|(glyph, _)| *glyph == glyphs).map(|(_, nag)| *nag).ok_or_else(|| PgnError::InvalidNag(glyphs.to_string()))?;
+						tree.add_nag(current, nag)?;
+					}
+				}
+			}
+		}
+
+		if !variation_stack.is_empty() {
+			return Err(PgnError::UnbalancedVariation.into());
+		}
+
+		Ok(tree)
+	}
+
+	/// Adds comment text to a node, pulling `[%csl]` and `[%cal]` commands out into annotations
+	fn append_comment(&mut self, node_id: NodeId, raw_comment: &str) -> Result<(), ChessError> {
+		let mut text = String::new();
+		let mut rest = raw_comment;
+
+		while let Some(start) = rest.find("[%") {
+			let end = rest[start..].find(']').map(|e| start + e).ok_or_else(|| PgnError::InvalidAnnotation(rest[start..].to_string()))?;
+			let command = &rest[start + 2..end];
+
+			if let Some(arrows) = command.strip_prefix("cal ") {
+				for arrow in arrows.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+					self.add_annotation(node_id, parse_arrow(arrow)?)?;
+				}
+				text.push_str(&rest[..start]);
+			} else if let Some(squares) = command.strip_prefix("csl ") {
+				for square in squares.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+					self.add_annotation(node_id, parse_square_annotation(square)?)?;
+				}
+				text.push_str(&rest[..start]);
+			} else {
+				// Unknown commands stay part of the comment
+				text.push_str(&rest[..=end]);
+			}
+
+			rest = &rest[end + 1..];
+		}
+		text.push_str(rest);
+
+		let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+		if text.is_empty() {
+			return Ok(());
+		}
+
+		let node = self.node(node_id)?;
+		let comment = match &node.comment {
+			Some(existing) => format!("{} {}", existing, text),
+			None => text,
+		};
+
+		self.set_comment(node_id, Some(comment))
+	}
+}
+
+impl Game {
+	/// Exports the game as PGN, with the remaining clock time after each move as a `%clk` comment
+	pub fn export_pgn(&self) -> Result<String, ChessError> {
+		let mut start_position = self.position;
+		for (undo, mv) in self.undo_history.iter().zip(self.move_history.iter()).rev() {
+			start_position.undo_move(*undo, *mv)?;
+		}
+
+		let mut tree = GameTree::new(start_position);
+		let clock_history = self.clock.as_ref().map(|clock| clock.history()).unwrap_or_default();
+		let first_clock_ply = self.clock.as_ref().map_or(0, |clock| clock.first_ply());
+
+		for (ply, mv) in self.move_history.iter().enumerate() {
+			let node_id = tree.add_move(mv)?;
+
+			if let Some(remaining) = ply.checked_sub(first_clock_ply).and_then(|clock_ply| clock_history.get(clock_ply)) {
+				tree.set_comment(node_id, Some(format!("[%clk {}]", format_clk(*remaining))))?;
+			}
+		}
+
+		tree.tags.push(("Result".to_string(), self.result_string().to_string()));
+
+		tree.export_pgn()
+	}
+
+	pub(crate) fn result_string(&self) -> &'static str {
+		match self.game_status {
+			GameStatus::CheckmateForSide(Side::White) | GameStatus::TimeoutForSide(Side::White) | GameStatus::ResignationForSide(Side::White) => "1-0",
+			GameStatus::CheckmateForSide(Side::Black) | GameStatus::TimeoutForSide(Side::Black) | GameStatus::ResignationForSide(Side::Black) => "0-1",
+			GameStatus::Playing | GameStatus::InCheck => "*",
+			_ => "1/2-1/2",
+		}
+	}
+}
+
+fn push_token(movetext: &mut String, token: &str) {
+	if !movetext.is_empty() && !movetext.ends_with('(') && token != ")" {
+		movetext.push(' ');
+	}
+	movetext.push_str(token);
+}
+
+fn comment_token(node: &Node) -> Option<String> {
+	let mut parts: Vec<String> = Vec::new();
+
+	if let Some(comment) = &node.comment {
+		parts.push(comment.clone());
+	}
+
+	let squares: Vec<String> = node
+		.annotations
+		.iter()
+		.filter_map(|a| match a {
+			Annotation::Square { square, color } => Some(format!("{}{}", color_char(*color), convert_square_to_string(*square))),
+			_ => None,
+		})
+		.collect();
+	if !squares.is_empty() {
+		parts.push(format!("[%csl {}]", squares.join(",")));
+	}
+
+	let arrows: Vec<String> = node
+		.annotations
+		.iter()
+		.filter_map(|a| match a {
+			Annotation::Arrow { from_square, to_square, color } => Some(format!("{}{}{}", color_char(*color), convert_square_to_string(*from_square), convert_square_to_string(*to_square))),
+			_ => None,
+		})
+		.collect();
+	if !arrows.is_empty() {
+		parts.push(format!("[%cal {}]", arrows.join(",")));
+	}
+
+	if parts.is_empty() {
+		return None;
+	}
+
+	Some(format!("{{{}}}", parts.join(" ")))
+}
+
+fn format_clk(remaining: Duration) -> String {
+	let seconds = remaining.as_secs();
+
+	format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
+
+fn color_char(color: AnnotationColor) -> char {
+	match color {
+		AnnotationColor::Red => 'R',
+		AnnotationColor::Green => 'G',
+		AnnotationColor::Blue => 'B',
+		AnnotationColor::Yellow => 'Y',
+	}
+}
+
+fn parse_color(annotation: &str) -> Result<AnnotationColor, PgnError> {
+	match annotation.chars().next() {
+		Some('R') => Ok(AnnotationColor::Red),
+		Some('G') => Ok(AnnotationColor::Green),
+		Some('B') => Ok(AnnotationColor::Blue),
+		Some('Y') => Ok(AnnotationColor::Yellow),
+		_ => Err(PgnError::InvalidAnnotation(annotation.to_string())),
+	}
+}
+
+fn parse_square_annotation(annotation: &str) -> Result<Annotation, PgnError> {
+	if annotation.len() != 3 || !annotation.is_ascii() {
+		return Err(PgnError::InvalidAnnotation(annotation.to_string()));
+	}
+
+	let color = parse_color(annotation)?;
+	let square = convert_string_to_square(&annotation[1..3]).map_err(|_| PgnError::InvalidAnnotation(annotation.to_string()))?;
+
+	Ok(Annotation::Square { square, color })
+}
+
+fn parse_arrow(annotation: &str) -> Result<Annotation, PgnError> {
+	if annotation.len() != 5 || !annotation.is_ascii() {
+		return Err(PgnError::InvalidAnnotation(annotation.to_string()));
+	}
+
+	let color = parse_color(annotation)?;
+	let from_square = convert_string_to_square(&annotation[1..3]).map_err(|_| PgnError::InvalidAnnotation(annotation.to_string()))?;
+	let to_square = convert_string_to_square(&annotation[3..5]).map_err(|_| PgnError::InvalidAnnotation(annotation.to_string()))?;
+
+	Ok(Annotation::Arrow { from_square, to_square, color })
+}
+
+fn parse_tag(tag: &str) -> Result<(String, String), PgnError> {
+	let invalid = || PgnError::InvalidTag(tag.to_string());
+
+	let (name, value) = tag.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+	let value = value.trim().strip_prefix('"').and_then(|v| v.strip_suffix('"')).ok_or_else(invalid)?;
+
+	let mut unescaped = String::new();
+	let mut value_chars = value.chars();
+	while let Some(c) = value_chars.next() {
+		match c {
+			'\\' => unescaped.push(value_chars.next().ok_or_else(invalid)?),
+			c => unescaped.push(c),
+		}
+	}
+
+	Ok((name.to_string(), unescaped))
+}
+
+/// Index of the `]` closing the tag that starts at `from`, skipping over the quoted value so it may contain `]`
+fn find_tag_end(chars: &[char], from: usize) -> Option<usize> {
+	let mut in_quotes = false;
+	let mut j = from;
+
+	while j < chars.len() {
+		match chars[j] {
+			'\\' if in_quotes => j += 1,
+			'"' => in_quotes = !in_quotes,
+			']' if !in_quotes => return Some(j),
+			_ => {}
+		}
+		j += 1;
+	}
+
+	None
+}
+
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+	(from..chars.len()).find(|&j| chars[j] == target)
+}
+
+/// Removes a leading move number like `12.` or `12...` from a movetext word
+fn strip_move_number(word: &str) -> &str {
+	let digits_end = word.find(|c: char| !c.is_ascii_digit()).unwrap_or(word.len());
+
+	if digits_end > 0 && word[digits_end..].starts_with('.') {
+		return word[digits_end..].trim_start_matches('.');
+	}
+
+	word
+}
+
+#[cfg(test)]
+mod tests;
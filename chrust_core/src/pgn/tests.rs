@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use crate::clock::{ChessClock, ManualTimeSource, TimeControl};
+use crate::errors::{ChessError, PgnError};
+use crate::game_tree::{Annotation, AnnotationColor, GameTree, ROOT_NODE};
+use crate::test_common::game_from_fen;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// export_pgn tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn export_pgn_mainline() {
+	let mut tree = GameTree::from_pgn("1. e4 e5 2. Nf3 Nc6").unwrap();
+	tree.tags.push(("Event".to_string(), "Test".to_string()));
+
+	assert_eq!(tree.export_pgn().unwrap(), "[Event \"Test\"]\n\n1. e4 e5 2. Nf3 Nc6 *\n");
+}
+
+#[test]
+fn export_pgn_variations_and_comments() {
+	let mut tree = GameTree::from_pgn("1. e4 e5 2. Nf3").unwrap();
+	tree.go_to_start();
+	tree.go_forward();
+	let c5 = tree.add_move_from_san("c5").unwrap();
+	tree.add_move_from_san("Nf3").unwrap();
+	tree.set_comment(c5, Some("Sicilian".to_string())).unwrap();
+	tree.add_nag(c5, 5).unwrap();
+
+	assert_eq!(tree.export_pgn().unwrap(), "1. e4 e5 (1... c5 $5 {Sicilian} 2. Nf3) 2. Nf3 *\n");
+}
+
+#[test]
+fn export_pgn_annotations() {
+	let mut tree = GameTree::from_pgn("1. e4").unwrap();
+	let e4 = tree.node(ROOT_NODE).unwrap().children[0];
+	tree.add_annotation(e4, Annotation::Square { square: 28, color: AnnotationColor::Red }).unwrap();
+	tree
+		.add_annotation(
+			e4,
+			Annotation::Arrow {
+				from_square: 6,
+				to_square: 21,
+				color: AnnotationColor::Green,
+			},
+		)
+		.unwrap();
+
+	assert_eq!(tree.export_pgn().unwrap(), "1. e4 {[%csl Re4] [%cal Gg1f3]} *\n");
+}
+
+#[test]
+fn export_pgn_custom_start_position_adds_fen() {
+	let tree = GameTree::try_from_fen("4k3/8/8/8/8/8/8/4K2R b K - 0 30").unwrap();
+
+	assert_eq!(tree.export_pgn().unwrap(), "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K2R b K - 0 30\"]\n\n*\n");
+}
+
+#[test]
+fn numbered_san_numbers_black_moves_only_when_forced() {
+	let tree = GameTree::from_pgn("1. e4 e5 *").unwrap();
+	let e4 = tree.node(ROOT_NODE).unwrap().children[0];
+	let e5 = tree.node(e4).unwrap().children[0];
+
+	assert_eq!(tree.numbered_san(e4, false).unwrap(), "1. e4");
+	assert_eq!(tree.numbered_san(e5, false).unwrap(), "e5");
+	assert_eq!(tree.numbered_san(e5, true).unwrap(), "1... e5");
+	assert_eq!(tree.numbered_san(ROOT_NODE, false), Err(ChessError::NoSuchNode { node_id: ROOT_NODE }));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Game export_pgn tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn export_pgn_with_clock_times() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	let time = ManualTimeSource::default();
+	game.start_clock(ChessClock::new(TimeControl::sudden_death(Duration::from_secs(90 * 60)), Box::new(time.clone())));
+
+	time.advance(Duration::from_secs(2));
+	game.make_move_from_uci("e2e4").unwrap();
+	time.advance(Duration::from_secs(75));
+	game.make_move_from_uci("e7e5").unwrap();
+
+	assert_eq!(game.export_pgn().unwrap(), "[Result \"*\"]\n\n1. e4 {[%clk 1:29:58]} 1... e5 {[%clk 1:28:45]} *\n");
+}
+
+#[test]
+fn export_pgn_without_clock() {
+	let mut game = game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+	for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+
+	assert_eq!(game.export_pgn().unwrap(), "[Result \"0-1\"]\n\n1. f3 e5 2. g4 Qh4# 0-1\n");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// from_pgn tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn from_pgn_round_trips() {
+	let pgn = "[Event \"Casual \\\"game\\\"\"]\n[Result \"1-0\"]\n\n{Start} 1. e4 $1 e5 (1... c5 {Sicilian} 2. Nf3 (2. c3 d5) 2... d6) (1... e6) 2. Nf3 Nc6 3. Bb5 {[%cal Gb5c6]} 3... a6 1-0\n";
+
+	let tree = GameTree::from_pgn(pgn).unwrap();
+
+	assert_eq!(tree.export_pgn().unwrap(), pgn);
+}
+
+#[test]
+fn from_pgn_tags_and_result() {
+	let tree = GameTree::from_pgn("[White \"A\"]\n[Black \"B\"]\n[Result \"0-1\"]\n\n1. f3 e5 2. g4 Qh4# 0-1").unwrap();
+
+	assert_eq!(tree.tags[0], ("White".to_string(), "A".to_string()));
+	assert_eq!(tree.mainline().len(), 4);
+}
+
+#[test]
+fn from_pgn_tag_values_may_contain_brackets_and_escapes() {
+	let tree = GameTree::from_pgn("[Event \"Open [A] \\\"final]\\\"\"]\n[Site \"C:\\\\chess\"]\n\n1. e4 *").unwrap();
+
+	assert_eq!(tree.tags[0], ("Event".to_string(), "Open [A] \"final]\"".to_string()));
+	assert_eq!(tree.tags[1], ("Site".to_string(), "C:\\chess".to_string()));
+	assert_eq!(tree.mainline().len(), 1);
+	assert_eq!(GameTree::from_pgn(&tree.export_pgn().unwrap()).unwrap().tags, tree.tags);
+}
+
+#[test]
+fn from_pgn_unterminated_tag_fails() {
+	assert!(matches!(GameTree::from_pgn("[Event \"Open]\n1. e4 *"), Err(ChessError::PgnError { pgn_error: PgnError::InvalidTag(_) })));
+}
+
+#[test]
+fn from_pgn_suffix_glyphs_become_nags() {
+	let tree = GameTree::from_pgn("1. e4!! e5?! *").unwrap();
+	let e4 = tree.node(ROOT_NODE).unwrap().children[0];
+	let e5 = tree.node(e4).unwrap().children[0];
+
+	assert_eq!(tree.node(e4).unwrap().nags, [3]);
+	assert_eq!(tree.node(e5).unwrap().nags, [6]);
+}
+
+#[test]
+fn from_pgn_with_fen_tag() {
+	let tree = GameTree::from_pgn("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K2R w K - 0 1\"]\n\n1. O-O Kd7 *").unwrap();
+
+	assert_eq!(tree.mainline().len(), 2);
+}
+
+#[test]
+fn from_pgn_keeps_unknown_commands_in_comment() {
+	let tree = GameTree::from_pgn("1. e4 {[%clk 0:05:00] [%csl Ge4] good} *").unwrap();
+	let e4 = tree.node(ROOT_NODE).unwrap().children[0];
+
+	assert_eq!(tree.node(e4).unwrap().comment.as_deref(), Some("[%clk 0:05:00] good"));
+	assert_eq!(
+		tree.node(e4).unwrap().annotations,
+		[Annotation::Square {
+			square: 28,
+			color: AnnotationColor::Green
+		}]
+	);
+}
+
+#[test]
+fn from_pgn_line_comments_and_compact_numbers() {
+	let tree = GameTree::from_pgn("1.e4 ; king pawn\n1...c5 2.Nf3 *").unwrap();
+	let e4 = tree.node(ROOT_NODE).unwrap().children[0];
+
+	assert_eq!(tree.node(e4).unwrap().comment.as_deref(), Some("king pawn"));
+	assert_eq!(tree.mainline().len(), 3);
+}
+
+#[test]
+fn from_pgn_errors() {
+	assert_eq!(GameTree::from_pgn("1. e4 (").err(), Some(ChessError::PgnError { pgn_error: PgnError::UnbalancedVariation }));
+	assert_eq!(GameTree::from_pgn("1. e4 )").err(), Some(ChessError::PgnError { pgn_error: PgnError::UnbalancedVariation }));
+	assert_eq!(
+		GameTree::from_pgn("(1. e4)").err(),
+		Some(ChessError::PgnError {
+			pgn_error: PgnError::VariationWithoutMove
+		})
+	);
+	assert_eq!(GameTree::from_pgn("1. e4 {open").err(), Some(ChessError::PgnError { pgn_error: PgnError::UnterminatedComment }));
+	assert_eq!(
+		GameTree::from_pgn("1. e5").err(),
+		Some(ChessError::PgnError {
+			pgn_error: PgnError::IllegalMove("e5".to_string())
+		})
+	);
+	assert!(matches!(GameTree::from_pgn("[Event]\n1. e4"), Err(ChessError::PgnError { pgn_error: PgnError::InvalidTag(_) })));
+	assert!(matches!(
+		GameTree::from_pgn("1. e4 {[%cal Xe2e4]}"),
+		Err(ChessError::PgnError {
+			pgn_error: PgnError::InvalidAnnotation(_)
+		})
+	));
+}
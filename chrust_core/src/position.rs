@@ -1,23 +1,34 @@
 use crate::{
 	ColoredPiece, Piece, Side, Square,
+	clock::ChessClock,
 	converter::{convert_square_to_string, convert_string_to_square, letter_to_piece},
-	errors::{ChessError, FenError},
+	errors::{ChessError, FenError, FenErrorKind, FenField},
 	game_status::GameStatus,
 	helper::square,
 	moves::make_move::Move,
 };
 
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
 	pub position: Position,
 	pub hash_history: Vec<u64>,
 	pub move_history: Vec<Move>,
 	pub undo_history: Vec<Undo>,
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub redo_history: Vec<Move>,
 	pub game_status: GameStatus,
 	pub draw_offer: Option<Side>,
+	/// Saved with its times, a running clock continues from the moment the game is loaded
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub clock: Option<ChessClock>,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
+	#[cfg_attr(feature = "serde", serde(with = "board_serde"))]
 	pub board: [Option<ColoredPiece>; 64],
 	pub side_to_move: Side,
 	pub(crate) castle: [bool; 4],
@@ -29,6 +40,7 @@ pub struct Position {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Undo {
 	pub(crate) captured_piece: Option<ColoredPiece>,
 	pub(crate) previous_castling_rights: [bool; 4],
@@ -41,14 +53,20 @@ pub struct Undo {
 
 impl Game {
 	pub fn try_from_fen(fen_string: &str) -> Result<Game, ChessError> {
-		let position = load_position_from_fen(fen_string)?;
+		Game::from_position(load_position_from_fen(fen_string)?)
+	}
+
+	/// A new game starting at `position`, e.g. one set up with a `PositionBuilder`
+	pub fn from_position(position: Position) -> Result<Game, ChessError> {
 		let mut game = Game {
 			position,
 			draw_offer: None,
 			hash_history: Vec::new(),
 			move_history: Vec::new(),
 			undo_history: Vec::new(),
+			redo_history: Vec::new(),
 			game_status: GameStatus::Playing,
+			clock: None,
 		};
 		game.update_game_status()?;
 		Ok(game)
@@ -70,20 +88,26 @@ pub fn load_position_from_fen(fen: &str) -> Result<Position, FenError> {
 	let fen_parts: Vec<&str> = fen.split_whitespace().collect();
 
 	if fen_parts.len() != 6 {
-		return Err(FenError::MissingFenParts);
+		return Err(FenErrorKind::MissingFenParts.into());
 	}
 
-	position.fullmove_counter = load_clock(fen_parts[5])?;
+	// Errors are located by the character index of the field start plus the offset inside the field
+	let in_field = |field: FenField, part: &str| {
+		let start = fen[..part.as_ptr() as usize - fen.as_ptr() as usize].chars().count();
+		move |(offset, kind): (usize, FenErrorKind)| FenError::located(kind, field, start + offset)
+	};
+
+	position.fullmove_counter = load_clock(fen_parts[5]).map_err(in_field(FenField::FullmoveNumber, fen_parts[5]))?;
 
-	position.halfmove_clock = load_clock(fen_parts[4])?;
+	position.halfmove_clock = load_clock(fen_parts[4]).map_err(in_field(FenField::HalfmoveClock, fen_parts[4]))?;
 
-	load_en_passant(&mut position, fen_parts[3])?;
+	load_en_passant(&mut position, fen_parts[3]).map_err(in_field(FenField::EnPassant, fen_parts[3]))?;
 
-	load_castling_ability(&mut position, fen_parts[2])?;
+	load_castling_ability(&mut position, fen_parts[2]).map_err(in_field(FenField::Castling, fen_parts[2]))?;
 
-	load_side_to_move(&mut position, fen_parts[1])?;
+	load_side_to_move(&mut position, fen_parts[1]).map_err(in_field(FenField::SideToMove, fen_parts[1]))?;
 
-	load_piece_placement(&mut position, fen_parts[0])?;
+	load_piece_placement(&mut position, fen_parts[0]).map_err(in_field(FenField::PiecePlacement, fen_parts[0]))?;
 
 	for (square, piece) in position.board.iter().enumerate() {
 		if let Some(colored_piece) = piece {
@@ -103,19 +127,41 @@ pub fn load_position_from_fen(fen: &str) -> Result<Position, FenError> {
 
 impl Position {
 	pub fn print_board(&self) {
-		for rank in (0..8).rev() {
-			print!("{} ", rank + 1);
-			for file in 0..8 {
-				let square = square(file, rank);
-				let test = self.board[square as usize];
-				match test {
-					Some(piece) => print!(" {}", piece.to_char()),
-					None => print!(" ."),
-				}
+		print!("{}", self.board_to_string(Side::White, false));
+	}
+
+	/// Draws the board with rank and file labels as seen by `perspective`, using chess symbols
+	/// instead of FEN letters when `unicode` is set
+	pub fn board_to_string(&self, perspective: Side, unicode: bool) -> String {
+		let mut ranks: Vec<u8> = (0..8).rev().collect();
+		let mut files: Vec<u8> = (0..8).collect();
+		if perspective == Side::Black {
+			ranks.reverse();
+			files.reverse();
+		}
+
+		let mut board = String::new();
+		for &rank in &ranks {
+			board.push_str(&format!("{} ", rank + 1));
+			for &file in &files {
+				board.push(' ');
+				board.push(match self.board[square(file, rank) as usize] {
+					Some(piece) if unicode => piece.to_unicode(),
+					Some(piece) => piece.to_char(),
+					None => '.',
+				});
 			}
-			print!("\n");
+			board.push('\n');
 		}
-		println!("   a b c d e f g h");
+
+		board.push_str("  ");
+		for &file in &files {
+			board.push(' ');
+			board.push((b'a' + file) as char);
+		}
+		board.push('\n');
+
+		board
 	}
 
 	pub fn export_position_to_fen(&self) -> Result<String, ChessError> {
@@ -168,52 +214,59 @@ impl Position {
 	}
 }
 
-fn load_castling_ability(position: &mut Position, castling_rules: &str) -> Result<(), FenError> {
-	for castle_char in castling_rules.chars() {
+/// Errors of a single FEN field, with the character offset inside the field
+type FieldResult<T> = Result<T, (usize, FenErrorKind)>;
+
+fn load_castling_ability(position: &mut Position, castling_rules: &str) -> FieldResult<()> {
+	for (offset, castle_char) in castling_rules.chars().enumerate() {
 		match castle_char {
 			'K' => position.castle[0] = true,
 			'Q' => position.castle[1] = true,
 			'k' => position.castle[2] = true,
 			'q' => position.castle[3] = true,
 			'-' => position.castle = [false; 4],
-			other => return Err(FenError::InvalidCastlingRights(other)),
+			other => return Err((offset, FenErrorKind::InvalidCastlingRights(other))),
 		}
 	}
 
 	Ok(())
 }
 
-fn load_en_passant(position: &mut Position, en_passant_string: &str) -> Result<(), FenError> {
+fn load_en_passant(position: &mut Position, en_passant_string: &str) -> FieldResult<()> {
 	if en_passant_string == "-" {
 		return Ok(());
 	}
 
-	match convert_string_to_square(en_passant_string) {
-		Ok(x) => position.en_passant = Some(x),
-		Err(x) => return Err(x),
-	}
+	position.en_passant = Some(convert_string_to_square(en_passant_string).map_err(|error| (0, error))?);
 
 	Ok(())
 }
 
-fn load_side_to_move(position: &mut Position, side_to_move: &str) -> Result<(), FenError> {
+fn load_side_to_move(position: &mut Position, side_to_move: &str) -> FieldResult<()> {
 	match side_to_move {
 		"b" => position.side_to_move = Side::Black,
 		"w" => position.side_to_move = Side::White,
-		_ => return Err(FenError::NotAValideSide),
+		_ => return Err((0, FenErrorKind::NotAValideSide)),
 	};
 
 	Ok(())
 }
 
-fn load_piece_placement(position: &mut Position, fen_board: &str) -> Result<(), FenError> {
-	let fen_ranks = fen_board.split("/");
+fn load_piece_placement(position: &mut Position, fen_board: &str) -> FieldResult<()> {
+	let fen_ranks: Vec<&str> = fen_board.split('/').collect();
+	if fen_ranks.len() != 8 {
+		return Err((0, FenErrorKind::InvalidRankCount(fen_ranks.len())));
+	}
 
-	let mut current_rank = 7;
-	for rank_str in fen_ranks {
+	let mut offset = 0;
+	for (rank_str, current_rank) in fen_ranks.into_iter().zip((0..8).rev()) {
 		let mut file = 0;
 
 		for c in rank_str.chars() {
+			if file >= 8 {
+				return Err((offset, FenErrorKind::InvalidRankLength(rank_str.to_string())));
+			}
+
 			if let Some(digit) = c.to_digit(10) {
 				file += digit as u8;
 			} else {
@@ -222,7 +275,7 @@ fn load_piece_placement(position: &mut Position, fen_board: &str) -> Result<(),
 				} else {
 					Side::Black
 				};
-				let piece_type = letter_to_piece(c)?;
+				let piece_type = letter_to_piece(c).map_err(|error| (offset, error))?;
 
 				let piece = ColoredPiece { piece: piece_type, side: piece_side };
 
@@ -231,23 +284,41 @@ fn load_piece_placement(position: &mut Position, fen_board: &str) -> Result<(),
 
 				file += 1;
 			}
+			offset += 1;
 		}
 
-		if current_rank > 0 {
-			current_rank -= 1;
+		// Short ranks are accepted, the remaining squares stay empty
+		if file > 8 {
+			return Err((offset - 1, FenErrorKind::InvalidRankLength(rank_str.to_string())));
 		}
+
+		// The separating '/'
+		offset += 1;
 	}
 
 	Ok(())
 }
 
-fn load_clock(clock_string: &str) -> Result<u32, FenError> {
-	// please dont judge me i am to lazy to find a var name
-	let new_clock_string = clock_string.to_string();
-	match new_clock_string.parse::<u32>() {
-		Ok(x) => return Ok(x),
-		Err(_) => return Err(FenError::InvalidNumber(new_clock_string.to_string())),
-	};
+fn load_clock(clock_string: &str) -> FieldResult<u32> {
+	clock_string.parse::<u32>().map_err(|_| (0, FenErrorKind::InvalidNumber(clock_string.to_string())))
+}
+
+/// Serde only implements arrays up to 32 elements, so the board is (de)serialized as a sequence
+#[cfg(feature = "serde")]
+mod board_serde {
+	use crate::ColoredPiece;
+	use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+	pub fn serialize<S: Serializer>(board: &[Option<ColoredPiece>; 64], serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(board.iter())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[Option<ColoredPiece>; 64], D::Error> {
+		let squares = Vec::<Option<ColoredPiece>>::deserialize(deserializer)?;
+		let length = squares.len();
+
+		squares.try_into().map_err(|_| D::Error::invalid_length(length, &"64 squares"))
+	}
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use super::*;
 use crate::converter::convert_square_to_string;
-use crate::errors::{ChessError, FenError};
+use crate::errors::{ChessError, FenErrorKind, FenField};
 use crate::game_status::GameStatus;
 use crate::{ColoredPiece, Piece, Side};
 
@@ -41,39 +41,39 @@ fn square_string_accepts_uppercase() {
 
 #[test]
 fn square_string_too_short_returns_error() {
-	assert!(matches!(convert_string_to_square("a"), Err(FenError::SquareLenghtIsnt2Wide(1))));
+	assert!(matches!(convert_string_to_square("a"), Err(FenErrorKind::SquareLenghtIsnt2Wide(1))));
 }
 
 #[test]
 fn square_string_too_long_returns_error() {
-	assert!(matches!(convert_string_to_square("a12"), Err(FenError::SquareLenghtIsnt2Wide(3))));
+	assert!(matches!(convert_string_to_square("a12"), Err(FenErrorKind::SquareLenghtIsnt2Wide(3))));
 }
 
 #[test]
 fn square_string_empty_returns_error() {
-	assert!(matches!(convert_string_to_square(""), Err(FenError::SquareLenghtIsnt2Wide(0))));
+	assert!(matches!(convert_string_to_square(""), Err(FenErrorKind::SquareLenghtIsnt2Wide(0))));
 }
 
 #[test]
 fn square_string_invalid_file_returns_error() {
-	assert!(matches!(convert_string_to_square("i4"), Err(FenError::InvalidFile('i'))));
-	assert!(matches!(convert_string_to_square("z1"), Err(FenError::InvalidFile('z'))));
+	assert!(matches!(convert_string_to_square("i4"), Err(FenErrorKind::InvalidFile('i'))));
+	assert!(matches!(convert_string_to_square("z1"), Err(FenErrorKind::InvalidFile('z'))));
 }
 
 #[test]
 fn square_string_rank_zero_returns_error() {
 	// Rank 0 is not a valid chess rank (1-8 only).
-	assert!(matches!(convert_string_to_square("a0"), Err(FenError::InvalidRank('0'))));
+	assert!(matches!(convert_string_to_square("a0"), Err(FenErrorKind::InvalidRank('0'))));
 }
 
 #[test]
 fn square_string_rank_nine_returns_error() {
-	assert!(matches!(convert_string_to_square("a9"), Err(FenError::InvalidRank('9'))));
+	assert!(matches!(convert_string_to_square("a9"), Err(FenErrorKind::InvalidRank('9'))));
 }
 
 #[test]
 fn square_string_non_digit_rank_returns_error() {
-	assert!(matches!(convert_string_to_square("ax"), Err(FenError::InvalidRank('x'))));
+	assert!(matches!(convert_string_to_square("ax"), Err(FenErrorKind::InvalidRank('x'))));
 }
 
 // ── load_position_from_fen — error paths ─────────────────────────────────────
@@ -81,28 +81,71 @@ fn square_string_non_digit_rank_returns_error() {
 #[test]
 fn fen_with_too_few_parts_returns_error() {
 	// Standard FEN has 6 space-separated fields.
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"), Err(FenError::MissingFenParts)));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").unwrap_err().kind(), FenErrorKind::MissingFenParts));
 }
 
 #[test]
 fn fen_with_too_many_parts_returns_error() {
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 extra"), Err(FenError::MissingFenParts)));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 extra").unwrap_err().kind(), FenErrorKind::MissingFenParts));
+}
+
+#[test]
+fn fen_with_seven_ranks_returns_error() {
+	assert_eq!(load_position_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1pPPPBBPPP/R3K2R w KQkq - 0 1").unwrap_err().kind(), &FenErrorKind::InvalidRankCount(7));
+}
+
+#[test]
+fn fen_with_too_long_rank_returns_error() {
+	assert_eq!(load_position_from_fen("4k3/8/8/8/8/8/8/4K3p w - - 0 1").unwrap_err().kind(), &FenErrorKind::InvalidRankLength("4K3p".to_string()));
+	assert_eq!(load_position_from_fen("4k3/8/8/8/8/8/9/4K3 w - - 0 1").unwrap_err().kind(), &FenErrorKind::InvalidRankLength("9".to_string()));
 }
 
 #[test]
 fn fen_with_invalid_piece_char_returns_error() {
 	// 'X' is not a valid piece character.
-	assert!(matches!(load_position_from_fen("rnbqkXnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), Err(FenError::InvalidPieceChar('X'))));
+	assert!(matches!(load_position_from_fen("rnbqkXnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap_err().kind(), FenErrorKind::InvalidPieceChar('X')));
 }
 
 #[test]
 fn fen_with_invalid_side_to_move_returns_error() {
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1"), Err(FenError::NotAValideSide)));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").unwrap_err().kind(), FenErrorKind::NotAValideSide));
 }
 
 #[test]
 fn fen_with_invalid_castling_char_returns_error() {
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w XQkq - 0 1"), Err(FenError::InvalidCastlingRights('X'))));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w XQkq - 0 1").unwrap_err().kind(), FenErrorKind::InvalidCastlingRights('X')));
+}
+
+#[test]
+fn fen_errors_name_the_field_and_character() {
+	let error = load_position_from_fen("rnbqkXnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap_err();
+	assert_eq!(error.kind(), &FenErrorKind::InvalidPieceChar('X'));
+	assert_eq!((error.field(), error.index()), (Some(FenField::PiecePlacement), Some(5)));
+
+	let error = load_position_from_fen("4k3/8/8/8/8/8/9/4K3 w - - 0 1").unwrap_err();
+	assert_eq!((error.field(), error.index()), (Some(FenField::PiecePlacement), Some(14)));
+
+	let error = load_position_from_fen("4k3/8/8/8/8/8/8/4K3 w KX - 0 1").unwrap_err();
+	assert_eq!((error.field(), error.index()), (Some(FenField::Castling), Some(23)));
+
+	let error = load_position_from_fen("  4k3/8/8/8/8/8/8/4K3 w - z9 0 1").unwrap_err();
+	assert_eq!(error.kind(), &FenErrorKind::InvalidFile('z'));
+	assert_eq!((error.field(), error.index()), (Some(FenField::EnPassant), Some(26)));
+
+	let error = load_position_from_fen("4k3/8/8/8/8/8/8/4K3 w -").unwrap_err();
+	assert_eq!((error.field(), error.index()), (None, None));
+}
+
+#[test]
+fn fen_errors_are_readable_and_chained() {
+	let error = ChessError::from(load_position_from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 x").unwrap_err());
+
+	assert_eq!(error.to_string(), "invalid FEN: fullmove number, character 29: 'x' is not a number");
+
+	let fen_error = std::error::Error::source(&error).unwrap();
+	assert_eq!(fen_error.to_string(), "fullmove number, character 29: 'x' is not a number");
+	assert_eq!(fen_error.source().unwrap().to_string(), "'x' is not a number");
+	assert!(fen_error.source().unwrap().source().is_none());
 }
 
 #[test]
@@ -346,12 +389,12 @@ fn fen_fullmove_counter_starting_position() {
 
 #[test]
 fn fen_invalid_halfmove_clock_returns_error() {
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - abc 1"), Err(FenError::InvalidNumber(_))));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - abc 1").unwrap_err().kind(), FenErrorKind::InvalidNumber(_)));
 }
 
 #[test]
 fn fen_invalid_fullmove_counter_returns_error() {
-	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 xyz"), Err(FenError::InvalidNumber(_))));
+	assert!(matches!(load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 xyz").unwrap_err().kind(), FenErrorKind::InvalidNumber(_)));
 }
 
 #[test]
@@ -649,3 +692,89 @@ fn export_fen_piece_placement_has_eight_ranks() {
 	let ranks: Vec<&str> = exported.split_whitespace().next().unwrap().split('/').collect();
 	assert_eq!(ranks.len(), 8, "piece placement must have 8 ranks separated by '/'");
 }
+
+// ── serde ─────────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_position_round_trips_through_json() {
+	let pos = load_position_from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 3 17").unwrap();
+
+	let json = serde_json::to_string(&pos).unwrap();
+	let restored: Position = serde_json::from_str(&json).unwrap();
+
+	assert_eq!(restored, pos);
+	assert_eq!(restored.zobrist_hash, pos.compute_hash());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_position_with_short_board_fails() {
+	let pos = load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+	let mut value = serde_json::to_value(pos).unwrap();
+	value["board"].as_array_mut().unwrap().pop();
+
+	assert!(serde_json::from_value::<Position>(value).is_err(), "a board without 64 squares must be rejected");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_game_round_trips_and_undo_still_works() {
+	let mut game = Game::try_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+	for uci in ["e2e4", "d7d5", "e4d5", "g8f6"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+	game.offer_draw().unwrap();
+
+	let json = serde_json::to_string(&game).unwrap();
+	let mut restored: Game = serde_json::from_str(&json).unwrap();
+
+	assert_eq!(restored.position, game.position);
+	assert_eq!(restored.hash_history, game.hash_history);
+	assert_eq!(restored.move_history, game.move_history);
+	assert_eq!(restored.draw_offer, Some(Side::White));
+	assert!(restored.game_status == GameStatus::Playing);
+
+	while !game.move_history.is_empty() {
+		game.undo_last_move().unwrap();
+		restored.undo_last_move().unwrap();
+		assert_eq!(restored.position, game.position);
+		assert_eq!(restored.draw_offer, game.draw_offer);
+	}
+
+	assert_eq!(restored.position.export_position_to_fen().unwrap(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_game_without_redo_history_loads() {
+	let mut game = Game::try_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+	game.make_move_from_uci("e2e4").unwrap();
+	let mut value = serde_json::to_value(&game).unwrap();
+	value.as_object_mut().unwrap().remove("redo_history");
+
+	let restored: Game = serde_json::from_value(value).unwrap();
+
+	assert!(restored.redo_history.is_empty());
+	assert_eq!(restored.move_history, game.move_history);
+}
+
+#[test]
+fn board_to_string_from_white() {
+	let position = load_position_from_fen("4k3/8/8/8/8/8/4P3/R3K3 w Q - 0 1").unwrap();
+
+	assert_eq!(
+		position.board_to_string(Side::White, false),
+		"8  . . . . k . . .\n7  . . . . . . . .\n6  . . . . . . . .\n5  . . . . . . . .\n4  . . . . . . . .\n3  . . . . . . . .\n2  . . . . P . . .\n1  R . . . K . . .\n   a b c d e f g h\n"
+	);
+}
+
+#[test]
+fn board_to_string_from_black_with_unicode() {
+	let position = load_position_from_fen("4k3/8/8/8/8/8/4P3/R3K3 w Q - 0 1").unwrap();
+
+	assert_eq!(
+		position.board_to_string(Side::Black, true),
+		"1  . . . ♔ . . . ♖\n2  . . . ♙ . . . .\n3  . . . . . . . .\n4  . . . . . . . .\n5  . . . . . . . .\n6  . . . . . . . .\n7  . . . . . . . .\n8  . . . ♚ . . . .\n   h g f e d c b a\n"
+	);
+}
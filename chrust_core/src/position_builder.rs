@@ -0,0 +1,249 @@
+//! Setting up a position piece by piece, for board editors and puzzle imports.
+
+use crate::{
+	ColoredPiece, Piece, Side, Square,
+	errors::SetupError,
+	helper::{file, rank, square},
+	position::Position,
+};
+
+/// Pieces per type at the start of a game, promotions can only add to these
+const INITIAL_COUNTS: [(Piece, usize); 4] = [
+	(Piece::Queen, 1),
+	(Piece::Rook, 2),
+	(Piece::Bishop, 2),
+	(Piece::Knight, 2),
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PositionBuilder {
+	board: [Option<ColoredPiece>; 64],
+	side_to_move: Side,
+	castle: [bool; 4],
+	en_passant: Option<Square>,
+	halfmove_clock: u32,
+	fullmove_counter: u32,
+}
+
+impl Default for PositionBuilder {
+	fn default() -> Self {
+		PositionBuilder::new()
+	}
+}
+
+impl PositionBuilder {
+	/// An empty board with white to move, no castling rights and the clocks of a new game
+	pub fn new() -> PositionBuilder {
+		PositionBuilder {
+			board: [None; 64],
+			side_to_move: Side::White,
+			castle: [false; 4],
+			en_passant: None,
+			halfmove_clock: 0,
+			fullmove_counter: 1,
+		}
+	}
+
+	/// Starts editing a copy of `position`
+	pub fn from_position(position: &Position) -> PositionBuilder {
+		PositionBuilder {
+			board: position.board,
+			side_to_move: position.side_to_move,
+			castle: position.castle,
+			en_passant: position.en_passant,
+			halfmove_clock: position.halfmove_clock,
+			fullmove_counter: position.fullmove_counter,
+		}
+	}
+
+	/// Puts `piece` on `square`, replacing whatever stood there. Panics if `square` isn't on the board.
+	pub fn place(&mut self, square: Square, piece: ColoredPiece) -> &mut Self {
+		self.board[square as usize] = Some(piece);
+		self
+	}
+
+	/// Empties `square`. Panics if `square` isn't on the board.
+	pub fn remove(&mut self, square: Square) -> &mut Self {
+		self.board[square as usize] = None;
+		self
+	}
+
+	/// Removes every piece, the other settings are kept
+	pub fn clear(&mut self) -> &mut Self {
+		self.board = [None; 64];
+		self
+	}
+
+	pub fn piece(&self, square: Square) -> Option<ColoredPiece> {
+		self.board.get(square as usize).copied().flatten()
+	}
+
+	pub fn side_to_move(&mut self, side: Side) -> &mut Self {
+		self.side_to_move = side;
+		self
+	}
+
+	pub fn castling(&mut self, side: Side, king_side: bool, allowed: bool) -> &mut Self {
+		self.castle[castle_index(side, king_side)] = allowed;
+		self
+	}
+
+	/// The square a pawn passed with a double push on the last move
+	pub fn en_passant(&mut self, square: Option<Square>) -> &mut Self {
+		self.en_passant = square;
+		self
+	}
+
+	pub fn halfmove_clock(&mut self, halfmove_clock: u32) -> &mut Self {
+		self.halfmove_clock = halfmove_clock;
+		self
+	}
+
+	pub fn fullmove_number(&mut self, fullmove_number: u32) -> &mut Self {
+		self.fullmove_counter = fullmove_number;
+		self
+	}
+
+	/// Validates the setup and returns the position with its king squares and hash
+	pub fn build(&self) -> Result<Position, SetupError> {
+		let mut position = Position {
+			board: self.board,
+			side_to_move: self.side_to_move,
+			castle: self.castle,
+			en_passant: self.en_passant,
+			king_squares: [0; 2],
+			halfmove_clock: self.halfmove_clock,
+			fullmove_counter: self.fullmove_counter,
+			zobrist_hash: 0,
+		};
+
+		position.king_squares = [
+			self.validate_material(Side::White)?,
+			self.validate_material(Side::Black)?,
+		];
+		self.validate_kings(&position)?;
+		self.validate_castling()?;
+		self.validate_en_passant()?;
+		if self.fullmove_counter == 0 {
+			return Err(SetupError::InvalidFullmoveNumber);
+		}
+
+		position.zobrist_hash = position.compute_hash();
+
+		Ok(position)
+	}
+
+	/// Checks the piece counts of `side` and returns the square of its king
+	fn validate_material(&self, side: Side) -> Result<Square, SetupError> {
+		let count = |piece: Piece| self.board.iter().filter(|&&occupant| occupant == Some(ColoredPiece { piece, side })).count();
+
+		let mut kings = self.board.iter().enumerate().filter(|(_, occupant)| **occupant == Some(ColoredPiece { piece: Piece::King, side }));
+		let king_square = match (kings.next(), kings.next()) {
+			(None, _) => return Err(SetupError::MissingKing { side }),
+			(Some(_), Some(_)) => return Err(SetupError::TooManyKings { side }),
+			(Some((king_square, _)), None) => king_square as Square,
+		};
+
+		let pieces = self.board.iter().filter(|occupant| occupant.is_some_and(|piece| piece.side == side)).count();
+		if pieces > 16 {
+			return Err(SetupError::TooManyPieces { side, count: pieces });
+		}
+
+		let pawns = count(Piece::Pawn);
+		if pawns > 8 {
+			return Err(SetupError::TooManyPawns { side, count: pawns });
+		}
+
+		// Every piece beyond the initial set needs a pawn that promoted
+		let promoted: usize = INITIAL_COUNTS.iter().map(|&(piece, initial)| count(piece).saturating_sub(initial)).sum();
+		if promoted > 8 - pawns {
+			return Err(SetupError::TooManyPromotedPieces { side });
+		}
+
+		if let Some(pawn_square) = (0..64).find(|&square| matches!(rank(square), 0 | 7) && self.board[square as usize] == Some(ColoredPiece { piece: Piece::Pawn, side })) {
+			return Err(SetupError::PawnOnBackRank { square: pawn_square });
+		}
+
+		Ok(king_square)
+	}
+
+	fn validate_kings(&self, position: &Position) -> Result<(), SetupError> {
+		let [white_king, black_king] = position.king_squares;
+		if file(white_king).abs_diff(file(black_king)) <= 1 && rank(white_king).abs_diff(rank(black_king)) <= 1 {
+			return Err(SetupError::KingsAreAdjacent);
+		}
+
+		// The side that just moved can't have left its king attacked
+		let opponent = self.side_to_move.opponent();
+		if position.is_king_in_check(opponent).ok().flatten().is_some() {
+			return Err(SetupError::OpponentInCheck { side: opponent });
+		}
+
+		// No single move gives check with more than two pieces
+		let checkers = position.is_king_in_check(self.side_to_move).ok().flatten().map_or(0, |checkers| checkers.len());
+		if checkers > 2 {
+			return Err(SetupError::TooManyCheckers { count: checkers });
+		}
+
+		Ok(())
+	}
+
+	/// Castling rights need the king and the rook on their initial squares
+	fn validate_castling(&self) -> Result<(), SetupError> {
+		for (side, home_rank) in [(Side::White, 0), (Side::Black, 7)] {
+			for (king_side, rook_file) in [(true, 7), (false, 0)] {
+				let king_home = self.board[square(4, home_rank) as usize] == Some(ColoredPiece { piece: Piece::King, side });
+				let rook_home = self.board[square(rook_file, home_rank) as usize] == Some(ColoredPiece { piece: Piece::Rook, side });
+
+				if self.castle[castle_index(side, king_side)] && !(king_home && rook_home) {
+					return Err(SetupError::InvalidCastlingRights { side, king_side });
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// The en passant square lies behind a pawn of the opponent that just made a double push
+	fn validate_en_passant(&self) -> Result<(), SetupError> {
+		let Some(en_passant) = self.en_passant else {
+			return Ok(());
+		};
+		if en_passant > 63 {
+			return Err(SetupError::NotASquareOnBoard { square: en_passant });
+		}
+
+		let (passed_rank, pawn_rank, start_rank) = match self.side_to_move {
+			Side::White => (5, 4, 6),
+			Side::Black => (2, 3, 1),
+		};
+		let pawn_square = square(file(en_passant), pawn_rank);
+		let start_square = square(file(en_passant), start_rank);
+
+		let is_valid = rank(en_passant) == passed_rank
+			&& self.board[en_passant as usize].is_none()
+			&& self.board[start_square as usize].is_none()
+			&& self.board[pawn_square as usize]
+				== Some(ColoredPiece {
+					piece: Piece::Pawn,
+					side: self.side_to_move.opponent(),
+				});
+		if !is_valid {
+			return Err(SetupError::InvalidEnPassant { square: en_passant });
+		}
+
+		Ok(())
+	}
+}
+
+fn castle_index(side: Side, king_side: bool) -> usize {
+	match (side, king_side) {
+		(Side::White, true) => 0,
+		(Side::White, false) => 1,
+		(Side::Black, true) => 2,
+		(Side::Black, false) => 3,
+	}
+}
+
+#[cfg(test)]
+mod tests;
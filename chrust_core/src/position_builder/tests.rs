@@ -0,0 +1,178 @@
+use crate::{
+	ColoredPiece, Piece, Side,
+	errors::{ChessError, SetupError},
+	position::{Game, STARTING_FEN, load_position_from_fen},
+	position_builder::PositionBuilder,
+	test_common::TEST_FENS,
+};
+
+const WHITE_KING: ColoredPiece = ColoredPiece { piece: Piece::King, side: Side::White };
+const BLACK_KING: ColoredPiece = ColoredPiece { piece: Piece::King, side: Side::Black };
+const WHITE_ROOK: ColoredPiece = ColoredPiece { piece: Piece::Rook, side: Side::White };
+const WHITE_PAWN: ColoredPiece = ColoredPiece { piece: Piece::Pawn, side: Side::White };
+const BLACK_PAWN: ColoredPiece = ColoredPiece { piece: Piece::Pawn, side: Side::Black };
+const WHITE_QUEEN: ColoredPiece = ColoredPiece { piece: Piece::Queen, side: Side::White };
+
+/// White king on e1, black king on e8
+fn kings() -> PositionBuilder {
+	let mut builder = PositionBuilder::new();
+	builder.place(4, WHITE_KING).place(60, BLACK_KING);
+	builder
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Building
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn built_position_matches_the_fen() {
+	let position = kings()
+		.place(0, WHITE_ROOK)
+		.place(12, WHITE_PAWN)
+		.castling(Side::White, false, true)
+		.side_to_move(Side::Black)
+		.halfmove_clock(3)
+		.fullmove_number(20)
+		.build()
+		.unwrap();
+
+	assert_eq!(position, load_position_from_fen("4k3/8/8/8/8/8/4P3/R3K3 b Q - 3 20").unwrap());
+	assert_eq!(position.king_squares, [4, 60]);
+	assert_eq!(position.zobrist_hash, position.compute_hash());
+}
+
+#[test]
+fn test_positions_survive_a_round_trip() {
+	for fen in TEST_FENS {
+		let position = load_position_from_fen(fen).unwrap();
+		let rebuilt = PositionBuilder::from_position(&position).build();
+
+		// Some test positions leave the side that just moved in check
+		if let Err(SetupError::OpponentInCheck { .. }) = rebuilt {
+			continue;
+		}
+		assert_eq!(rebuilt, Ok(position), "{}", fen);
+	}
+}
+
+#[test]
+fn editing_keeps_the_hash_in_sync() {
+	let start = load_position_from_fen(STARTING_FEN).unwrap();
+	let position = PositionBuilder::from_position(&start).remove(3).build().unwrap();
+
+	assert_eq!(position.board[3], None);
+	assert_eq!(position.zobrist_hash, position.compute_hash());
+	assert_ne!(position.zobrist_hash, start.zobrist_hash);
+}
+
+#[test]
+fn clear_keeps_the_settings() {
+	let mut builder = PositionBuilder::from_position(&load_position_from_fen("r3k3/8/8/8/8/8/8/4K3 b q - 0 30").unwrap());
+	builder.clear();
+
+	assert_eq!(builder.piece(56), None);
+	assert_eq!(builder.place(4, WHITE_KING).place(60, BLACK_KING).castling(Side::Black, false, false).build(), Ok(load_position_from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 30").unwrap()));
+}
+
+#[test]
+fn built_position_starts_a_game() {
+	let position = kings().place(48, WHITE_PAWN).place(51, BLACK_PAWN).build().unwrap();
+	let mut game = Game::from_position(position).unwrap();
+
+	game.make_move_from_uci("a7a8q").unwrap();
+	assert_eq!(game.position.export_position_to_fen().unwrap(), "Q3k3/3p4/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Validation
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn kings_are_required_once() {
+	assert_eq!(PositionBuilder::new().place(4, WHITE_KING).build(), Err(SetupError::MissingKing { side: Side::Black }));
+	assert_eq!(kings().place(7, WHITE_KING).build(), Err(SetupError::TooManyKings { side: Side::White }));
+	assert_eq!(kings().place(27, BLACK_KING).place(3, BLACK_KING).build(), Err(SetupError::TooManyKings { side: Side::Black }));
+}
+
+#[test]
+fn piece_counts_are_limited() {
+	let mut builder = kings();
+	for square in 16..25 {
+		builder.place(square, WHITE_PAWN);
+	}
+	assert_eq!(builder.build(), Err(SetupError::TooManyPawns { side: Side::White, count: 9 }));
+
+	// Seven pawns leave room for one promotion only
+	let mut builder = kings();
+	for square in 8..15 {
+		builder.place(square, WHITE_PAWN);
+	}
+	builder.place(40, WHITE_QUEEN).place(41, WHITE_QUEEN);
+	assert!(builder.build().is_ok());
+	builder.place(42, WHITE_QUEEN);
+	assert_eq!(builder.build(), Err(SetupError::TooManyPromotedPieces { side: Side::White }));
+
+	let mut builder = kings();
+	for square in 8..24 {
+		builder.place(square, WHITE_QUEEN);
+	}
+	assert_eq!(builder.build(), Err(SetupError::TooManyPieces { side: Side::White, count: 17 }));
+}
+
+#[test]
+fn pawns_cant_stand_on_the_back_ranks() {
+	assert_eq!(kings().place(0, WHITE_PAWN).build(), Err(SetupError::PawnOnBackRank { square: 0 }));
+	assert_eq!(kings().place(63, BLACK_PAWN).build(), Err(SetupError::PawnOnBackRank { square: 63 }));
+}
+
+#[test]
+fn king_placement_is_validated() {
+	assert_eq!(PositionBuilder::new().place(4, WHITE_KING).place(13, BLACK_KING).build(), Err(SetupError::KingsAreAdjacent));
+
+	// The rook on e2 checks the black king while white is to move
+	assert_eq!(kings().place(12, WHITE_ROOK).build(), Err(SetupError::OpponentInCheck { side: Side::Black }));
+	assert!(kings().place(12, WHITE_ROOK).side_to_move(Side::Black).build().is_ok());
+
+	let triple_check = PositionBuilder::new()
+		.place(0, WHITE_KING)
+		.place(36, BLACK_KING)
+		.place(28, WHITE_QUEEN)
+		.place(32, WHITE_ROOK)
+		.place(29, WHITE_PAWN)
+		.side_to_move(Side::Black)
+		.build();
+	assert_eq!(triple_check, Err(SetupError::TooManyCheckers { count: 3 }));
+}
+
+#[test]
+fn castling_rights_need_king_and_rook_at_home() {
+	assert_eq!(kings().castling(Side::White, true, true).build(), Err(SetupError::InvalidCastlingRights { side: Side::White, king_side: true }));
+	assert_eq!(kings().place(7, WHITE_ROOK).castling(Side::White, false, true).build(), Err(SetupError::InvalidCastlingRights { side: Side::White, king_side: false }));
+	assert!(kings().place(7, WHITE_ROOK).castling(Side::White, true, true).build().is_ok());
+}
+
+#[test]
+fn en_passant_needs_a_pawn_that_just_moved_two_squares() {
+	// Black just played d7d5
+	let mut builder = kings();
+	builder.place(35, BLACK_PAWN).place(36, WHITE_PAWN).en_passant(Some(43));
+	assert!(builder.build().is_ok());
+
+	assert_eq!(builder.en_passant(Some(42)).build(), Err(SetupError::InvalidEnPassant { square: 42 }));
+	assert_eq!(builder.en_passant(Some(19)).build(), Err(SetupError::InvalidEnPassant { square: 19 }));
+	assert_eq!(builder.en_passant(Some(64)).build(), Err(SetupError::NotASquareOnBoard { square: 64 }));
+	assert_eq!(builder.en_passant(Some(43)).place(51, BLACK_PAWN).build(), Err(SetupError::InvalidEnPassant { square: 43 }));
+}
+
+#[test]
+fn fullmove_number_starts_at_one() {
+	assert_eq!(kings().fullmove_number(0).build(), Err(SetupError::InvalidFullmoveNumber));
+}
+
+#[test]
+fn setup_errors_convert_into_chess_errors() {
+	let error = ChessError::from(SetupError::KingsAreAdjacent);
+
+	assert_eq!(error.to_string(), "invalid position: the kings stand next to each other");
+	assert!(std::error::Error::source(&error).is_some());
+}
@@ -0,0 +1,167 @@
+use crate::{
+	Piece, Side,
+	converter::{convert_square_to_string, convert_string_to_square, letter_to_piece},
+	errors::{ChessError, PgnError},
+	helper::{file, rank},
+	moves::make_move::{Move, MoveKind},
+	position::Position,
+};
+
+impl Position {
+	/// Converts a legal move into standard algebraic notation, including check and mate suffixes
+	pub fn move_to_san(&self, mv: &Move) -> Result<String, ChessError> {
+		let mut position = *self;
+		let mut san = String::new();
+
+		match mv.move_kind {
+			MoveKind::Castling { rook_from, .. } => {
+				if rook_from > mv.from_square {
+					san.push_str("O-O");
+				} else {
+					san.push_str("O-O-O");
+				}
+			}
+			_ => {
+				let is_capture = match mv.move_kind {
+					MoveKind::Capture | MoveKind::EnPassant { .. } => true,
+					MoveKind::Promotion { .. } => position.board[mv.to_square as usize].is_some(),
+					_ => false,
+				};
+
+				if mv.colored_piece.piece == Piece::Pawn {
+					if is_capture {
+						san.push((b'a' + file(mv.from_square)) as char);
+					}
+				} else {
+					san.push(mv.colored_piece.piece.to_char().to_ascii_uppercase());
+					san.push_str(&position.disambiguation(mv)?);
+				}
+
+				if is_capture {
+					san.push('x');
+				}
+
+				san.push_str(&convert_square_to_string(mv.to_square));
+
+				if let MoveKind::Promotion { promotion_piece } = mv.move_kind {
+					san.push('=');
+					san.push(promotion_piece.to_char().to_ascii_uppercase());
+				}
+			}
+		}
+
+		let undo = position.make_move_unvalidated(*mv)?;
+		if position.is_king_in_check(position.side_to_move)?.is_some() {
+			if position.get_all_legal_moves_for_side(position.side_to_move)?.is_empty() {
+				san.push('#');
+			} else {
+				san.push('+');
+			}
+		}
+		position.undo_move(undo, *mv)?;
+
+		Ok(san)
+	}
+
+	/// Finds the legal move described by a SAN string. Accepts trailing check, mate and annotation
+	/// glyphs, `0-0` style castling and promotions with or without `=`.
+	pub fn san_to_move(&self, san: &str) -> Result<Move, ChessError> {
+		let mut position = *self;
+		let legal_moves = position.get_all_legal_moves_for_side(position.side_to_move)?;
+		let trimmed = san.trim_end_matches(['+', '#', '!', '?']);
+
+		if trimmed == "O-O" || trimmed == "0-0" || trimmed == "O-O-O" || trimmed == "0-0-0" {
+			let king_side = trimmed.len() == 3;
+			return legal_moves
+				.into_iter()
+				.find(|m| matches!(m.move_kind, MoveKind::Castling { rook_from, .. } if (rook_from > m.from_square) == king_side))
+				.ok_or_else(|| PgnError::IllegalMove(san.to_string()).into());
+		}
+
+		let mut chars: Vec<char> = trimmed.chars().filter(|&c| c != 'x' && c != '-' && c != '=').collect();
+
+		let piece = match chars.first() {
+			Some(&c) if "KQRBN".contains(c) => {
+				chars.remove(0);
+				letter_to_piece(c)?
+			}
+			Some(_) => Piece::Pawn,
+			None => return Err(PgnError::InvalidSan(san.to_string()).into()),
+		};
+
+		let promotion_piece = match chars.last() {
+			Some(&c) if piece == Piece::Pawn && "QRBNqrbn".contains(c) && c != 'b' => {
+				chars.pop();
+				Some(letter_to_piece(c)?)
+			}
+			_ => None,
+		};
+
+		if chars.len() < 2 {
+			return Err(PgnError::InvalidSan(san.to_string()).into());
+		}
+
+		let destination: String = chars[chars.len() - 2..].iter().collect();
+		let to_square = convert_string_to_square(&destination).map_err(|_| PgnError::InvalidSan(san.to_string()))?;
+
+		let mut from_file: Option<u8> = None;
+		let mut from_rank: Option<u8> = None;
+		for &c in &chars[..chars.len() - 2] {
+			match c {
+				'a'..='h' => from_file = Some(c as u8 - b'a'),
+				'1'..='8' => from_rank = Some(c as u8 - b'1'),
+				_ => return Err(PgnError::InvalidSan(san.to_string()).into()),
+			}
+		}
+
+		let candidates: Vec<Move> = legal_moves
+			.into_iter()
+			.filter(|m| {
+				m.colored_piece.piece == piece
+					&& m.to_square == to_square
+					&& !matches!(m.move_kind, MoveKind::Castling { .. })
+					&& from_file.is_none_or(|f| file(m.from_square) == f)
+					&& from_rank.is_none_or(|r| rank(m.from_square) == r)
+					&& match m.move_kind {
+						MoveKind::Promotion { promotion_piece: p } => Some(p) == promotion_piece,
+						_ => promotion_piece.is_none(),
+					}
+			})
+			.collect();
+
+		match candidates.as_slice() {
+			[mv] => Ok(*mv),
+			[] => Err(PgnError::IllegalMove(san.to_string()).into()),
+			_ => Err(PgnError::AmbiguousSan(san.to_string()).into()),
+		}
+	}
+
+	/// File, rank or full square of the moving piece when another piece of the same type can reach
+	/// the same target square
+	fn disambiguation(&mut self, mv: &Move) -> Result<String, ChessError> {
+		let side: Side = mv.colored_piece.side;
+		let others: Vec<Move> = self
+			.get_all_legal_moves_for_side(side)?
+			.into_iter()
+			.filter(|m| m.colored_piece == mv.colored_piece && m.to_square == mv.to_square && m.from_square != mv.from_square)
+			.collect();
+
+		if others.is_empty() {
+			return Ok(String::new());
+		}
+
+		let square_string = convert_square_to_string(mv.from_square);
+		if others.iter().all(|m| file(m.from_square) != file(mv.from_square)) {
+			return Ok(square_string[..1].to_string());
+		}
+
+		if others.iter().all(|m| rank(m.from_square) != rank(mv.from_square)) {
+			return Ok(square_string[1..].to_string());
+		}
+
+		Ok(square_string)
+	}
+}
+
+#[cfg(test)]
+mod tests;
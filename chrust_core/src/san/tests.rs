@@ -0,0 +1,126 @@
+use crate::errors::{ChessError, PgnError};
+use crate::position::load_position_from_fen;
+use crate::test_common::TEST_FENS;
+
+fn san(fen: &str, uci: &str) -> String {
+	let game = crate::position::Game::try_from_fen(fen).unwrap();
+	let mv = game.convert_uci_to_move(uci).unwrap();
+	game.position.move_to_san(&mv).unwrap()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// move_to_san tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn move_to_san_pawn_push_and_knight_move() {
+	assert_eq!(san(TEST_FENS[0], "e2e4"), "e4");
+	assert_eq!(san(TEST_FENS[0], "g1f3"), "Nf3");
+}
+
+#[test]
+fn move_to_san_captures() {
+	assert_eq!(san("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", "e4d5"), "exd5");
+	assert_eq!(san("r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4", "b5c6"), "Bxc6");
+}
+
+#[test]
+fn move_to_san_en_passant() {
+	assert_eq!(san("4k3/ppp1pppp/8/3pP3/8/8/PPP2PPP/4K3 w - d6 0 1", "e5d6"), "exd6");
+}
+
+#[test]
+fn move_to_san_castling() {
+	assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"), "O-O");
+	assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8c8"), "O-O-O");
+}
+
+#[test]
+fn move_to_san_promotion_with_check() {
+	assert_eq!(san("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), "b8=Q+");
+	assert_eq!(san("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8n"), "b8=N");
+}
+
+#[test]
+fn move_to_san_checkmate() {
+	assert_eq!(san("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", "h5f7"), "Qxf7#");
+}
+
+#[test]
+fn move_to_san_disambiguation_by_file() {
+	assert_eq!(san("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1", "a1d1"), "Rad1");
+	assert_eq!(san("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1", "f1d1"), "Rfd1");
+}
+
+#[test]
+fn move_to_san_disambiguation_by_rank() {
+	assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+}
+
+#[test]
+fn move_to_san_disambiguation_by_square() {
+	assert_eq!(san("2k5/8/8/8/Q6Q/8/8/1K5Q w - - 0 1", "h4e4"), "Qh4e4");
+	assert_eq!(san("2k5/8/8/8/Q6Q/8/8/1K5Q w - - 0 1", "a4e4"), "Qae4");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// san_to_move tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn san_to_move_round_trips_all_legal_moves() {
+	for fen in TEST_FENS {
+		let mut pos = load_position_from_fen(fen).unwrap();
+		for mv in pos.get_all_legal_moves_for_side(pos.side_to_move).unwrap() {
+			let san = pos.move_to_san(&mv).unwrap();
+			assert_eq!(pos.san_to_move(&san).unwrap(), mv, "{} in {}", san, fen);
+		}
+	}
+}
+
+#[test]
+fn san_to_move_accepts_variants() {
+	let pos = load_position_from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+	assert_eq!(pos.san_to_move("0-0").unwrap(), pos.san_to_move("O-O").unwrap());
+	assert_eq!(pos.san_to_move("b8Q").unwrap(), pos.san_to_move("b8=Q").unwrap());
+	assert_eq!(pos.san_to_move("Rd1!?").unwrap(), pos.san_to_move("Rd1").unwrap());
+}
+
+#[test]
+fn san_to_move_ambiguous() {
+	let pos = load_position_from_fen("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1").unwrap();
+
+	assert_eq!(
+		pos.san_to_move("Rd1"),
+		Err(ChessError::PgnError {
+			pgn_error: PgnError::AmbiguousSan("Rd1".to_string())
+		})
+	);
+}
+
+#[test]
+fn san_to_move_illegal() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(
+		pos.san_to_move("e5"),
+		Err(ChessError::PgnError {
+			pgn_error: PgnError::IllegalMove("e5".to_string())
+		})
+	);
+	assert_eq!(
+		pos.san_to_move("O-O"),
+		Err(ChessError::PgnError {
+			pgn_error: PgnError::IllegalMove("O-O".to_string())
+		})
+	);
+}
+
+#[test]
+fn san_to_move_invalid() {
+	let pos = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert!(matches!(pos.san_to_move("Nz9"), Err(ChessError::PgnError { pgn_error: PgnError::InvalidSan(_) })));
+	assert!(matches!(pos.san_to_move(""), Err(ChessError::PgnError { pgn_error: PgnError::InvalidSan(_) })));
+}
@@ -0,0 +1,814 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicBool, AtomicU64, Ordering},
+	},
+	thread,
+	time::Duration,
+};
+
+#[cfg(feature = "nnue")]
+use crate::nnue::{Network, NnueEvaluator};
+use crate::{
+	Piece, Side,
+	clock::SystemTimeSource,
+	errors::ChessError,
+	evaluation::piece_value,
+	moves::make_move::{Move, MoveKind},
+	position::{Game, Position, Undo},
+	time_manager::{SearchLimits, TimeManager},
+	transposition_table::{Bound, TranspositionTable, TtEntry},
+};
+
+pub const MATE_SCORE: i32 = 30_000;
+pub const MAX_PLY: usize = 64;
+
+const INFINITY: i32 = 32_000;
+pub(crate) const MATE_THRESHOLD: i32 = MATE_SCORE - MAX_PLY as i32;
+const CHECK_INTERVAL: u64 = 1024;
+const MAX_HISTORY: i32 = 50_000;
+const REVERSE_FUTILITY_MAX_DEPTH: i32 = 6;
+const REVERSE_FUTILITY_MARGIN: i32 = 80;
+const RAZORING_MAX_DEPTH: i32 = 2;
+const RAZORING_MARGIN: i32 = 300;
+const NULL_MOVE_MIN_DEPTH: i32 = 3;
+const LMR_MIN_DEPTH: i32 = 3;
+const LMR_MIN_MOVE_INDEX: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SearchOptions {
+	/// Number of Lazy SMP threads. With one thread and a depth or node limit the search is deterministic.
+	pub threads: usize,
+	pub hash_size_mb: usize,
+	/// Skips nodes where passing the turn still fails high
+	pub null_move_pruning: bool,
+	/// Searches late quiet moves with less depth, re-searching them if they turn out good
+	pub late_move_reductions: bool,
+	/// Cuts nodes near the leaves whose static evaluation is far above beta
+	pub reverse_futility_pruning: bool,
+	/// Drops into quiescence search at low depth when the static evaluation is far below alpha
+	pub razoring: bool,
+	/// Searches one ply deeper when in check
+	pub check_extensions: bool,
+	/// Number of best root moves to search with an exact score (the UCI `MultiPV` option)
+	pub multi_pv: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+	/// `None` if the side to move has no legal moves
+	pub best_move: Option<Move>,
+	/// Centipawns from the point of view of the side to move, see `mate_in` for mate scores
+	pub score: i32,
+	pub depth: u8,
+	pub pv: Vec<Move>,
+	pub nodes: u64,
+	/// The `multi_pv` best root lines, best first
+	pub lines: Vec<SearchLine>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchLine {
+	pub score: i32,
+	pub depth: u8,
+	pub pv: Vec<Move>,
+}
+
+/// Progress of a running search, reported every time a root line is finished
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchInfo {
+	pub depth: u8,
+	/// 1 for the first line of an iteration, up to `multi_pv`
+	pub multi_pv: usize,
+	pub score: i32,
+	pub pv: Vec<Move>,
+	pub nodes: u64,
+	pub time: Duration,
+}
+
+pub type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+/// Alpha-beta search with iterative deepening. Threads share the transposition table and nothing
+/// else, helper threads search with a depth offset so they fill the table with different results.
+pub struct Search {
+	options: SearchOptions,
+	transposition_table: TranspositionTable,
+	info_callback: Option<InfoCallback>,
+	/// Set from outside to stop the running search, see `stop_handle`
+	stop_request: Arc<AtomicBool>,
+	#[cfg(feature = "nnue")]
+	network: Option<Arc<Network>>,
+}
+
+struct SharedState<'a> {
+	transposition_table: &'a TranspositionTable,
+	stop: AtomicBool,
+	stop_request: &'a AtomicBool,
+	nodes: AtomicU64,
+	node_limit: Option<u64>,
+	#[cfg(feature = "nnue")]
+	network: Option<Arc<Network>>,
+}
+
+struct SearchThread<'a> {
+	id: usize,
+	options: SearchOptions,
+	shared: &'a SharedState<'a>,
+	time_manager: Option<TimeManager>,
+	history: Box<[[i32; 64]; 64]>,
+	killers: [[Option<Move>; 2]; MAX_PLY],
+	null_moves: [bool; MAX_PLY],
+	excluded_root_moves: Vec<Move>,
+	info_callback: Option<&'a mut InfoCallback>,
+	#[cfg(feature = "nnue")]
+	nnue: Option<NnueEvaluator>,
+	hashes: Vec<u64>,
+	nodes: u64,
+	reported_nodes: u64,
+	stopped: bool,
+}
+
+impl Default for SearchOptions {
+	fn default() -> Self {
+		SearchOptions {
+			threads: 1,
+			hash_size_mb: 16,
+			null_move_pruning: true,
+			late_move_reductions: true,
+			reverse_futility_pruning: true,
+			razoring: true,
+			check_extensions: true,
+			multi_pv: 1,
+		}
+	}
+}
+
+impl Search {
+	pub fn new(options: SearchOptions) -> Search {
+		Search {
+			options,
+			transposition_table: TranspositionTable::new(options.hash_size_mb),
+			info_callback: None,
+			stop_request: Arc::new(AtomicBool::new(false)),
+			#[cfg(feature = "nnue")]
+			network: None,
+		}
+	}
+
+	pub fn options(&self) -> &SearchOptions {
+		&self.options
+	}
+
+	/// Changes the options, the transposition table is only reallocated if its size changed
+	pub fn set_options(&mut self, options: SearchOptions) {
+		if options.hash_size_mb != self.options.hash_size_mb {
+			self.transposition_table = TranspositionTable::new(options.hash_size_mb);
+		}
+		self.options = options;
+	}
+
+	/// Called from the searching thread whenever a line is finished, e.g. to stream the lines of an
+	/// analysis to the UI
+	pub fn set_info_callback(&mut self, callback: impl FnMut(&SearchInfo) + Send + 'static) {
+		self.info_callback = Some(Box::new(callback));
+	}
+
+	pub fn remove_info_callback(&mut self) {
+		self.info_callback = None;
+	}
+
+	/// Flag that stops the running search as soon as it is set, the search then returns the best
+	/// move found so far. The search never clears it, so it has to be cleared before the next one.
+	pub fn stop_handle(&self) -> Arc<AtomicBool> {
+		Arc::clone(&self.stop_request)
+	}
+
+	/// Evaluates with `network` instead of the handcrafted evaluation, `None` switches back
+	#[cfg(feature = "nnue")]
+	pub fn set_network(&mut self, network: Option<Arc<Network>>) {
+		self.network = network;
+	}
+
+	/// Forgets everything learned from previous searches
+	pub fn new_game(&mut self) {
+		self.transposition_table.clear();
+	}
+
+	/// Searches `position`. `history` holds the hashes of the positions played before it and is used
+	/// to detect repetitions.
+	pub fn search(&mut self, position: &Position, history: &[u64], limits: &SearchLimits) -> Result<SearchResult, ChessError> {
+		let time_manager = TimeManager::new(limits, position.side_to_move, Box::new(SystemTimeSource::new()));
+
+		self.search_with_time_manager(position, history, limits, time_manager)
+	}
+
+	pub fn search_with_time_manager(&mut self, position: &Position, history: &[u64], limits: &SearchLimits, time_manager: TimeManager) -> Result<SearchResult, ChessError> {
+		let shared = SharedState {
+			transposition_table: &self.transposition_table,
+			stop: AtomicBool::new(false),
+			stop_request: &self.stop_request,
+			nodes: AtomicU64::new(0),
+			node_limit: limits.nodes,
+			#[cfg(feature = "nnue")]
+			network: self.network.clone(),
+		};
+		let max_depth = limits.depth.unwrap_or(MAX_PLY as u8 - 1).clamp(1, MAX_PLY as u8 - 1);
+		let threads = self.options.threads.max(1);
+		let options = self.options;
+		let info_callback = self.info_callback.as_mut();
+
+		let result = thread::scope(|scope| {
+			let shared = &shared;
+			let helpers: Vec<_> = (1..threads)
+				.map(|id| {
+					let mut position = *position;
+					scope.spawn(move || SearchThread::new(id, options, shared, None, None, history, &position).iterative_deepening(&mut position, max_depth))
+				})
+				.collect();
+
+			let mut root = *position;
+			let result = SearchThread::new(0, options, shared, Some(time_manager), info_callback, history, &root).iterative_deepening(&mut root, max_depth);
+			shared.stop.store(true, Ordering::Relaxed);
+
+			for helper in helpers {
+				helper.join().expect("search.rs: search thread panicked")?;
+			}
+
+			result
+		})?;
+
+		Ok(SearchResult {
+			nodes: shared.nodes.load(Ordering::Relaxed),
+			..result
+		})
+	}
+}
+
+impl Game {
+	/// Searches the current position, using the moves played so far for repetition detection
+	pub fn search_best_move(&self, search: &mut Search, limits: &SearchLimits) -> Result<SearchResult, ChessError> {
+		// The last entry of the hash history is the current position
+		let history = &self.hash_history[..self.hash_history.len().saturating_sub(1)];
+
+		search.search(&self.position, history, limits)
+	}
+}
+
+/// Moves until mate for mate scores, negative if the side to move gets mated
+pub fn mate_in(score: i32) -> Option<i32> {
+	if score > MATE_THRESHOLD {
+		Some((MATE_SCORE - score + 1) / 2)
+	} else if score < -MATE_THRESHOLD {
+		Some(-(MATE_SCORE + score) / 2)
+	} else {
+		None
+	}
+}
+
+impl<'a> SearchThread<'a> {
+	fn new(
+		id: usize, options: SearchOptions, shared: &'a SharedState<'a>, time_manager: Option<TimeManager>, info_callback: Option<&'a mut InfoCallback>, history: &[u64], root: &Position,
+	) -> SearchThread<'a> {
+		let mut hashes = history.to_vec();
+		hashes.push(root.zobrist_hash);
+
+		SearchThread {
+			id,
+			options,
+			shared,
+			time_manager,
+			history: Box::new([[0; 64]; 64]),
+			killers: [[None; 2]; MAX_PLY],
+			null_moves: [false; MAX_PLY],
+			excluded_root_moves: Vec::new(),
+			info_callback,
+			#[cfg(feature = "nnue")]
+			nnue: shared.network.as_ref().map(|network| NnueEvaluator::new(Arc::clone(network), root)),
+			hashes,
+			nodes: 0,
+			reported_nodes: 0,
+			stopped: false,
+		}
+	}
+
+	fn iterative_deepening(&mut self, root: &mut Position, max_depth: u8) -> Result<SearchResult, ChessError> {
+		let mut root_moves = root.get_all_legal_moves_for_side(root.side_to_move)?;
+		self.order_moves(root, &mut root_moves, None, 0);
+
+		let mut result = SearchResult {
+			best_move: root_moves.first().copied(),
+			score: 0,
+			depth: 0,
+			pv: root_moves.first().copied().into_iter().collect(),
+			nodes: 0,
+			lines: Vec::new(),
+		};
+
+		if root_moves.is_empty() {
+			result.score = match root.is_king_in_check(root.side_to_move)? {
+				Some(_) => -MATE_SCORE,
+				None => 0,
+			};
+			return Ok(result);
+		}
+
+		if root_moves.len() == 1
+			&& let Some(time_manager) = self.time_manager.as_mut()
+		{
+			time_manager.set_single_reply();
+		}
+
+		// Only the main thread searches more than one line, helpers just fill the table
+		let multi_pv = if self.id == 0 {
+			self.options.multi_pv.clamp(1, root_moves.len())
+		} else {
+			1
+		};
+
+		for depth in 1..=max_depth {
+			// Helper threads search every other iteration one ply deeper
+			let search_depth = (depth + (self.id % 2) as u8).min(max_depth);
+
+			let lines = self.search_root_lines(root, search_depth, multi_pv)?;
+			if lines.len() < multi_pv {
+				break;
+			}
+
+			let best_move_changed = result.best_move != lines[0].pv.first().copied();
+			result = SearchResult {
+				best_move: lines[0].pv.first().copied(),
+				score: lines[0].score,
+				depth: search_depth,
+				pv: lines[0].pv.clone(),
+				nodes: self.nodes,
+				lines,
+			};
+
+			if let Some(time_manager) = self.time_manager.as_mut() {
+				time_manager.report_iteration(best_move_changed, result.score);
+				if time_manager.should_stop_iterating() {
+					break;
+				}
+			}
+
+			if self.shared.stop_request.load(Ordering::Relaxed) {
+				break;
+			}
+
+			if mate_in(result.score).is_some_and(|moves| moves.unsigned_abs() as u8 * 2 <= search_depth) {
+				break;
+			}
+		}
+
+		self.report_nodes();
+
+		Ok(result)
+	}
+
+	/// Searches the root `multi_pv` times, every time without the moves of the lines found before.
+	/// Returns fewer lines if the search was stopped.
+	fn search_root_lines(&mut self, root: &mut Position, depth: u8, multi_pv: usize) -> Result<Vec<SearchLine>, ChessError> {
+		let mut lines: Vec<SearchLine> = Vec::new();
+		self.excluded_root_moves.clear();
+
+		for index in 0..multi_pv {
+			let mut pv = Vec::new();
+			let score = self.negamax(root, depth as i32, 0, -INFINITY, INFINITY, &mut pv)?;
+
+			if self.stopped || pv.is_empty() {
+				break;
+			}
+
+			self.excluded_root_moves.push(pv[0]);
+			self.report_line(index + 1, depth, score, &pv);
+			lines.push(SearchLine { score, depth, pv });
+		}
+		self.excluded_root_moves.clear();
+
+		// A later line can come out higher because of search instability
+		lines.sort_by_key(|line| std::cmp::Reverse(line.score));
+
+		Ok(lines)
+	}
+
+	fn report_line(&mut self, multi_pv: usize, depth: u8, score: i32, pv: &[Move]) {
+		if self.info_callback.is_none() {
+			return;
+		}
+
+		self.report_nodes();
+		let info = SearchInfo {
+			depth,
+			multi_pv,
+			score,
+			pv: pv.to_vec(),
+			nodes: self.shared.nodes.load(Ordering::Relaxed),
+			time: self.time_manager.as_ref().map(|time_manager| time_manager.elapsed()).unwrap_or_default(),
+		};
+
+		if let Some(callback) = self.info_callback.as_mut() {
+			callback(&info);
+		}
+	}
+
+	fn negamax(&mut self, position: &mut Position, depth: i32, ply: usize, mut alpha: i32, mut beta: i32, pv: &mut Vec<Move>) -> Result<i32, ChessError> {
+		pv.clear();
+
+		if self.should_stop() {
+			return Ok(0);
+		}
+		self.nodes += 1;
+
+		let is_root = ply == 0;
+		if !is_root {
+			if self.is_draw(position) {
+				return Ok(0);
+			}
+
+			// A mate found closer to the root can't be improved on
+			alpha = alpha.max(-MATE_SCORE + ply as i32);
+			beta = beta.min(MATE_SCORE - ply as i32 - 1);
+			if alpha >= beta {
+				return Ok(alpha);
+			}
+		}
+
+		if ply >= MAX_PLY - 1 {
+			return Ok(self.evaluate(position));
+		}
+
+		let in_check = position.is_king_in_check(position.side_to_move)?.is_some();
+		let depth = if in_check && self.options.check_extensions {
+			depth + 1
+		} else {
+			depth
+		};
+
+		if depth <= 0 {
+			return self.quiescence(position, ply, alpha, beta);
+		}
+
+		let is_pv = beta - alpha > 1;
+		let tt_entry = self.shared.transposition_table.probe(position.zobrist_hash);
+		if let Some(entry) = tt_entry
+			&& !is_pv
+			&& entry.depth as i32 >= depth
+		{
+			let score = score_from_tt(entry.score, ply);
+			match entry.bound {
+				Bound::Exact => return Ok(score),
+				Bound::Lower if score >= beta => return Ok(score),
+				Bound::Upper if score <= alpha => return Ok(score),
+				_ => {}
+			}
+		}
+
+		if !is_pv && !in_check {
+			let static_eval = self.evaluate(position);
+
+			if self.options.reverse_futility_pruning && depth <= REVERSE_FUTILITY_MAX_DEPTH && beta.abs() < MATE_THRESHOLD && static_eval - REVERSE_FUTILITY_MARGIN * depth >= beta {
+				return Ok(static_eval);
+			}
+
+			if self.options.razoring && depth <= RAZORING_MAX_DEPTH && static_eval + RAZORING_MARGIN * depth < alpha {
+				let score = self.quiescence(position, ply, alpha, alpha + 1)?;
+				if score <= alpha {
+					return Ok(score);
+				}
+			}
+
+			// Zugzwang is common without pieces, so passing could be the best move there
+			if self.options.null_move_pruning && depth >= NULL_MOVE_MIN_DEPTH && static_eval >= beta && !is_root && !self.null_moves[ply - 1] && has_non_pawn_material(position, position.side_to_move) {
+				let reduction = 2 + depth / 4;
+
+				let undo = self.make_null_move(position);
+				self.hashes.push(position.zobrist_hash);
+				self.null_moves[ply] = true;
+
+				let score = -self.negamax(position, depth - 1 - reduction, ply + 1, -beta, -beta + 1, &mut Vec::new())?;
+
+				self.null_moves[ply] = false;
+				self.hashes.pop();
+				self.undo_null_move(position, undo);
+
+				if self.stopped {
+					return Ok(0);
+				}
+
+				// An unproven mate from a null move search isn't trusted
+				if score >= beta {
+					return Ok(if score > MATE_THRESHOLD {
+						beta
+					} else {
+						score
+					});
+				}
+			}
+		}
+
+		let mut moves = position.get_all_legal_moves_for_side(position.side_to_move)?;
+		if moves.is_empty() {
+			return Ok(if in_check {
+				-MATE_SCORE + ply as i32
+			} else {
+				0
+			});
+		}
+		if is_root {
+			moves.retain(|mv| !self.excluded_root_moves.contains(mv));
+		}
+		self.order_moves(position, &mut moves, tt_entry.and_then(|entry| entry.best_move), ply);
+
+		let original_alpha = alpha;
+		let mut best_score = -INFINITY;
+		let mut best_move = None;
+		let mut child_pv = Vec::new();
+
+		for (index, mv) in moves.iter().enumerate() {
+			let is_quiet = !is_capture(position, mv) && !matches!(mv.move_kind, MoveKind::Promotion { .. });
+			let is_killer = self.killers[ply].contains(&Some(*mv));
+
+			let undo = self.make_move(position, *mv)?;
+			self.hashes.push(position.zobrist_hash);
+
+			// Principal variation search: only the first move gets a full window
+			let mut score;
+			if index == 0 {
+				score = -self.negamax(position, depth - 1, ply + 1, -beta, -alpha, &mut child_pv)?;
+			} else {
+				let gives_check = position.is_king_in_check(position.side_to_move)?.is_some();
+				let reduction = if self.options.late_move_reductions && depth >= LMR_MIN_DEPTH && index >= LMR_MIN_MOVE_INDEX && is_quiet && !is_killer && !in_check && !gives_check {
+					late_move_reduction(depth, index)
+				} else {
+					0
+				};
+
+				score = -self.negamax(position, depth - 1 - reduction, ply + 1, -alpha - 1, -alpha, &mut child_pv)?;
+				if reduction > 0 && score > alpha {
+					score = -self.negamax(position, depth - 1, ply + 1, -alpha - 1, -alpha, &mut child_pv)?;
+				}
+				if score > alpha && score < beta {
+					score = -self.negamax(position, depth - 1, ply + 1, -beta, -alpha, &mut child_pv)?;
+				}
+			}
+
+			self.hashes.pop();
+			self.undo_move(position, undo, *mv)?;
+
+			if self.stopped {
+				return Ok(0);
+			}
+
+			if score > best_score {
+				best_score = score;
+				best_move = Some(*mv);
+
+				if score > alpha {
+					alpha = score;
+					pv.clear();
+					pv.push(*mv);
+					pv.extend_from_slice(&child_pv);
+				}
+			}
+
+			if alpha >= beta {
+				if is_quiet {
+					self.update_quiet_stats(mv, depth, ply);
+				}
+				break;
+			}
+		}
+
+		let bound = if best_score >= beta {
+			Bound::Lower
+		} else if best_score > original_alpha {
+			Bound::Exact
+		} else {
+			Bound::Upper
+		};
+
+		// Root results without the excluded moves would replace the real best move
+		if !is_root || self.excluded_root_moves.is_empty() {
+			self.shared.transposition_table.store(
+				position.zobrist_hash,
+				TtEntry {
+					best_move: best_move.and_then(|mv| mv.encode().ok()),
+					score: score_to_tt(best_score, ply),
+					depth: depth as u8,
+					bound,
+				},
+			);
+		}
+
+		Ok(best_score)
+	}
+
+	/// Only searches captures and promotions so the static evaluation isn't taken in the middle of an
+	/// exchange. When in check every evasion is searched, so mates on the horizon are still seen.
+	fn quiescence(&mut self, position: &mut Position, ply: usize, mut alpha: i32, beta: i32) -> Result<i32, ChessError> {
+		if self.should_stop() {
+			return Ok(0);
+		}
+		self.nodes += 1;
+
+		if ply >= MAX_PLY - 1 {
+			return Ok(self.evaluate(position));
+		}
+
+		let in_check = position.is_king_in_check(position.side_to_move)?.is_some();
+		let mut best_score = -MATE_SCORE + ply as i32;
+
+		if !in_check {
+			best_score = self.evaluate(position);
+			if best_score >= beta {
+				return Ok(best_score);
+			}
+			alpha = alpha.max(best_score);
+		}
+
+		let mut moves: Vec<Move> = position
+			.get_all_legal_moves_for_side(position.side_to_move)?
+			.into_iter()
+			.filter(|mv| in_check || is_capture(position, mv) || matches!(mv.move_kind, MoveKind::Promotion { .. }))
+			.collect();
+		self.order_moves(position, &mut moves, None, ply);
+
+		for mv in moves {
+			let undo = self.make_move(position, mv)?;
+			let score = -self.quiescence(position, ply + 1, -beta, -alpha)?;
+			self.undo_move(position, undo, mv)?;
+
+			if self.stopped {
+				return Ok(0);
+			}
+
+			if score >= beta {
+				return Ok(score);
+			}
+			best_score = best_score.max(score);
+			alpha = alpha.max(score);
+		}
+
+		Ok(best_score)
+	}
+
+	fn evaluate(&self, position: &Position) -> i32 {
+		#[cfg(feature = "nnue")]
+		if let Some(nnue) = &self.nnue {
+			return nnue.evaluate(position.side_to_move);
+		}
+
+		position.evaluate()
+	}
+
+	// Moves made in the search go through these so the network accumulators follow along
+
+	fn make_move(&mut self, position: &mut Position, mv: Move) -> Result<Undo, ChessError> {
+		let undo = position.make_move_unvalidated(mv)?;
+
+		#[cfg(feature = "nnue")]
+		if let Some(nnue) = self.nnue.as_mut() {
+			nnue.push_move(position, &mv, &undo);
+		}
+
+		Ok(undo)
+	}
+
+	fn undo_move(&mut self, position: &mut Position, undo: Undo, mv: Move) -> Result<(), ChessError> {
+		#[cfg(feature = "nnue")]
+		if let Some(nnue) = self.nnue.as_mut() {
+			nnue.pop();
+		}
+
+		position.undo_move(undo, mv)
+	}
+
+	fn make_null_move(&mut self, position: &mut Position) -> Undo {
+		#[cfg(feature = "nnue")]
+		if let Some(nnue) = self.nnue.as_mut() {
+			nnue.push_null_move();
+		}
+
+		position.make_null_move()
+	}
+
+	fn undo_null_move(&mut self, position: &mut Position, undo: Undo) {
+		#[cfg(feature = "nnue")]
+		if let Some(nnue) = self.nnue.as_mut() {
+			nnue.pop();
+		}
+
+		position.undo_null_move(undo);
+	}
+
+	/// Transposition table move first, then captures by most valuable victim / least valuable attacker,
+	/// promotions, killer moves and finally quiet moves by their history score
+	fn order_moves(&self, position: &Position, moves: &mut [Move], tt_move: Option<u16>, ply: usize) {
+		let killers = self.killers[ply.min(MAX_PLY - 1)];
+
+		moves.sort_by_cached_key(|mv| {
+			let score = if tt_move.is_some() && tt_move == mv.encode().ok() {
+				1_000_000
+			} else if is_capture(position, mv) {
+				let victim = position.board[mv.to_square as usize].map(|p| piece_value(p.piece)).unwrap_or(piece_value(Piece::Pawn));
+				100_000 + 10 * victim - piece_value(mv.colored_piece.piece)
+			} else if let MoveKind::Promotion { promotion_piece } = mv.move_kind {
+				90_000 + piece_value(promotion_piece)
+			} else if killers[0] == Some(*mv) {
+				80_000
+			} else if killers[1] == Some(*mv) {
+				79_000
+			} else {
+				self.history[mv.from_square as usize][mv.to_square as usize]
+			};
+
+			std::cmp::Reverse(score)
+		});
+	}
+
+	fn update_quiet_stats(&mut self, mv: &Move, depth: i32, ply: usize) {
+		let killers = &mut self.killers[ply];
+		if killers[0] != Some(*mv) {
+			killers[1] = killers[0];
+			killers[0] = Some(*mv);
+		}
+
+		let entry = &mut self.history[mv.from_square as usize][mv.to_square as usize];
+		*entry = (*entry + depth * depth).min(MAX_HISTORY);
+	}
+
+	fn is_draw(&self, position: &Position) -> bool {
+		if position.is_draw_by_fifty_moves() || position.is_insufficient_material() {
+			return true;
+		}
+
+		// A single repetition is enough inside the search, the position would just be repeated again
+		let current = position.zobrist_hash;
+		self.hashes.iter().rev().skip(1).take(position.halfmove_clock as usize).any(|&hash| hash == current)
+	}
+
+	fn should_stop(&mut self) -> bool {
+		if self.stopped {
+			return true;
+		}
+
+		if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+			self.report_nodes();
+
+			let out_of_nodes = self.shared.node_limit.is_some_and(|limit| self.shared.nodes.load(Ordering::Relaxed) >= limit);
+			let out_of_time = self.time_manager.as_ref().is_some_and(|time_manager| time_manager.should_abort());
+
+			if out_of_nodes || out_of_time || self.shared.stop_request.load(Ordering::Relaxed) {
+				self.shared.stop.store(true, Ordering::Relaxed);
+			}
+
+			self.stopped = self.shared.stop.load(Ordering::Relaxed);
+		}
+
+		self.stopped
+	}
+
+	fn report_nodes(&mut self) {
+		self.shared.nodes.fetch_add(self.nodes - self.reported_nodes, Ordering::Relaxed);
+		self.reported_nodes = self.nodes;
+	}
+}
+
+fn has_non_pawn_material(position: &Position, side: Side) -> bool {
+	position.board.iter().flatten().any(|p| p.side == side && !matches!(p.piece, Piece::Pawn | Piece::King))
+}
+
+/// Grows with the depth and with how late the move comes in the ordering, never drops into quiescence
+fn late_move_reduction(depth: i32, index: usize) -> i32 {
+	let reduction = 0.75 + (depth as f64).ln() * (index as f64).ln() / 2.25;
+
+	(reduction as i32).clamp(1, depth - 2)
+}
+
+pub(crate) fn is_capture(position: &Position, mv: &Move) -> bool {
+	position.board[mv.to_square as usize].is_some() || matches!(mv.move_kind, MoveKind::EnPassant { .. })
+}
+
+/// Mate scores are stored relative to the node so they stay correct when found through another path
+fn score_to_tt(score: i32, ply: usize) -> i32 {
+	if score > MATE_THRESHOLD {
+		score + ply as i32
+	} else if score < -MATE_THRESHOLD {
+		score - ply as i32
+	} else {
+		score
+	}
+}
+
+fn score_from_tt(score: i32, ply: usize) -> i32 {
+	if score > MATE_THRESHOLD {
+		score - ply as i32
+	} else if score < -MATE_THRESHOLD {
+		score + ply as i32
+	} else {
+		score
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use super::*;
+use crate::{clock::ManualTimeSource, converter::convert_string_to_square, position::load_position_from_fen, test_common::game_from_fen};
+
+fn search_fen(fen: &str, options: SearchOptions, limits: SearchLimits) -> SearchResult {
+	let position = load_position_from_fen(fen).unwrap();
+
+	Search::new(options).search(&position, &[], &limits).unwrap()
+}
+
+fn depth(depth: u8) -> SearchLimits {
+	SearchLimits { depth: Some(depth), ..Default::default() }
+}
+
+fn assert_best_move(result: &SearchResult, from: &str, to: &str) {
+	let best_move = result.best_move.expect("search found no move");
+
+	assert_eq!(best_move.from_square, convert_string_to_square(from).unwrap());
+	assert_eq!(best_move.to_square, convert_string_to_square(to).unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Search tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn finds_mate_in_one() {
+	let result = search_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", SearchOptions::default(), depth(3));
+
+	assert_best_move(&result, "a1", "a8");
+	assert_eq!(mate_in(result.score), Some(1));
+}
+
+#[test]
+fn finds_mate_in_two() {
+	// 1.Ra6 puts black in zugzwang, which null move pruning can't see
+	let options = SearchOptions {
+		null_move_pruning: false,
+		..Default::default()
+	};
+	let result = search_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1", options, depth(6));
+
+	assert_best_move(&result, "a1", "a6");
+	assert_eq!(mate_in(result.score), Some(2));
+	assert_eq!(result.pv.len(), 3);
+}
+
+#[test]
+fn captures_hanging_queen() {
+	let result = search_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", SearchOptions::default(), depth(2));
+
+	assert_best_move(&result, "d2", "d5");
+	assert!(result.score > 300);
+}
+
+#[test]
+fn checkmated_side_has_no_move() {
+	let result = search_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1", SearchOptions::default(), depth(3));
+
+	assert_eq!(result.best_move, None);
+	assert_eq!(result.score, -MATE_SCORE);
+}
+
+#[test]
+fn stalemated_side_scores_draw() {
+	let result = search_fen("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1", SearchOptions::default(), depth(3));
+
+	assert_eq!(result.best_move, None);
+	assert_eq!(result.score, 0);
+}
+
+#[test]
+fn single_thread_search_is_deterministic() {
+	let fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+
+	let first = search_fen(fen, SearchOptions::default(), depth(3));
+	let second = search_fen(fen, SearchOptions::default(), depth(3));
+
+	assert_eq!(first, second);
+	assert_eq!(first.depth, 3);
+}
+
+#[test]
+fn multi_threaded_search_finds_mate() {
+	let options = SearchOptions {
+		threads: 4,
+		hash_size_mb: 4,
+		..Default::default()
+	};
+	let result = search_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", options, depth(3));
+
+	assert_best_move(&result, "a1", "a8");
+	assert_eq!(mate_in(result.score), Some(1));
+}
+
+#[test]
+fn node_limit_stops_search() {
+	let limits = SearchLimits { nodes: Some(2000), ..Default::default() };
+	let result = search_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", SearchOptions::default(), limits);
+
+	assert!(result.best_move.is_some());
+	assert!(result.nodes < 2000 + CHECK_INTERVAL);
+	assert!(result.depth < MAX_PLY as u8 - 1);
+}
+
+#[test]
+fn expired_time_still_returns_a_move() {
+	let position = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let limits = SearchLimits {
+		movetime: Some(Duration::ZERO),
+		..Default::default()
+	};
+	let time_manager = TimeManager::new(&limits, position.side_to_move, Box::new(ManualTimeSource::default()));
+
+	let result = Search::new(SearchOptions::default()).search_with_time_manager(&position, &[], &limits, time_manager).unwrap();
+
+	assert!(result.best_move.is_some());
+	assert_eq!(result.depth, 0);
+}
+
+#[test]
+fn stop_handle_ends_an_unlimited_search() {
+	let position = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let mut search = Search::new(SearchOptions { threads: 2, ..Default::default() });
+	let stop = search.stop_handle();
+
+	let stopper = thread::spawn(move || {
+		thread::sleep(Duration::from_millis(100));
+		stop.store(true, Ordering::Relaxed);
+	});
+	let result = search.search(&position, &[], &SearchLimits::default()).unwrap();
+	stopper.join().unwrap();
+
+	assert!(result.best_move.is_some());
+	assert!(result.depth < MAX_PLY as u8 - 1);
+}
+
+#[test]
+fn stop_requested_before_the_search_still_returns_a_move() {
+	let position = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let mut search = Search::new(SearchOptions::default());
+	search.stop_handle().store(true, Ordering::Relaxed);
+
+	let result = search.search(&position, &[], &SearchLimits::default()).unwrap();
+
+	assert!(result.best_move.is_some());
+	assert!(result.depth <= 1);
+	assert!(search.stop_handle().load(Ordering::Relaxed), "the search leaves the flag to its owner");
+}
+
+#[test]
+fn single_reply_stops_after_first_iteration() {
+	let position = load_position_from_fen("k7/2R5/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+	let limits = SearchLimits {
+		btime: Some(Duration::from_secs(60)),
+		..Default::default()
+	};
+	let time_manager = TimeManager::new(&limits, position.side_to_move, Box::new(ManualTimeSource::default()));
+
+	let result = Search::new(SearchOptions::default()).search_with_time_manager(&position, &[], &limits, time_manager).unwrap();
+
+	assert_best_move(&result, "a8", "b8");
+	assert_eq!(result.depth, 1);
+}
+
+#[test]
+fn game_search_uses_repetition_history() {
+	// Black is three pawns up, but white can repeat the position with a perpetual check
+	let mut game = game_from_fen("6k1/5q2/8/8/1pp5/3p4/8/Q5K1 w - - 0 1");
+	for uci in ["a1a8", "g8h7", "a8h1", "h7g8"] {
+		game.make_move_from_uci(uci).unwrap();
+	}
+
+	let result = game.search_best_move(&mut Search::new(SearchOptions::default()), &depth(3)).unwrap();
+
+	assert_best_move(&result, "h1", "a8");
+	assert_eq!(result.score, 0);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Pruning and reduction tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+fn without_pruning() -> SearchOptions {
+	SearchOptions {
+		null_move_pruning: false,
+		late_move_reductions: false,
+		reverse_futility_pruning: false,
+		razoring: false,
+		check_extensions: false,
+		..Default::default()
+	}
+}
+
+#[test]
+fn pruning_searches_fewer_nodes() {
+	let fen = "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+
+	let pruned = search_fen(fen, SearchOptions::default(), depth(4));
+	let full = search_fen(fen, without_pruning(), depth(4));
+
+	assert!(pruned.nodes < full.nodes, "{} >= {}", pruned.nodes, full.nodes);
+}
+
+#[test]
+fn every_technique_alone_keeps_tactics() {
+	let techniques: [fn(&mut SearchOptions); 5] = [
+		|o| o.null_move_pruning = true,
+		|o| o.late_move_reductions = true,
+		|o| o.reverse_futility_pruning = true,
+		|o| o.razoring = true,
+		|o| o.check_extensions = true,
+	];
+
+	for enable in techniques {
+		let mut options = without_pruning();
+		enable(&mut options);
+
+		let mate = search_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1", options, depth(4));
+		assert_best_move(&mate, "a1", "a6");
+		assert_eq!(mate_in(mate.score), Some(2), "{:?}", options);
+
+		let capture = search_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", options, depth(3));
+		assert_best_move(&capture, "d2", "d5");
+	}
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// MultiPV tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+fn multi_pv(lines: usize) -> SearchOptions {
+	SearchOptions { multi_pv: lines, ..Default::default() }
+}
+
+#[test]
+fn multi_pv_returns_distinct_lines_best_first() {
+	let result = search_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", multi_pv(3), depth(3));
+
+	assert_eq!(result.lines.len(), 3);
+	assert_eq!(result.lines[0].pv, result.pv);
+	assert_eq!(result.lines[0].score, result.score);
+
+	let first_moves: Vec<Move> = result.lines.iter().map(|line| line.pv[0]).collect();
+	assert!(first_moves[0] != first_moves[1] && first_moves[1] != first_moves[2] && first_moves[0] != first_moves[2]);
+	assert!(result.lines.windows(2).all(|pair| pair[0].score >= pair[1].score));
+	assert!(result.lines.iter().all(|line| line.depth == 3));
+}
+
+#[test]
+fn multi_pv_is_limited_by_legal_moves() {
+	let result = search_fen("k7/2R5/1K6/8/8/8/8/8 b - - 0 1", multi_pv(5), depth(2));
+
+	assert_eq!(result.lines.len(), 1);
+}
+
+#[test]
+fn multi_pv_ranks_mate_above_other_moves() {
+	let result = search_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", multi_pv(2), depth(3));
+
+	assert_best_move(&result, "a1", "a8");
+	assert_eq!(mate_in(result.lines[0].score), Some(1));
+	assert_eq!(mate_in(result.lines[1].score), None);
+}
+
+#[test]
+fn single_pv_has_one_line() {
+	let result = search_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", SearchOptions::default(), depth(2));
+
+	assert_eq!(result.lines.len(), 1);
+	assert_eq!(result.lines[0].pv, result.pv);
+}
+
+#[test]
+fn info_callback_streams_every_line() {
+	let position = load_position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+	let (sender, receiver) = std::sync::mpsc::channel();
+
+	let mut search = Search::new(multi_pv(2));
+	search.set_info_callback(move |info| sender.send(info.clone()).unwrap());
+	let result = search.search(&position, &[], &depth(3)).unwrap();
+	drop(search);
+
+	let infos: Vec<SearchInfo> = receiver.iter().collect();
+	let reported: Vec<(u8, usize)> = infos.iter().map(|info| (info.depth, info.multi_pv)).collect();
+	assert_eq!(reported, vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2)]);
+	assert!(infos.windows(2).all(|pair| pair[0].nodes <= pair[1].nodes));
+
+	let last_first_line = infos.iter().rfind(|info| info.multi_pv == 1).unwrap();
+	assert_eq!(last_first_line.pv[0], result.lines[0].pv[0]);
+}
+
+#[test]
+fn mate_in_converts_scores() {
+	assert_eq!(mate_in(MATE_SCORE - 1), Some(1));
+	assert_eq!(mate_in(MATE_SCORE - 3), Some(2));
+	assert_eq!(mate_in(-MATE_SCORE + 2), Some(-1));
+	assert_eq!(mate_in(250), None);
+}
@@ -5,6 +5,30 @@ use crate::{
 	position::{Game, Position, load_position_from_fen},
 };
 
+/// Representative positions used by tests that run over many positions at once
+pub const TEST_FENS: &[&str] = &[
+	"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+	"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+	"rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+	"rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2",
+	"r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 1",
+	"r1bqkb1r/pppp1ppp/2n2n2/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+	"r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+	"r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
+	"r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1",
+	"r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1",
+	"r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 3 17",
+	"r3k2r/8/8/pP6/8/8/8/R3K2R w KQkq a6 0 1",
+	"4k3/ppp1pppp/8/3pP3/8/8/PPP2PPP/4K3 w - d6 0 1",
+	"4k3/PPPPPPPP/8/8/8/8/8/4K3 w - - 0 1",
+	"7k/P7/8/8/8/8/8/7K w - - 0 1",
+	"6k1/8/8/8/8/8/6PP/r6K w - - 0 1",
+	"8/8/8/8/8/1q6/2k5/K7 w - - 0 1",
+	"7k/8/5K2/6Q1/8/8/8/8 w - - 0 1",
+	"8/5k2/8/8/3K4/8/4P3/8 w - - 10 50",
+	"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 99 999",
+];
+
 pub fn game_from_fen(fen: &str) -> Game {
 	let position = position_with_hash(fen);
 	Game {
@@ -13,7 +37,9 @@ pub fn game_from_fen(fen: &str) -> Game {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	}
 }
 
@@ -24,7 +50,9 @@ pub fn empty_game() -> Game {
 		hash_history: Vec::new(),
 		move_history: Vec::new(),
 		undo_history: Vec::new(),
+		redo_history: Vec::new(),
 		game_status: GameStatus::Playing,
+		clock: None,
 	}
 }
 
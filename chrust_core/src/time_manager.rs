@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use crate::{Side, clock::TimeSource};
+
+/// Time kept back for communication and move output
+pub const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+const MAX_HARD_LIMIT_FACTOR: u32 = 4;
+const FAIL_LOW_MARGIN: i32 = 30;
+const STABILITY_SCALES: [f64; 5] = [1.6, 1.3, 1.1, 1.0, 0.85];
+const FAIL_LOW_SCALE: f64 = 1.4;
+
+/// Limits as sent with the UCI `go` command
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+	pub wtime: Option<Duration>,
+	pub btime: Option<Duration>,
+	pub winc: Option<Duration>,
+	pub binc: Option<Duration>,
+	pub movestogo: Option<u32>,
+	pub movetime: Option<Duration>,
+	pub depth: Option<u8>,
+	pub nodes: Option<u64>,
+}
+
+/// Decides how long a search may run. The soft limit is checked between iterations and is scaled by
+/// how stable the best move and score are, the hard limit aborts the search at any point.
+pub struct TimeManager {
+	soft_limit: Duration,
+	hard_limit: Duration,
+	start: Duration,
+	best_move_stability: usize,
+	previous_score: Option<i32>,
+	fail_low: bool,
+	single_reply: bool,
+	time_source: Box<dyn TimeSource>,
+}
+
+impl TimeManager {
+	pub fn new(limits: &SearchLimits, side: Side, time_source: Box<dyn TimeSource>) -> TimeManager {
+		let (soft_limit, hard_limit) = compute_limits(limits, side);
+
+		TimeManager {
+			soft_limit,
+			hard_limit,
+			start: time_source.now(),
+			best_move_stability: 0,
+			previous_score: None,
+			fail_low: false,
+			single_reply: false,
+			time_source,
+		}
+	}
+
+	pub fn elapsed(&self) -> Duration {
+		self.time_source.now().saturating_sub(self.start)
+	}
+
+	pub fn hard_limit(&self) -> Duration {
+		self.hard_limit
+	}
+
+	/// Soft limit after applying the stability and fail low adjustments, never above the hard limit
+	pub fn soft_limit(&self) -> Duration {
+		if self.single_reply {
+			return Duration::ZERO;
+		}
+
+		if self.soft_limit == Duration::MAX {
+			return Duration::MAX;
+		}
+
+		let mut scale = STABILITY_SCALES[self.best_move_stability.min(STABILITY_SCALES.len() - 1)];
+		if self.fail_low {
+			scale *= FAIL_LOW_SCALE;
+		}
+
+		self.soft_limit.mul_f64(scale).min(self.hard_limit)
+	}
+
+	/// Only one legal move: the search can stop after the first iteration
+	pub fn set_single_reply(&mut self) {
+		self.single_reply = true;
+	}
+
+	/// Reports a finished iteration of the iterative deepening loop
+	pub fn report_iteration(&mut self, best_move_changed: bool, score: i32) {
+		if best_move_changed {
+			self.best_move_stability = 0;
+		} else {
+			self.best_move_stability += 1;
+		}
+
+		self.fail_low = self.previous_score.is_some_and(|previous| score <= previous - FAIL_LOW_MARGIN);
+		self.previous_score = Some(score);
+	}
+
+	/// Checked between iterations: starting another one would likely overrun the soft limit
+	pub fn should_stop_iterating(&self) -> bool {
+		self.elapsed() >= self.soft_limit()
+	}
+
+	/// Checked inside the search: time is up, the search has to be aborted
+	pub fn should_abort(&self) -> bool {
+		self.elapsed() >= self.hard_limit
+	}
+}
+
+fn compute_limits(limits: &SearchLimits, side: Side) -> (Duration, Duration) {
+	if let Some(movetime) = limits.movetime {
+		let limit = movetime.saturating_sub(MOVE_OVERHEAD);
+		return (limit, limit);
+	}
+
+	let (time, increment) = match side {
+		Side::White => (limits.wtime, limits.winc),
+		Side::Black => (limits.btime, limits.binc),
+	};
+
+	let Some(time) = time else {
+		return (Duration::MAX, Duration::MAX);
+	};
+
+	let increment = increment.unwrap_or_default();
+	let moves_to_go = limits.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+	let available = time.saturating_sub(MOVE_OVERHEAD);
+
+	// Never plan to use more than what is left on the clock for the current time control
+	let hard_cap = if moves_to_go == 1 {
+		available
+	} else {
+		available / 2
+	};
+
+	let soft = (available / moves_to_go + increment * 3 / 4).min(hard_cap);
+	let hard = (soft * MAX_HARD_LIMIT_FACTOR).min(hard_cap);
+
+	(soft, hard)
+}
+
+#[cfg(test)]
+mod tests;
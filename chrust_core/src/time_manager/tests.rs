@@ -0,0 +1,195 @@
+use super::*;
+use crate::clock::ManualTimeSource;
+
+fn ms(millis: u64) -> Duration {
+	Duration::from_millis(millis)
+}
+
+fn manager_with(limits: SearchLimits, side: Side) -> (TimeManager, ManualTimeSource) {
+	let time = ManualTimeSource::default();
+	(TimeManager::new(&limits, side, Box::new(time.clone())), time)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Limit calculation tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn movetime_uses_fixed_limit_minus_overhead() {
+	let limits = SearchLimits {
+		movetime: Some(ms(1000)),
+		wtime: Some(ms(60_000)),
+		..Default::default()
+	};
+	let (manager, _) = manager_with(limits, Side::White);
+
+	assert_eq!(manager.hard_limit(), ms(950));
+	assert_eq!(manager.soft_limit(), ms(950));
+}
+
+#[test]
+fn no_time_given_searches_forever() {
+	let (manager, time) = manager_with(SearchLimits::default(), Side::White);
+
+	time.advance(Duration::from_secs(3600));
+
+	assert!(!manager.should_abort());
+	assert!(!manager.should_stop_iterating());
+}
+
+#[test]
+fn uses_time_of_side_to_move() {
+	let limits = SearchLimits {
+		wtime: Some(ms(60_050)),
+		btime: Some(ms(30_050)),
+		..Default::default()
+	};
+	let (white, _) = manager_with(limits, Side::White);
+	let (black, _) = manager_with(limits, Side::Black);
+
+	assert_eq!(white.hard_limit(), ms(8000));
+	assert_eq!(black.hard_limit(), ms(4000));
+}
+
+#[test]
+fn increment_adds_to_soft_limit() {
+	let without = SearchLimits {
+		btime: Some(ms(30_050)),
+		..Default::default()
+	};
+	let with = SearchLimits { binc: Some(ms(2000)), ..without };
+	let (without, _) = manager_with(without, Side::Black);
+	let (with, _) = manager_with(with, Side::Black);
+
+	assert_eq!(with.hard_limit() - without.hard_limit(), ms(6000));
+}
+
+#[test]
+fn movestogo_splits_remaining_time() {
+	let limits = SearchLimits {
+		wtime: Some(ms(10_050)),
+		movestogo: Some(10),
+		..Default::default()
+	};
+	let (manager, _) = manager_with(limits, Side::White);
+
+	assert_eq!(manager.hard_limit(), ms(4000));
+}
+
+#[test]
+fn last_move_before_time_control_may_use_all_time() {
+	let limits = SearchLimits {
+		wtime: Some(ms(10_050)),
+		movestogo: Some(1),
+		..Default::default()
+	};
+	let (manager, _) = manager_with(limits, Side::White);
+
+	assert_eq!(manager.hard_limit(), ms(10_000));
+}
+
+#[test]
+fn limits_never_exceed_remaining_time() {
+	let limits = SearchLimits {
+		wtime: Some(ms(100)),
+		winc: Some(ms(5000)),
+		..Default::default()
+	};
+	let (manager, _) = manager_with(limits, Side::White);
+
+	assert!(manager.hard_limit() <= ms(100));
+	assert!(manager.soft_limit() <= manager.hard_limit());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Stopping tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+fn standard_manager() -> (TimeManager, ManualTimeSource) {
+	manager_with(
+		SearchLimits {
+			wtime: Some(ms(60_050)),
+			..Default::default()
+		},
+		Side::White,
+	)
+}
+
+#[test]
+fn stops_iterating_at_soft_limit_and_aborts_at_hard_limit() {
+	let (manager, time) = standard_manager();
+	let soft = manager.soft_limit();
+
+	time.advance(soft - ms(1));
+	assert!(!manager.should_stop_iterating());
+
+	time.advance(ms(1));
+	assert!(manager.should_stop_iterating());
+	assert!(!manager.should_abort());
+
+	time.advance(manager.hard_limit());
+	assert!(manager.should_abort());
+}
+
+#[test]
+fn stable_best_move_shortens_soft_limit() {
+	let (mut manager, _) = standard_manager();
+	let initial = manager.soft_limit();
+
+	for _ in 0..5 {
+		manager.report_iteration(false, 20);
+	}
+
+	assert!(manager.soft_limit() < initial);
+}
+
+#[test]
+fn unstable_best_move_extends_soft_limit() {
+	let (mut manager, _) = standard_manager();
+	for _ in 0..5 {
+		manager.report_iteration(false, 20);
+	}
+	let stable = manager.soft_limit();
+
+	manager.report_iteration(true, 20);
+
+	assert!(manager.soft_limit() > stable);
+}
+
+#[test]
+fn fail_low_extends_soft_limit() {
+	let (mut manager, _) = standard_manager();
+	manager.report_iteration(false, 50);
+	let before = manager.soft_limit();
+
+	manager.report_iteration(false, 0);
+	assert!(manager.soft_limit() > before);
+
+	manager.report_iteration(false, 0);
+	assert!(manager.soft_limit() <= before);
+}
+
+#[test]
+fn extensions_never_exceed_hard_limit() {
+	let limits = SearchLimits {
+		wtime: Some(ms(10_050)),
+		movestogo: Some(2),
+		..Default::default()
+	};
+	let (mut manager, _) = manager_with(limits, Side::White);
+	manager.report_iteration(true, 100);
+	manager.report_iteration(true, -100);
+
+	assert_eq!(manager.soft_limit(), manager.hard_limit());
+}
+
+#[test]
+fn single_reply_stops_after_first_iteration() {
+	let (mut manager, _) = standard_manager();
+	assert!(!manager.should_stop_iterating());
+
+	manager.set_single_reply();
+
+	assert!(manager.should_stop_iterating());
+	assert!(!manager.should_abort());
+}
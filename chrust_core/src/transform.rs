@@ -0,0 +1,117 @@
+//! Symmetries of the board, for testing the evaluation and augmenting training data.
+
+use crate::{
+	ColoredPiece, Side, Square,
+	moves::make_move::{Move, MoveKind},
+	position::Position,
+};
+
+impl Position {
+	/// Swaps the ranks and the colours of all pieces, the castling rights, the en passant square and
+	/// the side to move. The mirrored position is the same game from the other side of the board.
+	pub fn mirror_vertical(&self) -> Position {
+		let mut board = [None; 64];
+		for (square, piece) in self.board.iter().enumerate() {
+			board[square ^ 56] = piece.map(|piece| ColoredPiece {
+				piece: piece.piece,
+				side: piece.side.opponent(),
+			});
+		}
+
+		let [
+			white_king_side,
+			white_queen_side,
+			black_king_side,
+			black_queen_side,
+		] = self.castle;
+		self.transformed(
+			board,
+			[
+				black_king_side,
+				black_queen_side,
+				white_king_side,
+				white_queen_side,
+			],
+			self.en_passant.map(|square| square ^ 56),
+			[self.king_squares[1] ^ 56, self.king_squares[0] ^ 56],
+			self.side_to_move.opponent(),
+		)
+	}
+
+	/// Swaps the files, nothing while there are castling rights since castling isn't symmetric
+	pub fn flip_horizontal(&self) -> Option<Position> {
+		if self.castle.contains(&true) {
+			return None;
+		}
+
+		let mut board = [None; 64];
+		for (square, piece) in self.board.iter().enumerate() {
+			board[square ^ 7] = *piece;
+		}
+
+		Some(self.transformed(board, self.castle, self.en_passant.map(|square| square ^ 7), self.king_squares.map(|square| square ^ 7), self.side_to_move))
+	}
+
+	/// Turns the board by 180 degrees and swaps the colours, nothing while there are castling rights
+	pub fn rotate(&self) -> Option<Position> {
+		self.flip_horizontal().map(|position| position.mirror_vertical())
+	}
+
+	fn transformed(&self, board: [Option<ColoredPiece>; 64], castle: [bool; 4], en_passant: Option<Square>, king_squares: [Square; 2], side_to_move: Side) -> Position {
+		let mut position = Position {
+			board,
+			side_to_move,
+			castle,
+			en_passant,
+			king_squares,
+			halfmove_clock: self.halfmove_clock,
+			fullmove_counter: self.fullmove_counter,
+			zobrist_hash: 0,
+		};
+		position.zobrist_hash = position.compute_hash();
+
+		position
+	}
+}
+
+impl Move {
+	/// The same move in the position returned by `Position::mirror_vertical`
+	pub fn mirror_vertical(&self) -> Move {
+		let mut mv = self.map_squares(|square| square ^ 56);
+		mv.colored_piece.side = self.colored_piece.side.opponent();
+
+		mv
+	}
+
+	/// The same move in the position returned by `Position::flip_horizontal`
+	pub fn flip_horizontal(&self) -> Move {
+		self.map_squares(|square| square ^ 7)
+	}
+
+	/// The same move in the position returned by `Position::rotate`
+	pub fn rotate(&self) -> Move {
+		self.flip_horizontal().mirror_vertical()
+	}
+
+	fn map_squares(&self, map: impl Fn(Square) -> Square) -> Move {
+		let move_kind = match self.move_kind {
+			MoveKind::DoublePawnPush { passed_square } => MoveKind::DoublePawnPush { passed_square: map(passed_square) },
+			MoveKind::EnPassant { capture_square } => MoveKind::EnPassant { capture_square: map(capture_square) },
+			MoveKind::Castling { rook_from, rook_to } => MoveKind::Castling {
+				rook_from: map(rook_from),
+				rook_to: map(rook_to),
+			},
+			move_kind => move_kind,
+		};
+
+		Move {
+			from_square: map(self.from_square),
+			to_square: map(self.to_square),
+			move_kind,
+			colored_piece: self.colored_piece,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,172 @@
+use crate::{
+	ColoredPiece, Piece, Side,
+	moves::make_move::{Move, MoveKind},
+	position::{Position, STARTING_FEN, load_position_from_fen},
+	test_common::TEST_FENS,
+};
+
+fn position(fen: &str) -> Position {
+	load_position_from_fen(fen).unwrap()
+}
+
+fn legal_moves(position: &Position) -> Vec<Move> {
+	let mut position = *position;
+	position.get_all_legal_moves_for_side(position.side_to_move).unwrap()
+}
+
+/// Every legal move of `position` maps to a legal move of `transformed` that leads to the
+/// transformed successor
+fn assert_moves_map(position: &Position, transformed: &Position, transform_position: impl Fn(&Position) -> Position, transform_move: impl Fn(&Move) -> Move) {
+	let moves = legal_moves(position);
+	let transformed_moves = legal_moves(transformed);
+	assert_eq!(moves.len(), transformed_moves.len());
+
+	for mv in moves {
+		let transformed_mv = transform_move(&mv);
+		assert!(transformed_moves.contains(&transformed_mv), "{} has no counterpart {}", mv.to_uci(), transformed_mv.to_uci());
+
+		let mut after = *position;
+		after.make_move_unvalidated(mv).unwrap();
+		let mut transformed_after = *transformed;
+		transformed_after.make_move_unvalidated(transformed_mv).unwrap();
+		// The fullmove number goes up after black moves, so it doesn't follow the swapped colours
+		let expected = transform_position(&after);
+		transformed_after.fullmove_counter = expected.fullmove_counter;
+		assert_eq!(expected, transformed_after, "after {}", mv.to_uci());
+	}
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Position transforms
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn mirror_swaps_colours_ranks_and_rights() {
+	let mirrored = position("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 17").mirror_vertical();
+
+	assert_eq!(mirrored, position("r3k2r/8/8/8/3Pp3/8/8/R3K2R b Qk d3 3 17"));
+	assert_eq!(mirrored.king_squares, [4, 60]);
+	assert_eq!(mirrored.zobrist_hash, mirrored.compute_hash());
+}
+
+#[test]
+fn starting_position_mirrors_to_itself_with_black_to_move() {
+	let mirrored = position(STARTING_FEN).mirror_vertical();
+
+	assert_eq!(mirrored.board, position(STARTING_FEN).board);
+	assert_eq!(mirrored.side_to_move, Side::Black);
+}
+
+#[test]
+fn flip_swaps_files() {
+	let flipped = position("6k1/8/8/3pP3/8/8/1P6/K7 w - d6 0 1").flip_horizontal().unwrap();
+
+	assert_eq!(flipped, position("1k6/8/8/3Pp3/8/8/6P1/7K w - e6 0 1"));
+	assert_eq!(flipped.king_squares, [7, 57]);
+}
+
+#[test]
+fn flip_and_rotate_need_no_castling_rights() {
+	assert_eq!(position(STARTING_FEN).flip_horizontal(), None);
+	assert_eq!(position(STARTING_FEN).rotate(), None);
+}
+
+#[test]
+fn rotate_turns_the_board() {
+	let rotated = position("6k1/8/8/8/8/8/1P6/K7 w - - 0 1").rotate().unwrap();
+
+	assert_eq!(rotated, position("7k/6p1/8/8/8/8/8/1K6 b - - 0 1"));
+}
+
+#[test]
+fn transforms_are_involutions() {
+	for fen in TEST_FENS {
+		let position = position(fen);
+
+		assert_eq!(position.mirror_vertical().mirror_vertical(), position, "{}", fen);
+		if let Some(flipped) = position.flip_horizontal() {
+			assert_eq!(flipped.flip_horizontal(), Some(position), "{}", fen);
+			assert_eq!(position.rotate().unwrap().rotate(), Some(position), "{}", fen);
+		}
+	}
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Move transforms
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn move_transforms_map_every_square() {
+	let castling = Move {
+		from_square: 4,
+		to_square: 6,
+		move_kind: MoveKind::Castling { rook_from: 7, rook_to: 5 },
+		colored_piece: ColoredPiece { piece: Piece::King, side: Side::White },
+	};
+	let en_passant = Move {
+		from_square: 36,
+		to_square: 43,
+		move_kind: MoveKind::EnPassant { capture_square: 35 },
+		colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::White },
+	};
+
+	assert_eq!(
+		castling.mirror_vertical(),
+		Move {
+			from_square: 60,
+			to_square: 62,
+			move_kind: MoveKind::Castling { rook_from: 63, rook_to: 61 },
+			colored_piece: ColoredPiece { piece: Piece::King, side: Side::Black },
+		}
+	);
+	assert_eq!(
+		en_passant.flip_horizontal(),
+		Move {
+			from_square: 35,
+			to_square: 44,
+			move_kind: MoveKind::EnPassant { capture_square: 36 },
+			colored_piece: ColoredPiece { piece: Piece::Pawn, side: Side::White },
+		}
+	);
+	assert_eq!(en_passant.rotate().to_uci(), "d4e3");
+}
+
+#[test]
+fn legal_moves_map_to_the_transformed_positions() {
+	for fen in TEST_FENS {
+		let position = position(fen);
+
+		assert_moves_map(&position, &position.mirror_vertical(), Position::mirror_vertical, Move::mirror_vertical);
+		if let (Some(flipped), Some(rotated)) = (position.flip_horizontal(), position.rotate()) {
+			assert_moves_map(&position, &flipped, |position| position.flip_horizontal().unwrap(), Move::flip_horizontal);
+			assert_moves_map(&position, &rotated, |position| position.rotate().unwrap(), Move::rotate);
+		}
+	}
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Symmetry of evaluation and perft
+// ════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn evaluation_is_symmetric_under_mirroring() {
+	for fen in TEST_FENS {
+		let position = position(fen);
+
+		assert_eq!(position.evaluate(), position.mirror_vertical().evaluate(), "{}", fen);
+	}
+}
+
+#[test]
+fn perft_counts_match_across_transformed_positions() {
+	for fen in TEST_FENS {
+		let position = position(fen);
+		let nodes = position.perft(3).unwrap();
+
+		assert_eq!(position.mirror_vertical().perft(3).unwrap(), nodes, "{}", fen);
+		if let (Some(flipped), Some(rotated)) = (position.flip_horizontal(), position.rotate()) {
+			assert_eq!(flipped.perft(3).unwrap(), nodes, "{}", fen);
+			assert_eq!(rotated.perft(3).unwrap(), nodes, "{}", fen);
+		}
+	}
+}
@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const ENTRY_SIZE: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bound {
+	Exact,
+	/// Fail high, the real score is at least `score`
+	Lower,
+	/// Fail low, the real score is at most `score`
+	Upper,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TtEntry {
+	/// Best move as encoded by `Move::encode`
+	pub best_move: Option<u16>,
+	pub score: i32,
+	pub depth: u8,
+	pub bound: Bound,
+}
+
+/// Hash table shared between search threads without locks. Every slot stores the key xor'ed with
+/// its data, so a slot torn by two threads writing at the same time simply doesn't match on probe.
+pub struct TranspositionTable {
+	slots: Vec<Slot>,
+}
+
+struct Slot {
+	key: AtomicU64,
+	data: AtomicU64,
+}
+
+impl TranspositionTable {
+	pub fn new(size_mb: usize) -> TranspositionTable {
+		let count = (size_mb * 1024 * 1024 / ENTRY_SIZE).max(1);
+
+		TranspositionTable {
+			slots: (0..count)
+				.map(|_| Slot {
+					key: AtomicU64::new(0),
+					data: AtomicU64::new(0),
+				})
+				.collect(),
+		}
+	}
+
+	pub fn clear(&self) {
+		for slot in &self.slots {
+			slot.key.store(0, Ordering::Relaxed);
+			slot.data.store(0, Ordering::Relaxed);
+		}
+	}
+
+	pub fn probe(&self, hash: u64) -> Option<TtEntry> {
+		let slot = self.slot(hash);
+		let data = slot.data.load(Ordering::Relaxed);
+
+		if data == 0 || slot.key.load(Ordering::Relaxed) ^ data != hash {
+			return None;
+		}
+
+		Some(unpack(data))
+	}
+
+	/// Replaces the slot unless it holds a deeper result for the same position
+	pub fn store(&self, hash: u64, entry: TtEntry) {
+		let slot = self.slot(hash);
+
+		if let Some(existing) = self.probe(hash)
+			&& existing.depth > entry.depth
+			&& entry.bound != Bound::Exact
+		{
+			return;
+		}
+
+		let data = pack(entry);
+		slot.key.store(hash ^ data, Ordering::Relaxed);
+		slot.data.store(data, Ordering::Relaxed);
+	}
+
+	/// Used slots per thousand, sampled from the start of the table
+	pub fn hashfull(&self) -> u32 {
+		let sample = self.slots.len().min(1000);
+		let used = self.slots[..sample].iter().filter(|slot| slot.data.load(Ordering::Relaxed) != 0).count();
+
+		(used * 1000 / sample) as u32
+	}
+
+	fn slot(&self, hash: u64) -> &Slot {
+		let index = ((hash as u128 * self.slots.len() as u128) >> 64) as usize;
+
+		&self.slots[index]
+	}
+}
+
+// Layout: move in bits 0-15, score in bits 16-31, depth in bits 32-39 and bound in bits 40-41.
+// Bit 42 is always set so a stored entry is never 0.
+fn pack(entry: TtEntry) -> u64 {
+	let bound = match entry.bound {
+		Bound::Exact => 0,
+		Bound::Lower => 1,
+		Bound::Upper => 2,
+	};
+
+	entry.best_move.unwrap_or(0) as u64 | ((entry.score as i16 as u16 as u64) << 16) | ((entry.depth as u64) << 32) | (bound << 40) | (1 << 42)
+}
+
+fn unpack(data: u64) -> TtEntry {
+	let best_move = (data & 0xFFFF) as u16;
+	let bound = match (data >> 40) & 0b11 {
+		0 => Bound::Exact,
+		1 => Bound::Lower,
+		_ => Bound::Upper,
+	};
+
+	TtEntry {
+		best_move: (best_move != 0).then_some(best_move),
+		score: ((data >> 16) & 0xFFFF) as u16 as i16 as i32,
+		depth: ((data >> 32) & 0xFF) as u8,
+		bound,
+	}
+}
+
+#[cfg(test)]
+mod tests;
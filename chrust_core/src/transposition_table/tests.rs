@@ -0,0 +1,114 @@
+use super::*;
+use std::{sync::Arc, thread};
+
+fn entry(score: i32, depth: u8, bound: Bound) -> TtEntry {
+	TtEntry {
+		best_move: Some(0x0F1C),
+		score,
+		depth,
+		bound,
+	}
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// TranspositionTable tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn probe_empty_table_misses() {
+	let table = TranspositionTable::new(1);
+
+	assert_eq!(table.probe(0x1234_5678_9ABC_DEF0), None);
+	assert_eq!(table.probe(0), None);
+}
+
+#[test]
+fn store_then_probe_returns_entry() {
+	let table = TranspositionTable::new(1);
+	let stored = entry(-123, 7, Bound::Lower);
+
+	table.store(42, stored);
+
+	assert_eq!(table.probe(42), Some(stored));
+}
+
+#[test]
+fn entry_fields_round_trip() {
+	let table = TranspositionTable::new(1);
+
+	for (score, bound) in [
+		(i16::MAX as i32, Bound::Exact),
+		(i16::MIN as i32, Bound::Upper),
+		(0, Bound::Lower),
+	] {
+		let stored = TtEntry {
+			best_move: None,
+			score,
+			depth: u8::MAX,
+			bound,
+		};
+		table.store(7, stored);
+
+		assert_eq!(table.probe(7), Some(stored));
+	}
+}
+
+#[test]
+fn different_key_in_same_slot_misses() {
+	let table = TranspositionTable::new(0);
+	table.store(1, entry(10, 3, Bound::Exact));
+
+	assert_eq!(table.probe(2), None);
+}
+
+#[test]
+fn shallower_bound_does_not_replace_deeper_entry() {
+	let table = TranspositionTable::new(1);
+	let deep = entry(50, 8, Bound::Lower);
+	table.store(9, deep);
+
+	table.store(9, entry(20, 2, Bound::Upper));
+	assert_eq!(table.probe(9), Some(deep));
+
+	let exact = entry(30, 2, Bound::Exact);
+	table.store(9, exact);
+	assert_eq!(table.probe(9), Some(exact));
+}
+
+#[test]
+fn clear_removes_entries() {
+	let table = TranspositionTable::new(1);
+	table.store(5, entry(1, 1, Bound::Exact));
+
+	table.clear();
+
+	assert_eq!(table.probe(5), None);
+	assert_eq!(table.hashfull(), 0);
+}
+
+#[test]
+fn concurrent_writes_never_return_torn_entries() {
+	let table = Arc::new(TranspositionTable::new(0));
+
+	let writers: Vec<_> = (0..4u8)
+		.map(|id| {
+			let table = Arc::clone(&table);
+			thread::spawn(move || {
+				for i in 0..10_000 {
+					table.store(99, entry(id as i32 * 100, (i % 200) as u8, Bound::Exact));
+				}
+			})
+		})
+		.collect();
+
+	for _ in 0..10_000 {
+		if let Some(found) = table.probe(99) {
+			assert_eq!(found.score % 100, 0);
+			assert!(found.score <= 300);
+		}
+	}
+
+	for writer in writers {
+		writer.join().unwrap();
+	}
+}
@@ -0,0 +1,379 @@
+//! Texel tuning of the static evaluation.
+//!
+//! Every labelled position is first resolved with a captures only quiescence search, so the tuner only
+//! sees quiet positions. The evaluation of a quiet position is linear in the weights of `EvalParams`,
+//! which lets the loss `mean((result - sigmoid(k * eval))^2)` be minimized with gradient descent
+//! (Adam) on precomputed feature coefficients instead of evaluating every position again each epoch.
+
+use std::fmt::Write;
+
+use crate::{
+	Side,
+	datagen::TrainingRecord,
+	errors::{ChessError, TunerError},
+	evaluation::{EvalParams, MAX_PHASE, phase_weight, piece_param_index, piece_value},
+	moves::make_move::{Move, MoveKind},
+	position::{Position, load_position_from_fen},
+	search::is_capture,
+};
+
+/// Number of tunable weights: piece values, piece square tables and both king tables
+pub const PARAM_COUNT: usize = KING_MIDDLEGAME_OFFSET + 2 * 64;
+
+const TABLES_OFFSET: usize = 5;
+const KING_MIDDLEGAME_OFFSET: usize = TABLES_OFFSET + 5 * 64;
+const KING_ENDGAME_OFFSET: usize = KING_MIDDLEGAME_OFFSET + 64;
+
+const MAX_QUIESCENCE_PLY: usize = 16;
+const ADAM_BETA1: f64 = 0.9;
+const ADAM_BETA2: f64 = 0.999;
+const ADAM_EPSILON: f64 = 1e-8;
+const K_SEARCH_RANGE: (f64, f64) = (0.05, 5.0);
+const K_SEARCH_ITERATIONS: usize = 60;
+
+const TABLE_NAMES: [&str; 5] = [
+	"PAWN_TABLE",
+	"KNIGHT_TABLE",
+	"BISHOP_TABLE",
+	"ROOK_TABLE",
+	"QUEEN_TABLE",
+];
+
+/// A position and the score white got in the game it was taken from
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LabelledPosition {
+	pub position: Position,
+	/// 1.0 for a white win, 0.5 for a draw and 0.0 for a black win
+	pub result: f64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TunerOptions {
+	pub learning_rate: f64,
+	/// Scaling of the evaluation inside the sigmoid, fitted to the data if `None`
+	pub k: Option<f64>,
+}
+
+pub struct Tuner {
+	entries: Vec<Entry>,
+	weights: Vec<f64>,
+	k: f64,
+	learning_rate: f64,
+	first_moments: Vec<f64>,
+	second_moments: Vec<f64>,
+	steps: i32,
+}
+
+/// Sparse coefficients of the white point of view evaluation of a resolved position
+struct Entry {
+	coefficients: Vec<(usize, f64)>,
+	result: f64,
+}
+
+impl Default for TunerOptions {
+	fn default() -> Self {
+		TunerOptions { learning_rate: 1.0, k: None }
+	}
+}
+
+impl From<TrainingRecord> for LabelledPosition {
+	fn from(record: TrainingRecord) -> Self {
+		LabelledPosition {
+			position: record.position,
+			result: record.result.white_score(),
+		}
+	}
+}
+
+/// Parses a FEN followed by the game result, as `1-0`, `0-1`, `1/2-1/2` or `1.0`, `0.5`, `0.0`.
+/// Brackets, quotes, `|`, `;` and an EPD `c9` opcode around the result are ignored and a FEN without
+/// move counters is accepted.
+pub fn parse_labelled_position(line: &str) -> Result<LabelledPosition, TunerError> {
+	let mut tokens: Vec<&str> = line.split_whitespace().map(|token| token.trim_matches(|c| matches!(c, '[' | ']' | '"' | ';' | '|'))).filter(|token| !token.is_empty()).collect();
+
+	let result = match tokens.pop() {
+		Some("1-0" | "1.0") => 1.0,
+		Some("1/2-1/2" | "0.5") => 0.5,
+		Some("0-1" | "0.0") => 0.0,
+		Some(result) => return Err(TunerError::InvalidResult(result.to_string())),
+		None => return Err(TunerError::MissingResult(line.to_string())),
+	};
+
+	if tokens.last() == Some(&"c9") {
+		tokens.pop();
+	}
+	if tokens.len() == 4 {
+		tokens.extend(["0", "1"]);
+	}
+
+	let position = load_position_from_fen(&tokens.join(" "))?;
+
+	Ok(LabelledPosition { position, result })
+}
+
+impl EvalParams {
+	/// Flattens the weights in the order used by the tuner
+	pub fn to_weights(&self) -> Vec<f64> {
+		self
+			.piece_values
+			.iter()
+			.chain(self.piece_tables.iter().flatten())
+			.chain(&self.king_middlegame_table)
+			.chain(&self.king_endgame_table)
+			.map(|&weight| weight as f64)
+			.collect()
+	}
+
+	/// Inverse of `to_weights`, rounding every weight to the nearest centipawn
+	pub fn from_weights(weights: &[f64]) -> EvalParams {
+		let weight = |index: usize| weights[index].round() as i32;
+
+		EvalParams {
+			piece_values: std::array::from_fn(weight),
+			piece_tables: std::array::from_fn(|table| std::array::from_fn(|square| weight(TABLES_OFFSET + table * 64 + square))),
+			king_middlegame_table: std::array::from_fn(|square| weight(KING_MIDDLEGAME_OFFSET + square)),
+			king_endgame_table: std::array::from_fn(|square| weight(KING_ENDGAME_OFFSET + square)),
+		}
+	}
+
+	/// Formats the weights like the tables in `evaluation.rs` so they can be pasted over them
+	pub fn to_rust_source(&self) -> String {
+		let mut source = String::new();
+		let tables = TABLE_NAMES.into_iter().zip(&self.piece_tables).chain([
+			("KING_MIDDLEGAME_TABLE", &self.king_middlegame_table),
+			("KING_ENDGAME_TABLE", &self.king_endgame_table),
+		]);
+
+		for (name, table) in tables {
+			writeln!(source, "#[rustfmt::skip]\nconst {}: [i32; 64] = [", name).unwrap();
+			for rank in table.chunks(8) {
+				let row: Vec<String> = rank.iter().map(|weight| format!("{:>3}", weight)).collect();
+				writeln!(source, "\t{},", row.join(", ")).unwrap();
+			}
+			writeln!(source, "];\n").unwrap();
+		}
+
+		let piece_values: Vec<String> = self.piece_values.iter().map(|value| value.to_string()).collect();
+		writeln!(source, "pub const DEFAULT_EVAL_PARAMS: EvalParams = EvalParams {{").unwrap();
+		writeln!(source, "\tpiece_values: [{}],", piece_values.join(", ")).unwrap();
+		writeln!(source, "\tpiece_tables: [{}],", TABLE_NAMES.join(", ")).unwrap();
+		writeln!(source, "\tking_middlegame_table: KING_MIDDLEGAME_TABLE,\n\tking_endgame_table: KING_ENDGAME_TABLE,\n}};").unwrap();
+
+		source
+	}
+}
+
+impl Position {
+	/// Follows the best captures and promotions until the position is quiet and returns that position
+	pub fn quiescence_resolve(&self, params: &EvalParams) -> Result<Position, ChessError> {
+		let mut position = *self;
+		let (_, leaf) = quiescence(&mut position, params, -i32::MAX, i32::MAX, 0)?;
+
+		Ok(leaf)
+	}
+}
+
+fn quiescence(position: &mut Position, params: &EvalParams, mut alpha: i32, beta: i32, ply: usize) -> Result<(i32, Position), ChessError> {
+	let mut best = (position.evaluate_with(params), *position);
+	if best.0 >= beta || ply >= MAX_QUIESCENCE_PLY {
+		return Ok(best);
+	}
+	alpha = alpha.max(best.0);
+
+	let mut moves: Vec<Move> = position
+		.get_all_legal_moves_for_side(position.side_to_move)?
+		.into_iter()
+		.filter(|mv| is_capture(position, mv) || matches!(mv.move_kind, MoveKind::Promotion { .. }))
+		.collect();
+	moves.sort_by_key(|mv| -position.board[mv.to_square as usize].map_or(0, |victim| piece_value(victim.piece)));
+
+	for mv in moves {
+		let undo = position.make_move_unvalidated(mv)?;
+		let (score, leaf) = quiescence(position, params, -beta, -alpha, ply + 1)?;
+		position.undo_move(undo, mv)?;
+
+		if -score > best.0 {
+			best = (-score, leaf);
+		}
+		if best.0 >= beta {
+			break;
+		}
+		alpha = alpha.max(best.0);
+	}
+
+	Ok(best)
+}
+
+impl Tuner {
+	/// Resolves every position with `params` and precomputes its coefficients. Positions where the side
+	/// to move is in check are skipped since their static evaluation says little.
+	pub fn new(positions: &[LabelledPosition], params: &EvalParams, options: &TunerOptions) -> Result<Tuner, ChessError> {
+		let mut entries = Vec::with_capacity(positions.len());
+
+		for labelled in positions {
+			if labelled.position.is_king_in_check(labelled.position.side_to_move)?.is_some() {
+				continue;
+			}
+
+			let resolved = labelled.position.quiescence_resolve(params)?;
+			entries.push(Entry {
+				coefficients: coefficients(&resolved),
+				result: labelled.result,
+			});
+		}
+
+		let mut tuner = Tuner {
+			entries,
+			weights: params.to_weights(),
+			k: 1.0,
+			learning_rate: options.learning_rate,
+			first_moments: vec![0.0; PARAM_COUNT],
+			second_moments: vec![0.0; PARAM_COUNT],
+			steps: 0,
+		};
+		tuner.k = match options.k {
+			Some(k) => k,
+			None => tuner.fit_k(),
+		};
+
+		Ok(tuner)
+	}
+
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub fn k(&self) -> f64 {
+		self.k
+	}
+
+	pub fn params(&self) -> EvalParams {
+		EvalParams::from_weights(&self.weights)
+	}
+
+	/// Mean squared error between the results and the predicted scores with the current weights
+	pub fn loss(&self) -> f64 {
+		self.loss_with_k(self.k)
+	}
+
+	/// One epoch of Adam over all positions
+	pub fn step(&mut self) {
+		if self.entries.is_empty() {
+			return;
+		}
+
+		let mut gradient = vec![0.0; PARAM_COUNT];
+		let scale = self.k * std::f64::consts::LN_10 / 400.0;
+
+		for entry in &self.entries {
+			let predicted = sigmoid(self.k, self.evaluate(entry));
+			let error = (predicted - entry.result) * predicted * (1.0 - predicted) * scale;
+			for &(index, coefficient) in &entry.coefficients {
+				gradient[index] += error * coefficient;
+			}
+		}
+
+		self.steps += 1;
+		let count = self.entries.len() as f64;
+		for (index, gradient) in gradient.iter().enumerate() {
+			let gradient = 2.0 * gradient / count;
+
+			self.first_moments[index] = ADAM_BETA1 * self.first_moments[index] + (1.0 - ADAM_BETA1) * gradient;
+			self.second_moments[index] = ADAM_BETA2 * self.second_moments[index] + (1.0 - ADAM_BETA2) * gradient * gradient;
+
+			let first_moment = self.first_moments[index] / (1.0 - ADAM_BETA1.powi(self.steps));
+			let second_moment = self.second_moments[index] / (1.0 - ADAM_BETA2.powi(self.steps));
+			self.weights[index] -= self.learning_rate * first_moment / (second_moment.sqrt() + ADAM_EPSILON);
+		}
+	}
+
+	/// Golden section search for the `k` that fits the current weights best
+	fn fit_k(&self) -> f64 {
+		let ratio = (5f64.sqrt() - 1.0) / 2.0;
+		let (mut low, mut high) = K_SEARCH_RANGE;
+
+		for _ in 0..K_SEARCH_ITERATIONS {
+			let left = high - ratio * (high - low);
+			let right = low + ratio * (high - low);
+			if self.loss_with_k(left) < self.loss_with_k(right) {
+				high = right;
+			} else {
+				low = left;
+			}
+		}
+
+		(low + high) / 2.0
+	}
+
+	fn loss_with_k(&self, k: f64) -> f64 {
+		if self.entries.is_empty() {
+			return 0.0;
+		}
+
+		let total: f64 = self.entries.iter().map(|entry| (entry.result - sigmoid(k, self.evaluate(entry))).powi(2)).sum();
+
+		total / self.entries.len() as f64
+	}
+
+	fn evaluate(&self, entry: &Entry) -> f64 {
+		entry.coefficients.iter().map(|&(index, coefficient)| self.weights[index] * coefficient).sum()
+	}
+}
+
+fn sigmoid(k: f64, eval: f64) -> f64 {
+	1.0 / (1.0 + 10f64.powf(-k * eval / 400.0))
+}
+
+/// The evaluation from whites point of view as a sum of weight times coefficient
+fn coefficients(position: &Position) -> Vec<(usize, f64)> {
+	let mut coefficients = Vec::new();
+	let mut kings = Vec::new();
+	let mut phase = 0;
+
+	for (square, colored_piece) in position.board.iter().enumerate() {
+		let Some(colored_piece) = colored_piece else {
+			continue;
+		};
+
+		let (table_index, sign) = match colored_piece.side {
+			Side::White => (square ^ 56, 1.0),
+			Side::Black => (square, -1.0),
+		};
+
+		phase += phase_weight(colored_piece.piece);
+
+		match piece_param_index(colored_piece.piece) {
+			Some(index) => {
+				coefficients.push((index, sign));
+				coefficients.push((TABLES_OFFSET + index * 64 + table_index, sign));
+			}
+			None => kings.push((table_index, sign)),
+		}
+	}
+
+	let middlegame = phase.min(MAX_PHASE) as f64 / MAX_PHASE as f64;
+	for (table_index, sign) in kings {
+		coefficients.push((KING_MIDDLEGAME_OFFSET + table_index, sign * middlegame));
+		coefficients.push((KING_ENDGAME_OFFSET + table_index, sign * (1.0 - middlegame)));
+	}
+
+	// Pieces of the same kind on either side share the value weight
+	coefficients.sort_by_key(|&(index, _)| index);
+	coefficients.dedup_by(|next, previous| {
+		let same = next.0 == previous.0;
+		if same {
+			previous.1 += next.1;
+		}
+		same
+	});
+	coefficients.retain(|&(_, coefficient)| coefficient != 0.0);
+
+	coefficients
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,152 @@
+use super::*;
+use crate::{evaluation::DEFAULT_EVAL_PARAMS, test_common::TEST_FENS};
+
+fn labelled(fen: &str, result: f64) -> LabelledPosition {
+	LabelledPosition {
+		position: load_position_from_fen(fen).unwrap(),
+		result,
+	}
+}
+
+fn linear_eval(position: &Position, params: &EvalParams) -> f64 {
+	let weights = params.to_weights();
+
+	coefficients(position).iter().map(|&(index, coefficient)| weights[index] * coefficient).sum()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Parsing tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn parses_common_result_formats() {
+	let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+	let expected = load_position_from_fen(fen).unwrap();
+
+	for (line, result) in [
+		(format!("{} [1.0]", fen), 1.0),
+		(format!("{} [0.5]", fen), 0.5),
+		(format!("{} | 0-1", fen), 0.0),
+		(format!("{} \"1/2-1/2\"", fen), 0.5),
+		("4k3/8/8/8/8/8/4P3/4K3 w - - c9 \"1-0\";".to_string(), 1.0),
+	] {
+		assert_eq!(parse_labelled_position(&line), Ok(LabelledPosition { position: expected, result }), "{}", line);
+	}
+}
+
+#[test]
+fn invalid_lines_are_rejected() {
+	assert_eq!(parse_labelled_position(""), Err(TunerError::MissingResult(String::new())));
+	assert_eq!(parse_labelled_position("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"), Err(TunerError::InvalidResult("1".to_string())));
+	assert!(matches!(parse_labelled_position("4k3/8/8/8/8/8/4P3/4X3 w - - 0 1 1-0"), Err(TunerError::FenError { .. })));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Parameter tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn weights_round_trip() {
+	let weights = DEFAULT_EVAL_PARAMS.to_weights();
+
+	assert_eq!(weights.len(), PARAM_COUNT);
+	assert_eq!(EvalParams::from_weights(&weights), DEFAULT_EVAL_PARAMS);
+}
+
+#[test]
+fn coefficients_match_the_evaluation() {
+	for fen in TEST_FENS {
+		let position = load_position_from_fen(fen).unwrap();
+		let white_eval = match position.side_to_move {
+			Side::White => position.evaluate(),
+			Side::Black => -position.evaluate(),
+		};
+
+		// The evaluation rounds the tapered king score down to whole centipawns
+		assert!((linear_eval(&position, &DEFAULT_EVAL_PARAMS) - white_eval as f64).abs() < 1.0, "{}", fen);
+	}
+}
+
+#[test]
+fn rust_source_contains_every_table() {
+	let source = DEFAULT_EVAL_PARAMS.to_rust_source();
+
+	for name in TABLE_NAMES.iter().chain(&["KING_MIDDLEGAME_TABLE", "KING_ENDGAME_TABLE"]) {
+		assert!(source.contains(&format!("const {}: [i32; 64] = [", name)), "{}", name);
+	}
+	assert!(source.contains("piece_values: [100, 320, 330, 500, 900],"));
+	assert!(source.contains("\t-50, -40, -30, -30, -30, -30, -40, -50,\n"));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Quiescence tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn quiet_position_resolves_to_itself() {
+	let position = load_position_from_fen(TEST_FENS[0]).unwrap();
+
+	assert_eq!(position.quiescence_resolve(&DEFAULT_EVAL_PARAMS), Ok(position));
+}
+
+#[test]
+fn hanging_piece_is_captured_before_evaluating() {
+	let position = load_position_from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+	let resolved = position.quiescence_resolve(&DEFAULT_EVAL_PARAMS).unwrap();
+
+	assert_eq!(resolved, load_position_from_fen("4k3/8/8/3R4/8/8/8/4K3 b - - 0 1").unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Tuning tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn positions_in_check_are_skipped() {
+	let positions = [
+		labelled("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", 1.0),
+		labelled("4k3/8/8/8/8/8/8/4K2r w - - 0 1", 0.0),
+	];
+
+	let tuner = Tuner::new(&positions, &DEFAULT_EVAL_PARAMS, &TunerOptions::default()).unwrap();
+
+	assert_eq!(tuner.len(), 1);
+}
+
+#[test]
+fn tuning_reduces_the_loss() {
+	// Extra knights win and extra pawns draw, so the knight should gain value relative to the pawn
+	let positions: Vec<LabelledPosition> = [
+		("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1", 1.0),
+		("1n2k3/8/8/8/8/8/8/4K3 w - - 0 1", 0.0),
+		("4k3/8/8/8/8/8/3P4/4K3 w - - 0 1", 0.5),
+		("4k3/3p4/8/8/8/8/8/4K3 w - - 0 1", 0.5),
+		("4k3/8/8/8/8/8/8/4KN2 b - - 0 1", 1.0),
+		("4kn2/8/8/8/8/8/8/4K3 b - - 0 1", 0.0),
+	]
+	.iter()
+	.map(|(fen, result)| labelled(fen, *result))
+	.collect();
+	let options = TunerOptions { learning_rate: 2.0, k: Some(1.0) };
+
+	let mut tuner = Tuner::new(&positions, &DEFAULT_EVAL_PARAMS, &options).unwrap();
+	let initial_loss = tuner.loss();
+	for _ in 0..200 {
+		tuner.step();
+	}
+	let params = tuner.params();
+
+	assert!(tuner.loss() < initial_loss);
+	assert!(params.piece_values[1] > DEFAULT_EVAL_PARAMS.piece_values[1]);
+	assert!(params.piece_values[0] < DEFAULT_EVAL_PARAMS.piece_values[0]);
+}
+
+#[test]
+fn fitted_k_is_in_range() {
+	let positions: Vec<LabelledPosition> = TEST_FENS.iter().enumerate().map(|(index, fen)| labelled(fen, [1.0, 0.5, 0.0][index % 3])).collect();
+
+	let tuner = Tuner::new(&positions, &DEFAULT_EVAL_PARAMS, &TunerOptions::default()).unwrap();
+
+	assert!(tuner.k() > K_SEARCH_RANGE.0 && tuner.k() < K_SEARCH_RANGE.1);
+	assert!(tuner.loss() <= tuner.loss_with_k(1.0) + 1e-9);
+}
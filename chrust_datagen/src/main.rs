@@ -0,0 +1,147 @@
+use std::{
+	fs::OpenOptions,
+	io::{BufWriter, Write},
+	process::ExitCode,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		mpsc,
+	},
+	thread,
+};
+
+use chrust_core::{
+	datagen::{DatagenOptions, Rng, TrainingRecord, play_game},
+	search::{Search, SearchOptions},
+	time_manager::SearchLimits,
+};
+
+const USAGE: &str = "Usage: chrust_datagen --output <file> [--games <n>] [--depth <n> | --nodes <n>] [--random-plies <n>] [--book <fen file>] [--max-plies <n>] [--threads <n>] [--seed <n>]
+
+Plays self-play games and appends the recorded positions to the output file, 35 bytes per position.";
+
+struct Args {
+	output: String,
+	games: usize,
+	threads: usize,
+	seed: u64,
+	options: DatagenOptions,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run(&args) {
+		Ok(positions) => {
+			println!("Wrote {} positions from {} games to {}", positions, args.games, args.output);
+			ExitCode::SUCCESS
+		}
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let mut output = None;
+	let mut games = 100;
+	let mut threads = 1;
+	let mut seed = 1;
+	let mut options = DatagenOptions::default();
+
+	while let Some(flag) = args.next() {
+		let mut value = || args.next().ok_or(format!("Missing value for {}", flag));
+
+		match flag.as_str() {
+			"--output" => output = Some(value()?),
+			"--games" => games = parse_number(&value()?)?,
+			"--threads" => threads = parse_number::<usize>(&value()?)?.max(1),
+			"--seed" => seed = parse_number(&value()?)?,
+			"--random-plies" => options.random_plies = parse_number(&value()?)?,
+			"--max-plies" => options.max_plies = parse_number(&value()?)?,
+			"--depth" => {
+				options.limits = SearchLimits {
+					depth: Some(parse_number(&value()?)?),
+					..Default::default()
+				}
+			}
+			"--nodes" => {
+				options.limits = SearchLimits {
+					nodes: Some(parse_number(&value()?)?),
+					..Default::default()
+				}
+			}
+			"--book" => {
+				let path = value()?;
+				let book = std::fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+				options.book = book.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).map(String::from).collect();
+			}
+			"--help" | "-h" => return Err(String::new()),
+			_ => return Err(format!("Unknown argument {}", flag)),
+		}
+	}
+
+	Ok(Args {
+		output: output.ok_or("Missing --output")?,
+		games,
+		threads,
+		seed,
+		options,
+	})
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid number {}", value))
+}
+
+/// Plays the games on `threads` workers, each with its own single threaded search, and writes the
+/// records as they come in. Returns the number of positions written.
+fn run(args: &Args) -> Result<usize, String> {
+	let file = OpenOptions::new().create(true).append(true).open(&args.output).map_err(|e| format!("Can't open {}: {}", args.output, e))?;
+	let mut writer = BufWriter::new(file);
+	let next_game = AtomicUsize::new(0);
+	let (sender, receiver) = mpsc::channel::<Result<Vec<TrainingRecord>, String>>();
+
+	thread::scope(|scope| {
+		for worker in 0..args.threads {
+			let sender = sender.clone();
+			let next_game = &next_game;
+
+			scope.spawn(move || {
+				let mut search = Search::new(SearchOptions { threads: 1, ..Default::default() });
+				let mut rng = Rng::new(args.seed.wrapping_add(worker as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+				while next_game.fetch_add(1, Ordering::Relaxed) < args.games {
+					let records = play_game(&mut search, &args.options, &mut rng).map_err(|e| format!("Game failed: {}", e));
+					if sender.send(records).is_err() {
+						break;
+					}
+				}
+			});
+		}
+		drop(sender);
+
+		let mut positions = 0;
+		for (game, records) in receiver.into_iter().enumerate() {
+			for record in records? {
+				let bytes = record.encode().map_err(|e| format!("Can't encode position: {}", e))?;
+				writer.write_all(&bytes).map_err(|e| format!("Can't write {}: {}", args.output, e))?;
+				positions += 1;
+			}
+
+			if (game + 1) % 10 == 0 {
+				println!("{} / {} games, {} positions", game + 1, args.games, positions);
+			}
+		}
+
+		writer.flush().map_err(|e| format!("Can't write {}: {}", args.output, e))?;
+
+		Ok(positions)
+	})
+}
@@ -0,0 +1,324 @@
+use std::{
+	io::{BufRead, BufReader, Write},
+	process::{Child, ChildStdin, Command, Stdio},
+	sync::mpsc::{self, Receiver, RecvTimeoutError},
+	thread,
+	time::Duration,
+};
+
+use chrust_core::{
+	Side,
+	position::Game,
+	search::{MATE_SCORE, Search, SearchOptions},
+	time_manager::SearchLimits,
+};
+
+/// How long an engine may take to answer anything but `go`
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Extra time on top of the clock before an engine that doesn't answer `go` is given up on
+const GO_TIMEOUT_MARGIN: Duration = Duration::from_secs(5);
+/// Searches with only a depth or node limit have no clock to derive a timeout from
+const UNLIMITED_GO_TIMEOUT: Duration = Duration::from_secs(600);
+const QUIT_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+/// Best move of an engine and the score it reported for it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EngineMove {
+	pub uci: String,
+	/// Centipawns from the point of view of the engine, mates are reported near `MATE_SCORE`
+	pub score: Option<i32>,
+}
+
+pub trait Engine: Send {
+	fn name(&self) -> &str;
+
+	fn new_game(&mut self) -> Result<(), String>;
+
+	/// Searches the current position of `game`, which started at `start_fen` followed by `moves`
+	fn best_move(&mut self, game: &Game, start_fen: &str, moves: &[String], limits: &SearchLimits) -> Result<EngineMove, String>;
+}
+
+/// A `--engine` argument. UCI engines are given as `cmd=<path>[,arg=<arg>][,option.<name>=<value>]`,
+/// the built-in search as `builtin[,threads=<n>][,hash=<mb>][,<technique>=<true|false>]`. Both take `name=<name>`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EngineSpec {
+	Uci {
+		name: String,
+		command: String,
+		args: Vec<String>,
+		options: Vec<(String, String)>,
+	},
+	Builtin {
+		name: String,
+		options: SearchOptions,
+	},
+}
+
+pub struct UciEngine {
+	name: String,
+	child: Child,
+	stdin: ChildStdin,
+	lines: Receiver<String>,
+}
+
+pub struct BuiltinEngine {
+	name: String,
+	search: Search,
+}
+
+impl EngineSpec {
+	pub fn parse(spec: &str) -> Result<EngineSpec, String> {
+		let mut name = None;
+		let mut command = None;
+		let mut args = Vec::new();
+		let mut uci_options = Vec::new();
+		let mut builtin = false;
+		let mut search_options = SearchOptions::default();
+
+		for part in spec.split(',').filter(|part| !part.is_empty()) {
+			if part == "builtin" {
+				builtin = true;
+				continue;
+			}
+
+			let (key, value) = part.split_once('=').ok_or(format!("Expected key=value in engine spec, found {}", part))?;
+			let flag = || value.parse::<bool>().map_err(|_| format!("Invalid value for {}: {}", key, value));
+			let number = || value.parse::<usize>().map_err(|_| format!("Invalid value for {}: {}", key, value));
+
+			match key {
+				"name" => name = Some(value.to_string()),
+				"cmd" => command = Some(value.to_string()),
+				"arg" => args.push(value.to_string()),
+				"threads" => search_options.threads = number()?,
+				"hash" => search_options.hash_size_mb = number()?,
+				"null_move" => search_options.null_move_pruning = flag()?,
+				"lmr" => search_options.late_move_reductions = flag()?,
+				"rfp" => search_options.reverse_futility_pruning = flag()?,
+				"razoring" => search_options.razoring = flag()?,
+				"check_extensions" => search_options.check_extensions = flag()?,
+				_ => match key.strip_prefix("option.") {
+					Some(option) => uci_options.push((option.to_string(), value.to_string())),
+					None => return Err(format!("Unknown engine spec key {}", key)),
+				},
+			}
+		}
+
+		match (builtin, command) {
+			(true, None) => Ok(EngineSpec::Builtin {
+				name: name.unwrap_or("chrust".to_string()),
+				options: search_options,
+			}),
+			(false, Some(command)) => Ok(EngineSpec::Uci {
+				name: name.unwrap_or(command.clone()),
+				command,
+				args,
+				options: uci_options,
+			}),
+			(true, Some(_)) => Err("An engine can't be both builtin and a command".to_string()),
+			(false, None) => Err(format!("Engine spec needs cmd=<path> or builtin: {}", spec)),
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		match self {
+			EngineSpec::Uci { name, .. } | EngineSpec::Builtin { name, .. } => name,
+		}
+	}
+
+	pub fn start(&self) -> Result<Box<dyn Engine>, String> {
+		match self {
+			EngineSpec::Uci { name, command, args, options } => Ok(Box::new(UciEngine::start(name, command, args, options)?)),
+			EngineSpec::Builtin { name, options } => Ok(Box::new(BuiltinEngine {
+				name: name.clone(),
+				search: Search::new(*options),
+			})),
+		}
+	}
+}
+
+impl UciEngine {
+	pub fn start(name: &str, command: &str, args: &[String], options: &[(String, String)]) -> Result<UciEngine, String> {
+		let mut child = Command::new(command)
+			.args(args)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::null())
+			.spawn()
+			.map_err(|e| format!("Can't start {}: {}", command, e))?;
+		let stdin = child.stdin.take().expect("engine.rs: stdin is piped");
+		let stdout = child.stdout.take().expect("engine.rs: stdout is piped");
+
+		// Reading on a separate thread lets every wait have a timeout
+		let (sender, lines) = mpsc::channel();
+		thread::spawn(move || {
+			for line in BufReader::new(stdout).lines() {
+				let Ok(line) = line else {
+					break;
+				};
+				if sender.send(line).is_err() {
+					break;
+				}
+			}
+		});
+
+		let mut engine = UciEngine {
+			name: name.to_string(),
+			child,
+			stdin,
+			lines,
+		};
+
+		engine.send("uci")?;
+		engine.wait_for("uciok", HANDSHAKE_TIMEOUT)?;
+		for (option, value) in options {
+			engine.send(&format!("setoption name {} value {}", option, value))?;
+		}
+		engine.send("isready")?;
+		engine.wait_for("readyok", HANDSHAKE_TIMEOUT)?;
+
+		Ok(engine)
+	}
+
+	fn send(&mut self, command: &str) -> Result<(), String> {
+		writeln!(self.stdin, "{}", command).and_then(|_| self.stdin.flush()).map_err(|e| format!("{} stopped accepting commands: {}", self.name, e))
+	}
+
+	/// Waits for a line starting with `token` and returns it together with every line before it
+	fn wait_for(&mut self, token: &str, timeout: Duration) -> Result<Vec<String>, String> {
+		let mut lines = Vec::new();
+
+		loop {
+			match self.lines.recv_timeout(timeout) {
+				Ok(line) => {
+					let done = line.split_whitespace().next() == Some(token);
+					lines.push(line);
+					if done {
+						return Ok(lines);
+					}
+				}
+				Err(RecvTimeoutError::Timeout) => return Err(format!("{} didn't send {} in time", self.name, token)),
+				Err(RecvTimeoutError::Disconnected) => return Err(format!("{} exited", self.name)),
+			}
+		}
+	}
+}
+
+impl Engine for UciEngine {
+	fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn new_game(&mut self) -> Result<(), String> {
+		self.send("ucinewgame")?;
+		self.send("isready")?;
+		self.wait_for("readyok", HANDSHAKE_TIMEOUT)?;
+
+		Ok(())
+	}
+
+	fn best_move(&mut self, game: &Game, start_fen: &str, moves: &[String], limits: &SearchLimits) -> Result<EngineMove, String> {
+		let mut position = format!("position fen {}", start_fen);
+		if !moves.is_empty() {
+			position.push_str(" moves ");
+			position.push_str(&moves.join(" "));
+		}
+		self.send(&position)?;
+		self.send(&go_command(limits))?;
+
+		let remaining = match game.position.side_to_move {
+			Side::White => limits.wtime,
+			Side::Black => limits.btime,
+		};
+		let timeout = limits.movetime.or(remaining).map_or(UNLIMITED_GO_TIMEOUT, |time| time + GO_TIMEOUT_MARGIN);
+		let lines = self.wait_for("bestmove", timeout)?;
+
+		let uci = lines.last().and_then(|line| line.split_whitespace().nth(1)).ok_or(format!("{} sent bestmove without a move", self.name))?.to_string();
+		let score = lines.iter().rev().find_map(|line| parse_info_score(line));
+
+		Ok(EngineMove { uci, score })
+	}
+}
+
+impl Drop for UciEngine {
+	fn drop(&mut self) {
+		let _ = self.send("quit");
+		thread::sleep(QUIT_GRACE_PERIOD);
+		if let Ok(None) = self.child.try_wait() {
+			let _ = self.child.kill();
+		}
+		let _ = self.child.wait();
+	}
+}
+
+impl Engine for BuiltinEngine {
+	fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn new_game(&mut self) -> Result<(), String> {
+		self.search.new_game();
+
+		Ok(())
+	}
+
+	fn best_move(&mut self, game: &Game, _start_fen: &str, _moves: &[String], limits: &SearchLimits) -> Result<EngineMove, String> {
+		let result = game.search_best_move(&mut self.search, limits).map_err(|e| format!("{} failed to search: {}", self.name, e))?;
+		let best_move = result.best_move.ok_or(format!("{} found no move", self.name))?;
+
+		Ok(EngineMove {
+			uci: best_move.to_uci(),
+			score: Some(result.score),
+		})
+	}
+}
+
+pub fn go_command(limits: &SearchLimits) -> String {
+	let mut command = "go".to_string();
+
+	let durations = [
+		("wtime", limits.wtime),
+		("btime", limits.btime),
+		("winc", limits.winc),
+		("binc", limits.binc),
+		("movetime", limits.movetime),
+	];
+	for (name, duration) in durations {
+		if let Some(duration) = duration {
+			command.push_str(&format!(" {} {}", name, duration.as_millis()));
+		}
+	}
+
+	let counts = [
+		("movestogo", limits.movestogo.map(u64::from)),
+		("depth", limits.depth.map(u64::from)),
+		("nodes", limits.nodes),
+	];
+	for (name, count) in counts {
+		if let Some(count) = count {
+			command.push_str(&format!(" {} {}", name, count));
+		}
+	}
+
+	command
+}
+
+/// Score of an `info` line, with `mate <n>` mapped to the same scale as the built-in search
+pub fn parse_info_score(line: &str) -> Option<i32> {
+	let tokens: Vec<&str> = line.split_whitespace().collect();
+	if tokens.first() != Some(&"info") {
+		return None;
+	}
+
+	let index = tokens.iter().position(|&token| token == "score")?;
+	let value: i32 = tokens.get(index + 2)?.parse().ok()?;
+
+	match *tokens.get(index + 1)? {
+		"cp" => Some(value),
+		"mate" if value > 0 => Some(MATE_SCORE - (2 * value - 1)),
+		"mate" => Some(-MATE_SCORE - 2 * value),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
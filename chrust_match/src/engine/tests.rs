@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use super::*;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// UCI protocol tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn info_scores_are_parsed() {
+	assert_eq!(parse_info_score("info depth 12 seldepth 18 score cp -35 nodes 12345 pv e2e4"), Some(-35));
+	assert_eq!(parse_info_score("info depth 12 score cp 20 lowerbound nodes 1"), Some(20));
+	assert_eq!(parse_info_score("info depth 5 score mate 1 pv a1a8"), Some(MATE_SCORE - 1));
+	assert_eq!(parse_info_score("info depth 5 score mate -2"), Some(-MATE_SCORE + 4));
+	assert_eq!(parse_info_score("info string score cp is nice"), None);
+	assert_eq!(parse_info_score("bestmove e2e4"), None);
+}
+
+#[test]
+fn go_command_contains_every_limit() {
+	let limits = SearchLimits {
+		wtime: Some(Duration::from_millis(60_000)),
+		btime: Some(Duration::from_millis(59_500)),
+		winc: Some(Duration::from_millis(1_000)),
+		binc: Some(Duration::from_millis(1_000)),
+		movestogo: Some(20),
+		..Default::default()
+	};
+
+	assert_eq!(go_command(&limits), "go wtime 60000 btime 59500 winc 1000 binc 1000 movestogo 20");
+	assert_eq!(go_command(&SearchLimits { depth: Some(8), ..Default::default() }), "go depth 8");
+	assert_eq!(go_command(&SearchLimits::default()), "go");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Engine spec tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn uci_spec_is_parsed() {
+	assert_eq!(
+		EngineSpec::parse("cmd=./engine,name=new,arg=--uci,option.Hash=64,option.Threads=2"),
+		Ok(EngineSpec::Uci {
+			name: "new".to_string(),
+			command: "./engine".to_string(),
+			args: vec!["--uci".to_string()],
+			options: vec![
+				("Hash".to_string(), "64".to_string()),
+				("Threads".to_string(), "2".to_string())
+			],
+		})
+	);
+}
+
+#[test]
+fn builtin_spec_is_parsed() {
+	let spec = EngineSpec::parse("builtin,name=no_lmr,lmr=false,hash=4").unwrap();
+
+	assert_eq!(
+		spec,
+		EngineSpec::Builtin {
+			name: "no_lmr".to_string(),
+			options: SearchOptions {
+				late_move_reductions: false,
+				hash_size_mb: 4,
+				..Default::default()
+			},
+		}
+	);
+	assert_eq!(spec.name(), "no_lmr");
+}
+
+#[test]
+fn invalid_specs_are_rejected() {
+	assert!(EngineSpec::parse("name=nothing").is_err());
+	assert!(EngineSpec::parse("builtin,cmd=./engine").is_err());
+	assert!(EngineSpec::parse("builtin,lmr=maybe").is_err());
+	assert!(EngineSpec::parse("builtin,colour=blue").is_err());
+}
+
+#[test]
+fn builtin_engine_plays_a_move() {
+	let mut engine = EngineSpec::parse("builtin,hash=1").unwrap().start().unwrap();
+	let game = Game::try_from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+
+	engine.new_game().unwrap();
+	let engine_move = engine.best_move(&game, "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", &[], &SearchLimits { depth: Some(3), ..Default::default() }).unwrap();
+
+	assert_eq!(engine_move.uci, "a1a8");
+	assert_eq!(engine_move.score, Some(MATE_SCORE - 1));
+}
+
+#[test]
+fn missing_uci_engine_fails_to_start() {
+	assert!(EngineSpec::parse("cmd=/nonexistent/engine").unwrap().start().is_err());
+}
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use chrust_core::{
+	Side,
+	clock::{ChessClock, SystemTimeSource, TimeControl},
+	datagen::GameResult,
+	errors::ChessError,
+	game_status::GameStatus,
+	game_tree::GameTree,
+	moves::make_move::Move,
+	position::{Game, Position, load_position_from_fen},
+	time_manager::SearchLimits,
+};
+
+use crate::engine::Engine;
+
+/// Either fixed limits for every move or a chess clock with base time and increment
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimeLimit {
+	PerMove(SearchLimits),
+	Clock {
+		time: Duration,
+		increment: Duration,
+	},
+}
+
+/// Ends games early once both engines agree on the outcome. Scores count from both sides, so
+/// `resign_moves` and `draw_moves` are full moves, i.e. plies of both engines. Tablebases are not
+/// probed, endgames are adjudicated by score like any other position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Adjudication {
+	/// Score both engines have to report in favour of the same side
+	pub resign_score: i32,
+	pub resign_moves: usize,
+	/// Both engines report a score within this many centipawns of zero
+	pub draw_score: i32,
+	pub draw_moves: usize,
+	/// No draw adjudication before this many full moves have been played
+	pub draw_after: usize,
+	/// Games reaching this many plies are drawn
+	pub max_plies: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameOutcome {
+	pub result: GameResult,
+	pub termination: String,
+	pub pgn: String,
+}
+
+impl Default for Adjudication {
+	fn default() -> Self {
+		Adjudication {
+			resign_score: 1_000,
+			resign_moves: 3,
+			draw_score: 10,
+			draw_moves: 8,
+			draw_after: 40,
+			max_plies: 600,
+		}
+	}
+}
+
+/// Plays one game from `opening_fen`. Engine failures and illegal moves lose the game for the engine
+/// at fault instead of aborting the match; `tags` are written to the PGN before the result.
+pub fn play_game(white: &mut dyn Engine, black: &mut dyn Engine, opening_fen: &str, time_limit: &TimeLimit, adjudication: &Adjudication, tags: &[(String, String)]) -> Result<GameOutcome, ChessError> {
+	let start_position = load_position_from_fen(opening_fen)?;
+	let mut game = Game::try_from_fen(opening_fen)?;
+	let mut moves: Vec<Move> = Vec::new();
+	let mut uci_moves: Vec<String> = Vec::new();
+	// Scores from whites point of view, one per ply
+	let mut white_scores: Vec<Option<i32>> = Vec::new();
+
+	if let Err(error) = white.new_game() {
+		return finish(start_position, &moves, forfeit(Side::White), error, tags);
+	}
+	if let Err(error) = black.new_game() {
+		return finish(start_position, &moves, forfeit(Side::Black), error, tags);
+	}
+
+	if let TimeLimit::Clock { time, increment } = time_limit {
+		game.start_clock(ChessClock::new(TimeControl::fischer(*time, *increment), Box::new(SystemTimeSource::new())));
+	}
+
+	let (result, termination) = loop {
+		if !game.is_legal_game_state() {
+			break (GameResult::from_status(&game.game_status), describe_status(&game.game_status));
+		}
+		if game.position.is_dead_position() {
+			break (GameResult::Draw, "dead position".to_string());
+		}
+		if moves.len() >= adjudication.max_plies {
+			break (GameResult::Draw, "adjudication: maximum game length".to_string());
+		}
+		if let Some(adjudicated) = adjudicate(&white_scores, adjudication) {
+			break adjudicated;
+		}
+
+		let side = game.position.side_to_move;
+		let engine: &mut dyn Engine = match side {
+			Side::White => &mut *white,
+			Side::Black => &mut *black,
+		};
+
+		let engine_move = match engine.best_move(&game, opening_fen, &uci_moves, &search_limits(&game, time_limit)) {
+			Ok(engine_move) => engine_move,
+			Err(error) => break (forfeit(side), error),
+		};
+
+		let mv = match game.convert_uci_to_move(&engine_move.uci) {
+			Ok(mv) => mv,
+			_ => break (forfeit(side), format!("{} played the illegal move {}", engine.name(), engine_move.uci)),
+		};
+
+		match game.make_move(&mv) {
+			Ok(()) => {}
+			// The flag fell while thinking, the status says who won
+			Err(ChessError::GameIsFinished) => continue,
+			Err(_) => break (forfeit(side), format!("{} played the illegal move {}", engine.name(), engine_move.uci)),
+		}
+
+		moves.push(mv);
+		uci_moves.push(engine_move.uci);
+		white_scores.push(engine_move.score.map(|score| match side {
+			Side::White => score,
+			Side::Black => -score,
+		}));
+	};
+
+	finish(start_position, &moves, result, termination, tags)
+}
+
+/// Limits for the side to move, with the clock times when playing on a clock
+fn search_limits(game: &Game, time_limit: &TimeLimit) -> SearchLimits {
+	match (time_limit, &game.clock) {
+		(TimeLimit::Clock { increment, .. }, Some(clock)) => SearchLimits {
+			wtime: Some(clock.remaining(Side::White)),
+			btime: Some(clock.remaining(Side::Black)),
+			winc: Some(*increment),
+			binc: Some(*increment),
+			..Default::default()
+		},
+		(TimeLimit::PerMove(limits), _) => *limits,
+		(TimeLimit::Clock { .. }, None) => SearchLimits::default(),
+	}
+}
+
+/// Result of the game if the last plies agree on a decisive or a drawn score
+pub fn adjudicate(white_scores: &[Option<i32>], adjudication: &Adjudication) -> Option<(GameResult, String)> {
+	let last = |plies: usize| white_scores.len().checked_sub(plies).map(|start| &white_scores[start..]).filter(|_| plies > 0);
+
+	if let Some(scores) = last(2 * adjudication.resign_moves) {
+		if scores.iter().all(|score| score.is_some_and(|score| score >= adjudication.resign_score)) {
+			return Some((GameResult::WhiteWin, "adjudication: score".to_string()));
+		}
+		if scores.iter().all(|score| score.is_some_and(|score| score <= -adjudication.resign_score)) {
+			return Some((GameResult::BlackWin, "adjudication: score".to_string()));
+		}
+	}
+
+	if white_scores.len() / 2 >= adjudication.draw_after
+		&& let Some(scores) = last(2 * adjudication.draw_moves)
+		&& scores.iter().all(|score| score.is_some_and(|score| score.abs() <= adjudication.draw_score))
+	{
+		return Some((GameResult::Draw, "adjudication: draw score".to_string()));
+	}
+
+	None
+}
+
+pub fn result_string(result: GameResult) -> &'static str {
+	match result {
+		GameResult::WhiteWin => "1-0",
+		GameResult::Draw => "1/2-1/2",
+		GameResult::BlackWin => "0-1",
+	}
+}
+
+fn forfeit(side: Side) -> GameResult {
+	match side {
+		Side::White => GameResult::BlackWin,
+		Side::Black => GameResult::WhiteWin,
+	}
+}
+
+fn describe_status(game_status: &GameStatus) -> String {
+	match game_status {
+		GameStatus::CheckmateForSide(_) => "checkmate",
+		GameStatus::Stalemate => "stalemate",
+		GameStatus::DrawByAgreement => "draw by agreement",
+		GameStatus::DrawByFiftyMoves => "fifty move rule",
+		GameStatus::DrawByRepetition => "threefold repetition",
+		GameStatus::DrawByInsufficientMaterial => "insufficient material",
+		GameStatus::TimeoutForSide(_) => "time forfeit",
+		GameStatus::DrawByTimeoutVsInsufficientMaterial => "time forfeit against insufficient material",
+		GameStatus::ResignationForSide(_) => "resignation",
+		GameStatus::Playing | GameStatus::InCheck => "unterminated",
+	}
+	.to_string()
+}
+
+fn finish(start_position: Position, moves: &[Move], result: GameResult, termination: String, tags: &[(String, String)]) -> Result<GameOutcome, ChessError> {
+	let mut tree = GameTree::new(start_position);
+	for mv in moves {
+		tree.add_move(mv)?;
+	}
+
+	tree.tags = tags.to_vec();
+	tree.tags.push(("Result".to_string(), result_string(result).to_string()));
+	tree.tags.push(("Termination".to_string(), termination.clone()));
+
+	Ok(GameOutcome {
+		result,
+		termination,
+		pgn: tree.export_pgn()?,
+	})
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use chrust_core::{position::STARTING_FEN, search::MATE_SCORE};
+
+use super::*;
+use crate::engine::{EngineMove, EngineSpec};
+
+/// Plays a fixed list of moves and reports a fixed score
+struct ScriptedEngine {
+	moves: Vec<&'static str>,
+	score: Option<i32>,
+}
+
+impl Engine for ScriptedEngine {
+	fn name(&self) -> &str {
+		"scripted"
+	}
+
+	fn new_game(&mut self) -> Result<(), String> {
+		Ok(())
+	}
+
+	fn best_move(&mut self, _game: &Game, _start_fen: &str, _moves: &[String], _limits: &SearchLimits) -> Result<EngineMove, String> {
+		if self.moves.is_empty() {
+			return Err("scripted engine ran out of moves".to_string());
+		}
+
+		Ok(EngineMove {
+			uci: self.moves.remove(0).to_string(),
+			score: self.score,
+		})
+	}
+}
+
+fn scripted(moves: &[&'static str], score: Option<i32>) -> ScriptedEngine {
+	ScriptedEngine { moves: moves.to_vec(), score }
+}
+
+fn depth_limit() -> TimeLimit {
+	TimeLimit::PerMove(SearchLimits { depth: Some(2), ..Default::default() })
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Game tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn checkmate_ends_the_game_and_is_written_to_pgn() {
+	let mut white = scripted(&["f2f3", "g2g4"], None);
+	let mut black = scripted(&["e7e5", "d8h4"], None);
+	let tags = [
+		("White".to_string(), "A".to_string()),
+		("Black".to_string(), "B".to_string()),
+	];
+
+	let outcome = play_game(&mut white, &mut black, STARTING_FEN, &depth_limit(), &Adjudication::default(), &tags).unwrap();
+
+	assert_eq!(outcome.result, GameResult::BlackWin);
+	assert_eq!(outcome.termination, "checkmate");
+	assert!(outcome.pgn.starts_with("[White \"A\"]\n[Black \"B\"]\n[Result \"0-1\"]\n[Termination \"checkmate\"]\n"));
+	assert!(outcome.pgn.contains("1. f3 e5 2. g4 Qh4# 0-1"));
+}
+
+#[test]
+fn illegal_move_loses() {
+	let mut white = scripted(&["e2e5"], None);
+	let mut black = scripted(&[], None);
+
+	let outcome = play_game(&mut white, &mut black, STARTING_FEN, &depth_limit(), &Adjudication::default(), &[]).unwrap();
+
+	assert_eq!(outcome.result, GameResult::BlackWin);
+	assert!(outcome.termination.contains("illegal move e2e5"));
+}
+
+#[test]
+fn engine_failure_loses() {
+	let mut white = scripted(&["e2e4"], None);
+	let mut black = scripted(&[], None);
+
+	let outcome = play_game(&mut white, &mut black, STARTING_FEN, &depth_limit(), &Adjudication::default(), &[]).unwrap();
+
+	assert_eq!(outcome.result, GameResult::WhiteWin);
+	assert_eq!(outcome.termination, "scripted engine ran out of moves");
+}
+
+#[test]
+fn dead_position_is_drawn_right_away() {
+	let mut white = scripted(&[], None);
+	let mut black = scripted(&[], None);
+
+	let outcome = play_game(&mut white, &mut black, "4k3/8/8/1p1p1p1p/pPpPpPpP/P1P1P1P1/8/4K3 w - - 0 1", &depth_limit(), &Adjudication::default(), &[]).unwrap();
+
+	assert_eq!(outcome.result, GameResult::Draw);
+	assert_eq!(outcome.termination, "dead position");
+}
+
+#[test]
+fn builtin_engines_finish_a_game() {
+	let spec = EngineSpec::parse("builtin,hash=1").unwrap();
+	let mut white = spec.start().unwrap();
+	let mut black = spec.start().unwrap();
+	let adjudication = Adjudication { max_plies: 20, ..Default::default() };
+
+	let outcome = play_game(white.as_mut(), black.as_mut(), "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", &depth_limit(), &adjudication, &[]).unwrap();
+
+	assert_eq!(outcome.result, GameResult::WhiteWin);
+	assert_eq!(outcome.termination, "checkmate");
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Adjudication tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn both_engines_have_to_agree_to_resign() {
+	let adjudication = Adjudication {
+		resign_score: 500,
+		resign_moves: 2,
+		..Default::default()
+	};
+
+	assert_eq!(adjudicate(&[Some(600), Some(700), Some(800)], &adjudication), None);
+	assert_eq!(adjudicate(&[Some(600), Some(700), Some(800), Some(900)], &adjudication).map(|(result, _)| result), Some(GameResult::WhiteWin));
+	assert_eq!(adjudicate(&[Some(-600), Some(-700), Some(-800), Some(-MATE_SCORE)], &adjudication).map(|(result, _)| result), Some(GameResult::BlackWin));
+	assert_eq!(adjudicate(&[Some(600), Some(700), Some(800), Some(400)], &adjudication), None);
+	assert_eq!(adjudicate(&[Some(600), None, Some(800), Some(900)], &adjudication), None);
+}
+
+#[test]
+fn draw_adjudication_waits_for_the_move_number() {
+	let adjudication = Adjudication {
+		draw_score: 10,
+		draw_moves: 2,
+		draw_after: 3,
+		..Default::default()
+	};
+
+	assert_eq!(adjudicate(&[Some(0); 4], &adjudication), None);
+	assert_eq!(adjudicate(&[Some(5); 6], &adjudication).map(|(result, _)| result), Some(GameResult::Draw));
+	assert_eq!(adjudicate(&[Some(5), Some(5), Some(5), Some(50), Some(5), Some(5)], &adjudication), None);
+}
+
+#[test]
+fn score_adjudication_ends_the_game() {
+	let adjudication = Adjudication {
+		resign_score: 500,
+		resign_moves: 1,
+		..Default::default()
+	};
+	let mut white = scripted(&["e2e4", "d2d4"], Some(900));
+	let mut black = scripted(&["e7e5", "d7d5"], Some(-900));
+
+	let outcome = play_game(&mut white, &mut black, STARTING_FEN, &depth_limit(), &adjudication, &[]).unwrap();
+
+	assert_eq!(outcome.result, GameResult::WhiteWin);
+	assert_eq!(outcome.termination, "adjudication: score");
+	assert!(outcome.pgn.contains("1. e4 e5 1-0"));
+}
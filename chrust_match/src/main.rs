@@ -0,0 +1,295 @@
+mod engine;
+mod game;
+mod sprt;
+
+use std::{
+	fs::OpenOptions,
+	io::Write,
+	process::ExitCode,
+	sync::{
+		Mutex,
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+	},
+	thread,
+	time::Duration,
+};
+
+use chrust_core::{datagen::GameResult, errors::ChessError, position::STARTING_FEN, time_manager::SearchLimits};
+
+use crate::{
+	engine::EngineSpec,
+	game::{Adjudication, TimeLimit, play_game, result_string},
+	sprt::{Score, Sprt, SprtVerdict},
+};
+
+const USAGE: &str = "Usage: chrust_match --engine <spec> --engine <spec> [--rounds <n>] [--openings <file>] [--tc <seconds>+<increment> | --movetime <ms> | --depth <n> | --nodes <n>]
+                    [--concurrency <n>] [--pgn <file>] [--sprt <elo0>,<elo1>[,<alpha>,<beta>]] [--resign <cp>,<moves>] [--draw <cp>,<moves>,<after>] [--max-plies <n>]
+
+Engine specs are cmd=<path>[,name=<name>][,arg=<arg>][,option.<name>=<value>] for UCI engines and
+builtin[,name=<name>][,threads=<n>][,hash=<mb>][,null_move|lmr|rfp|razoring|check_extensions=<bool>] for the built-in search.
+Every opening is played twice with colours reversed, results are from the point of view of the first engine.
+Games are adjudicated by the scores of both engines (--resign, --draw) and by length (--max-plies), there is no tablebase adjudication.";
+
+struct Args {
+	engines: [EngineSpec; 2],
+	rounds: usize,
+	openings: Vec<String>,
+	time_limit: TimeLimit,
+	concurrency: usize,
+	pgn: Option<String>,
+	sprt: Option<Sprt>,
+	adjudication: Adjudication,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run(&args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let mut engines = Vec::new();
+	let mut rounds = 50;
+	let mut openings = Vec::new();
+	let mut time_limit = TimeLimit::PerMove(SearchLimits {
+		movetime: Some(Duration::from_millis(100)),
+		..Default::default()
+	});
+	let mut concurrency = 1;
+	let mut pgn = None;
+	let mut sprt = None;
+	let mut adjudication = Adjudication::default();
+
+	while let Some(flag) = args.next() {
+		let mut value = || args.next().ok_or(format!("Missing value for {}", flag));
+
+		match flag.as_str() {
+			"--engine" => engines.push(EngineSpec::parse(&value()?)?),
+			"--rounds" => rounds = parse_number(&value()?)?,
+			"--concurrency" => concurrency = parse_number::<usize>(&value()?)?.max(1),
+			"--pgn" => pgn = Some(value()?),
+			"--max-plies" => adjudication.max_plies = parse_number(&value()?)?,
+			"--openings" => {
+				let path = value()?;
+				let text = std::fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+				openings = text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).map(opening_fen).collect();
+			}
+			"--tc" => {
+				let value = value()?;
+				let (time, increment) = value.split_once('+').unwrap_or((&value, "0"));
+				time_limit = TimeLimit::Clock {
+					time: parse_seconds(time)?,
+					increment: parse_seconds(increment)?,
+				};
+			}
+			"--movetime" => {
+				time_limit = TimeLimit::PerMove(SearchLimits {
+					movetime: Some(Duration::from_millis(parse_number(&value()?)?)),
+					..Default::default()
+				})
+			}
+			"--depth" => {
+				time_limit = TimeLimit::PerMove(SearchLimits {
+					depth: Some(parse_number(&value()?)?),
+					..Default::default()
+				})
+			}
+			"--nodes" => {
+				time_limit = TimeLimit::PerMove(SearchLimits {
+					nodes: Some(parse_number(&value()?)?),
+					..Default::default()
+				})
+			}
+			"--sprt" => {
+				let values = parse_list(&value()?)?;
+				let (elo0, elo1, alpha, beta) = match values[..] {
+					[elo0, elo1] => (elo0, elo1, 0.05, 0.05),
+					[elo0, elo1, alpha, beta] => (elo0, elo1, alpha, beta),
+					_ => return Err("--sprt takes elo0,elo1 or elo0,elo1,alpha,beta".to_string()),
+				};
+				sprt = Some(Sprt { elo0, elo1, alpha, beta });
+			}
+			"--resign" => match parse_list(&value()?)?[..] {
+				[score, moves] => {
+					adjudication.resign_score = score as i32;
+					adjudication.resign_moves = moves as usize;
+				}
+				_ => return Err("--resign takes cp,moves".to_string()),
+			},
+			"--draw" => match parse_list(&value()?)?[..] {
+				[score, moves, after] => {
+					adjudication.draw_score = score as i32;
+					adjudication.draw_moves = moves as usize;
+					adjudication.draw_after = after as usize;
+				}
+				_ => return Err("--draw takes cp,moves,after".to_string()),
+			},
+			"--help" | "-h" => return Err(String::new()),
+			_ => return Err(format!("Unknown argument {}", flag)),
+		}
+	}
+
+	let engines: [EngineSpec; 2] = engines.try_into().map_err(|_| "Exactly two --engine arguments are needed".to_string())?;
+	if openings.is_empty() {
+		openings.push(STARTING_FEN.to_string());
+	}
+
+	Ok(Args {
+		engines,
+		rounds,
+		openings,
+		time_limit,
+		concurrency,
+		pgn,
+		sprt,
+		adjudication,
+	})
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid number {}", value))
+}
+
+/// Rejects negative, infinite and NaN values, which `Duration` can't hold
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+	Duration::try_from_secs_f64(parse_number(value)?).map_err(|_| format!("Invalid number of seconds {}", value))
+}
+
+fn parse_list(value: &str) -> Result<Vec<f64>, String> {
+	value.split(',').map(parse_number).collect()
+}
+
+/// Accepts EPD lines without move counters and drops any operations after the FEN
+fn opening_fen(line: &str) -> String {
+	let mut fields: Vec<&str> = line.split(';').next().unwrap_or_default().split_whitespace().collect();
+
+	match fields.len() {
+		4 => fields.extend(["0", "1"]),
+		5.. if fields[4].parse::<u32>().is_err() => {
+			fields.truncate(4);
+			fields.extend(["0", "1"]);
+		}
+		_ => fields.truncate(6),
+	}
+
+	fields.join(" ")
+}
+
+/// Plays `rounds` game pairs on `concurrency` workers, each with its own engine processes, and stops
+/// early once the SPRT reaches a verdict
+fn run(args: &Args) -> Result<(), String> {
+	let games = 2 * args.rounds;
+	let next_game = AtomicUsize::new(0);
+	let stop = AtomicBool::new(false);
+	let score = Mutex::new(Score::default());
+	let pgn_file = match &args.pgn {
+		Some(path) => Some(Mutex::new(OpenOptions::new().create(true).append(true).open(path).map_err(|e| format!("Can't open {}: {}", path, e))?)),
+		None => None,
+	};
+	let [first, second] = &args.engines;
+
+	let errors: Vec<String> = thread::scope(|scope| {
+		let workers: Vec<_> = (0..args.concurrency.min(games.max(1)))
+			.map(|_| {
+				scope.spawn(|| -> Result<(), String> {
+					let mut engines = [first.start()?, second.start()?];
+
+					loop {
+						let game_index = next_game.fetch_add(1, Ordering::Relaxed);
+						if game_index >= games || stop.load(Ordering::Relaxed) {
+							return Ok(());
+						}
+
+						// The first engine plays white in even games, so every opening is played from both sides
+						let round = game_index / 2;
+						let first_is_white = game_index.is_multiple_of(2);
+						let [a, b] = &mut engines;
+						let (white, black) = if first_is_white {
+							(a, b)
+						} else {
+							(b, a)
+						};
+						let tags = [
+							("Event", "chrust_match".to_string()),
+							("Site", "?".to_string()),
+							("Date", "????.??.??".to_string()),
+							("Round", format!("{}.{}", round + 1, game_index % 2 + 1)),
+							("White", white.name().to_string()),
+							("Black", black.name().to_string()),
+						]
+						.map(|(name, value)| (name.to_string(), value));
+
+						let opening = &args.openings[round % args.openings.len()];
+						let outcome = play_game(white.as_mut(), black.as_mut(), opening, &args.time_limit, &args.adjudication, &tags).map_err(|e| match e {
+							ChessError::FenError { .. } => format!("Invalid opening {}: {}", opening, e),
+							e => format!("Game {} failed: {}", game_index + 1, e),
+						})?;
+
+						if let Some(file) = &pgn_file {
+							let mut file = file.lock().expect("main.rs: pgn file lock poisoned");
+							writeln!(file, "{}", outcome.pgn).map_err(|e| format!("Can't write PGN: {}", e))?;
+						}
+
+						let mut score = score.lock().expect("main.rs: score lock poisoned");
+						match (outcome.result, first_is_white) {
+							(GameResult::Draw, _) => score.draws += 1,
+							(GameResult::WhiteWin, true) | (GameResult::BlackWin, false) => score.wins += 1,
+							_ => score.losses += 1,
+						}
+
+						println!("Game {} ({} vs {}): {} {{{}}}", game_index + 1, tags[4].1, tags[5].1, result_string(outcome.result), outcome.termination);
+						println!("Score of {} vs {}: {} - {} - {} [{:.3}] {}", first.name(), second.name(), score.wins, score.losses, score.draws, score.score().unwrap_or(0.5), score.games());
+
+						if let Some(sprt) = &args.sprt
+							&& sprt.verdict(&score) != SprtVerdict::Continue
+						{
+							stop.store(true, Ordering::Relaxed);
+						}
+					}
+				})
+			})
+			.collect();
+
+		workers.into_iter().filter_map(|worker| worker.join().expect("main.rs: match worker panicked").err()).collect()
+	});
+
+	let score = score.into_inner().expect("main.rs: score lock poisoned");
+	report(args, &score);
+
+	match errors.first() {
+		Some(error) => Err(error.clone()),
+		None => Ok(()),
+	}
+}
+
+fn report(args: &Args, score: &Score) {
+	println!();
+	println!("Finished {} games: {} - {} - {} for {}", score.games(), score.wins, score.losses, score.draws, args.engines[0].name());
+
+	match score.elo() {
+		Some((elo, margin)) => println!("Elo difference: {:.1} +/- {:.1}", elo, margin),
+		None => println!("Elo difference: not enough decisive games"),
+	}
+
+	if let Some(sprt) = &args.sprt {
+		let (lower, upper) = sprt.bounds();
+		let verdict = match sprt.verdict(score) {
+			SprtVerdict::H1 => "H1 accepted",
+			SprtVerdict::H0 => "H0 accepted",
+			SprtVerdict::Continue => "no verdict yet",
+		};
+		println!("SPRT [{}, {}]: LLR {:.2} ({:.2}, {:.2}), {}", sprt.elo0, sprt.elo1, sprt.llr(score), lower, upper, verdict);
+	}
+}
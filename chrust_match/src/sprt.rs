@@ -0,0 +1,125 @@
+//! Match statistics: logistic Elo with a 95% confidence interval and a sequential probability ratio
+//! test on the trinomial (win, draw, loss) distribution.
+
+/// Two sided 95% quantile of the normal distribution
+const CONFIDENCE_Z: f64 = 1.959_964;
+
+/// Wins, draws and losses from the point of view of the first engine
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Score {
+	pub wins: u32,
+	pub draws: u32,
+	pub losses: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sprt {
+	/// Elo difference of the null hypothesis
+	pub elo0: f64,
+	/// Elo difference of the alternative hypothesis
+	pub elo1: f64,
+	/// False positive rate
+	pub alpha: f64,
+	/// False negative rate
+	pub beta: f64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SprtVerdict {
+	/// H1 accepted: the first engine is at least `elo1` stronger
+	H1,
+	/// H0 accepted: the first engine is at most `elo0` stronger
+	H0,
+	Continue,
+}
+
+impl Score {
+	pub fn games(&self) -> u32 {
+		self.wins + self.draws + self.losses
+	}
+
+	/// Points per game, 1.0 for only wins
+	pub fn score(&self) -> Option<f64> {
+		if self.games() == 0 {
+			return None;
+		}
+
+		Some((self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64)
+	}
+
+	/// Elo difference and the half width of its 95% confidence interval. `None` without games or when
+	/// every game had the same result, since the variance is zero then.
+	pub fn elo(&self) -> Option<(f64, f64)> {
+		let score = self.score()?;
+		let variance = self.score_variance()?;
+		let margin = CONFIDENCE_Z * variance.sqrt();
+
+		let low = score_to_elo(score - margin)?;
+		let high = score_to_elo(score + margin)?;
+
+		Some((score_to_elo(score)?, (high - low) / 2.0))
+	}
+
+	/// Variance of the mean score per game
+	fn score_variance(&self) -> Option<f64> {
+		let games = self.games() as f64;
+		let score = self.score()?;
+		let win_ratio = self.wins as f64 / games;
+		let draw_ratio = self.draws as f64 / games;
+
+		let variance = win_ratio + draw_ratio / 4.0 - score * score;
+		if variance <= 0.0 {
+			return None;
+		}
+
+		Some(variance / games)
+	}
+}
+
+impl Sprt {
+	/// Log likelihood ratio of H1 against H0 (generalized SPRT with the normal approximation)
+	pub fn llr(&self, score: &Score) -> f64 {
+		let (Some(mean), Some(variance)) = (score.score(), score.score_variance()) else {
+			return 0.0;
+		};
+
+		let score0 = elo_to_score(self.elo0);
+		let score1 = elo_to_score(self.elo1);
+
+		(score1 - score0) * (2.0 * mean - score0 - score1) / (2.0 * variance)
+	}
+
+	/// Lower and upper LLR bound, crossing either ends the test
+	pub fn bounds(&self) -> (f64, f64) {
+		((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln())
+	}
+
+	pub fn verdict(&self, score: &Score) -> SprtVerdict {
+		let llr = self.llr(score);
+		let (lower, upper) = self.bounds();
+
+		if llr >= upper {
+			SprtVerdict::H1
+		} else if llr <= lower {
+			SprtVerdict::H0
+		} else {
+			SprtVerdict::Continue
+		}
+	}
+}
+
+pub fn elo_to_score(elo: f64) -> f64 {
+	1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Inverse of `elo_to_score`, `None` for a score of 0 or 1 where the Elo difference is infinite
+pub fn score_to_elo(score: f64) -> Option<f64> {
+	if score <= 0.0 || score >= 1.0 {
+		return None;
+	}
+
+	Some(-400.0 * (1.0 / score - 1.0).log10())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,77 @@
+use super::*;
+
+const SPRT: Sprt = Sprt {
+	elo0: 0.0,
+	elo1: 10.0,
+	alpha: 0.05,
+	beta: 0.05,
+};
+
+fn score(wins: u32, draws: u32, losses: u32) -> Score {
+	Score { wins, draws, losses }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Elo tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn elo_and_score_convert_back_and_forth() {
+	for elo in [-400.0, -35.5, 0.0, 12.0, 250.0] {
+		assert!((score_to_elo(elo_to_score(elo)).unwrap() - elo).abs() < 1e-9);
+	}
+
+	assert_eq!(score_to_elo(0.0), None);
+	assert_eq!(score_to_elo(1.0), None);
+}
+
+#[test]
+fn even_score_is_zero_elo() {
+	let (elo, margin) = score(30, 40, 30).elo().unwrap();
+
+	assert!(elo.abs() < 1e-9);
+	assert!(margin > 0.0);
+}
+
+#[test]
+fn winning_score_is_positive_elo_and_more_games_shrink_the_error() {
+	let (elo, margin) = score(60, 20, 20).elo().unwrap();
+	let (_, large_margin) = score(6, 2, 2).elo().unwrap();
+
+	// 70% is about 147 Elo
+	assert!((elo - 147.2).abs() < 0.1);
+	assert!(margin < large_margin);
+}
+
+#[test]
+fn elo_needs_games_with_different_results() {
+	assert_eq!(score(0, 0, 0).elo(), None);
+	assert_eq!(score(0, 10, 0).elo(), None);
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// SPRT tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn bounds_match_the_error_rates() {
+	let (lower, upper) = SPRT.bounds();
+
+	assert!((lower + 2.944).abs() < 0.001);
+	assert!((upper - 2.944).abs() < 0.001);
+}
+
+#[test]
+fn verdicts_follow_the_score() {
+	assert_eq!(SPRT.verdict(&score(0, 0, 0)), SprtVerdict::Continue);
+	assert_eq!(SPRT.verdict(&score(12, 10, 10)), SprtVerdict::Continue);
+	assert_eq!(SPRT.verdict(&score(700, 600, 500)), SprtVerdict::H1);
+	assert_eq!(SPRT.verdict(&score(500, 600, 700)), SprtVerdict::H0);
+}
+
+#[test]
+fn llr_is_zero_halfway_between_the_hypotheses() {
+	let sprt = Sprt { elo0: -10.0, ..SPRT };
+
+	assert!(sprt.llr(&score(40, 20, 40)).abs() < 1e-9);
+}
@@ -0,0 +1,126 @@
+use chrust_core::{
+	perft::PerftOptions,
+	position::{Game, Position},
+};
+
+/// Node count below every root move, moves in UCI notation
+pub type Divide = Vec<(String, u64)>;
+
+/// Differences between our divide and the reference divide of the same position
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DivideDiff {
+	/// Moves only the reference generates
+	pub missing: Vec<String>,
+	/// Moves only we generate
+	pub extra: Vec<String>,
+	/// Moves both generate with different counts: move, our count, reference count
+	pub different: Vec<(String, u64, u64)>,
+}
+
+/// One step of a bisection: the mismatching move at `depth` after `moves`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BisectStep {
+	pub moves: Vec<String>,
+	pub depth: u8,
+	pub diff: DivideDiff,
+}
+
+impl DivideDiff {
+	pub fn is_empty(&self) -> bool {
+		self.missing.is_empty() && self.extra.is_empty() && self.different.is_empty()
+	}
+}
+
+/// Reads divide output like Stockfish's `go perft`: one `<move>: <nodes>` line per root move. Lines
+/// that don't start with a move, e.g. the node total, are skipped.
+pub fn parse_divide(text: &str) -> Result<Divide, String> {
+	let mut divide = Divide::new();
+
+	for line in text.lines() {
+		let mut tokens = line.split(|c: char| c == ':' || c.is_whitespace()).filter(|token| !token.is_empty());
+		let (Some(mv), Some(nodes)) = (tokens.next(), tokens.next()) else {
+			continue;
+		};
+		if !is_uci_move(mv) {
+			continue;
+		}
+
+		let nodes = nodes.parse().map_err(|_| format!("Invalid node count in divide line {}", line))?;
+		divide.push((mv.to_ascii_lowercase(), nodes));
+	}
+
+	Ok(divide)
+}
+
+fn is_uci_move(text: &str) -> bool {
+	let bytes = text.as_bytes();
+	let is_square = |file: u8, rank: u8| (b'a'..=b'h').contains(&file.to_ascii_lowercase()) && (b'1'..=b'8').contains(&rank);
+
+	matches!(bytes.len(), 4 | 5) && is_square(bytes[0], bytes[1]) && is_square(bytes[2], bytes[3]) && bytes.get(4).is_none_or(|piece| b"qrbnQRBN".contains(piece))
+}
+
+pub fn compare(ours: &Divide, reference: &Divide) -> DivideDiff {
+	let mut diff = DivideDiff::default();
+
+	for (mv, nodes) in reference {
+		match ours.iter().find(|(our_move, _)| our_move == mv) {
+			None => diff.missing.push(mv.clone()),
+			Some((_, our_nodes)) if our_nodes != nodes => diff.different.push((mv.clone(), *our_nodes, *nodes)),
+			Some(_) => {}
+		}
+	}
+	diff.extra = ours.iter().filter(|(mv, _)| !reference.iter().any(|(reference_move, _)| reference_move == mv)).map(|(mv, _)| mv.clone()).collect();
+
+	diff
+}
+
+/// Position after playing `moves` from `fen`
+pub fn position_after(fen: &str, moves: &[String]) -> Result<Position, String> {
+	let mut game = Game::try_from_fen(fen).map_err(|e| format!("Invalid FEN {}: {}", fen, e))?;
+	for mv in moves {
+		game.make_move_from_uci(mv).map_err(|e| format!("Can't play {}: {}", mv, e))?;
+	}
+
+	Ok(game.position)
+}
+
+pub fn divide(position: &Position, depth: u8, options: &PerftOptions) -> Result<Divide, String> {
+	let divide = position.divide(depth, options).map_err(|e| format!("Perft failed: {}", e))?;
+
+	Ok(divide.into_iter().map(|(mv, nodes)| (mv.to_uci(), nodes)).collect())
+}
+
+/// Follows the first move with a different count one ply deeper until a position is reached where
+/// the move lists themselves differ. `reference` computes the reference divide for a list of moves
+/// from `fen` and a depth. Returns every step, the last one holds the missing or extra moves, and
+/// nothing if both divides agree.
+pub fn bisect(fen: &str, initial_moves: &[String], depth: u8, options: &PerftOptions, mut reference: impl FnMut(&[String], u8) -> Result<Divide, String>) -> Result<Vec<BisectStep>, String> {
+	let mut moves = initial_moves.to_vec();
+	let mut steps = Vec::new();
+
+	for depth in (1..=depth).rev() {
+		let ours = divide(&position_after(fen, &moves)?, depth, options)?;
+		let diff = compare(&ours, &reference(&moves, depth)?);
+
+		if diff.is_empty() {
+			if !steps.is_empty() {
+				return Err(format!("The counts differ one ply higher but agree after {}, is the reference deterministic?", moves.join(" ")));
+			}
+			break;
+		}
+
+		let next_move = diff.different.first().map(|(mv, _, _)| mv.clone());
+		let is_move_list_mismatch = !diff.missing.is_empty() || !diff.extra.is_empty();
+		steps.push(BisectStep { moves: moves.clone(), depth, diff });
+
+		match next_move {
+			Some(mv) if !is_move_list_mismatch => moves.push(mv),
+			_ => break,
+		}
+	}
+
+	Ok(steps)
+}
+
+#[cfg(test)]
+mod tests;
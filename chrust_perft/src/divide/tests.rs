@@ -0,0 +1,97 @@
+use chrust_core::position::STARTING_FEN;
+
+use super::*;
+
+fn divide_of(entries: &[(&str, u64)]) -> Divide {
+	entries.iter().map(|&(mv, nodes)| (mv.to_string(), nodes)).collect()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Parsing tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn stockfish_output_is_parsed() {
+	let text = "info string NNUE evaluation using nn.nnue\na2a3: 380\nb7b8q: 1\ng1f3 440\n\nNodes searched: 821\n";
+
+	assert_eq!(parse_divide(text), Ok(divide_of(&[("a2a3", 380), ("b7b8q", 1), ("g1f3", 440)])));
+}
+
+#[test]
+fn invalid_node_count_is_rejected() {
+	assert!(parse_divide("e2e4: many").is_err());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Comparison tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn compare_finds_every_difference() {
+	let ours = divide_of(&[("e2e4", 20), ("d2d4", 21), ("e1g1", 5)]);
+	let reference = divide_of(&[("d2d4", 20), ("e2e4", 20), ("e1c1", 5)]);
+
+	assert_eq!(
+		compare(&ours, &reference),
+		DivideDiff {
+			missing: vec!["e1c1".to_string()],
+			extra: vec!["e1g1".to_string()],
+			different: vec![("d2d4".to_string(), 21, 20)],
+		}
+	);
+	assert!(compare(&ours, &ours).is_empty());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Bisection tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn bisect_without_mismatch_has_no_steps() {
+	let options = PerftOptions::default();
+	let steps = bisect(STARTING_FEN, &[], 3, &options, |moves, depth| divide(&position_after(STARTING_FEN, moves)?, depth, &options)).unwrap();
+
+	assert!(steps.is_empty());
+}
+
+#[test]
+fn bisect_follows_the_mismatch_down_to_the_missing_move() {
+	let options = PerftOptions::default();
+
+	// A reference that also finds a phantom move after 1. e4 e5
+	let reference = |moves: &[String], depth: u8| -> Result<Divide, String> {
+		let mut reference = divide(&position_after(STARTING_FEN, moves)?, depth, &options)?;
+		for (mv, nodes) in reference.iter_mut() {
+			if moves.len() == 1 && mv == "e7e5" && depth >= 2 {
+				*nodes += 1;
+			}
+			if moves.is_empty() && mv == "e2e4" && depth >= 3 {
+				*nodes += 1;
+			}
+		}
+		if moves == ["e2e4", "e7e5"] {
+			reference.push(("e1e3".to_string(), 1));
+		}
+		Ok(reference)
+	};
+
+	let steps = bisect(STARTING_FEN, &[], 3, &options, reference).unwrap();
+
+	assert_eq!(steps.len(), 3);
+	assert_eq!(steps[0].diff.different, vec![("e2e4".to_string(), 600, 601)]);
+	assert_eq!(steps[1].moves, vec!["e2e4".to_string()]);
+	assert_eq!(steps[2].moves, vec!["e2e4".to_string(), "e7e5".to_string()]);
+	assert_eq!(steps[2].depth, 1);
+	assert_eq!(steps[2].diff.missing, vec!["e1e3".to_string()]);
+}
+
+#[test]
+fn bisect_starts_after_the_given_moves() {
+	let options = PerftOptions::default();
+	let start = vec!["e2e4".to_string()];
+	let steps = bisect(STARTING_FEN, &start, 1, &options, |_, _| Ok(divide_of(&[("e7e5", 1)]))).unwrap();
+
+	assert_eq!(steps.len(), 1);
+	assert_eq!(steps[0].moves, start);
+	assert_eq!(steps[0].diff.extra.len(), 19);
+}
@@ -0,0 +1,181 @@
+mod divide;
+mod reference;
+
+use std::{io::Read, process::ExitCode, time::Instant};
+
+use chrust_core::{perft::PerftOptions, position::STARTING_FEN};
+
+use crate::{
+	divide::{BisectStep, DivideDiff, bisect, compare, divide, parse_divide, position_after},
+	reference::ReferenceEngine,
+};
+
+const USAGE: &str = "Usage: chrust_perft --depth <n> [--fen <fen>] [--moves <uci>...] [--divide] [--threads <n>] [--hash <mb>]
+                    [--compare <file> | --reference <command>]
+
+Counts the leaf nodes of the move tree. --divide prints the count below every root move, --compare diffs
+that against divide output of another engine in a file (- for stdin), --reference starts a UCI engine
+that supports go perft and bisects down to the first position where the move lists differ.";
+
+struct Args {
+	fen: String,
+	moves: Vec<String>,
+	depth: u8,
+	divide: bool,
+	options: PerftOptions,
+	compare: Option<String>,
+	reference: Option<String>,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run(&args) {
+		Ok(true) => ExitCode::SUCCESS,
+		// A mismatch with the reference was found and printed
+		Ok(false) => ExitCode::FAILURE,
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let mut fen = STARTING_FEN.to_string();
+	let mut moves = Vec::new();
+	let mut depth = None;
+	let mut divide = false;
+	let mut options = PerftOptions::default();
+	let mut compare = None;
+	let mut reference = None;
+
+	let mut args = args.peekable();
+	while let Some(flag) = args.next() {
+		let mut value = || args.next().ok_or(format!("Missing value for {}", flag));
+
+		match flag.as_str() {
+			"--fen" => fen = value()?,
+			"--depth" => depth = Some(parse_number(&value()?)?),
+			"--divide" => divide = true,
+			"--threads" => options.threads = parse_number(&value()?)?,
+			"--hash" => options.hash_size_mb = parse_number(&value()?)?,
+			"--compare" => compare = Some(value()?),
+			"--reference" => reference = Some(value()?),
+			"--moves" => {
+				while let Some(mv) = args.next_if(|arg| !arg.starts_with("--")) {
+					moves.push(mv);
+				}
+			}
+			"--help" | "-h" => return Err(String::new()),
+			_ => return Err(format!("Unknown argument {}", flag)),
+		}
+	}
+
+	if compare.is_some() && reference.is_some() {
+		return Err("--compare and --reference can't be combined".to_string());
+	}
+
+	Ok(Args {
+		fen,
+		moves,
+		depth: depth.ok_or("Missing --depth".to_string())?,
+		divide,
+		options,
+		compare,
+		reference,
+	})
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid number {}", value))
+}
+
+/// Returns whether the counts matched the reference, always true without one
+fn run(args: &Args) -> Result<bool, String> {
+	if let Some(command) = &args.reference {
+		let mut engine = ReferenceEngine::start(command)?;
+		let steps = bisect(&args.fen, &args.moves, args.depth, &args.options, |moves, depth| engine.divide(&args.fen, moves, depth))?;
+		report_bisect(&args.fen, &steps);
+		return Ok(steps.is_empty());
+	}
+
+	let position = position_after(&args.fen, &args.moves)?;
+	let start = Instant::now();
+	let ours = divide(&position, args.depth, &args.options)?;
+	let elapsed = start.elapsed();
+	let nodes = match args.depth {
+		0 => 1,
+		_ => ours.iter().map(|(_, nodes)| nodes).sum(),
+	};
+
+	if args.divide || args.compare.is_some() {
+		for (mv, nodes) in &ours {
+			println!("{}: {}", mv, nodes);
+		}
+		println!();
+	}
+	println!("Nodes searched: {}", nodes);
+	println!("Time: {} ms, {:.0} nodes/s", elapsed.as_millis(), nodes as f64 / elapsed.as_secs_f64().max(1e-9));
+
+	let Some(path) = &args.compare else {
+		return Ok(true);
+	};
+
+	let text = match path.as_str() {
+		"-" => {
+			let mut text = String::new();
+			std::io::stdin().read_to_string(&mut text).map_err(|e| format!("Can't read stdin: {}", e))?;
+			text
+		}
+		_ => std::fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path, e))?,
+	};
+
+	let diff = compare(&ours, &parse_divide(&text)?);
+	println!();
+	if diff.is_empty() {
+		println!("Divide matches the reference");
+	} else {
+		print_diff(&diff);
+	}
+
+	Ok(diff.is_empty())
+}
+
+fn print_diff(diff: &DivideDiff) {
+	for mv in &diff.missing {
+		println!("  {}: missing, only the reference generates it", mv);
+	}
+	for mv in &diff.extra {
+		println!("  {}: extra, the reference doesn't generate it", mv);
+	}
+	for (mv, ours, reference) in &diff.different {
+		println!("  {}: {} instead of {} ({:+})", mv, ours, reference, *ours as i64 - *reference as i64);
+	}
+}
+
+fn report_bisect(fen: &str, steps: &[BisectStep]) {
+	let Some(last) = steps.last() else {
+		println!("Divide matches the reference");
+		return;
+	};
+
+	for step in steps {
+		println!("Depth {} after [{}]:", step.depth, step.moves.join(" "));
+		print_diff(&step.diff);
+	}
+
+	println!();
+	let mut position = format!("position fen {}", fen);
+	if !last.moves.is_empty() {
+		position.push_str(" moves ");
+		position.push_str(&last.moves.join(" "));
+	}
+	println!("First mismatch: {}", position);
+}
@@ -0,0 +1,75 @@
+use std::{
+	io::{BufRead, BufReader, Lines, Write},
+	process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use crate::divide::{Divide, parse_divide};
+
+/// A UCI engine that supports `go perft <depth>`, e.g. Stockfish, kept running between divides
+pub struct ReferenceEngine {
+	child: Child,
+	stdin: ChildStdin,
+	lines: Lines<BufReader<ChildStdout>>,
+}
+
+impl ReferenceEngine {
+	/// Starts `command`, which is split on whitespace into the program and its arguments
+	pub fn start(command: &str) -> Result<ReferenceEngine, String> {
+		let mut parts = command.split_whitespace();
+		let program = parts.next().ok_or("Empty reference command".to_string())?;
+
+		let mut child = Command::new(program)
+			.args(parts)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::null())
+			.spawn()
+			.map_err(|e| format!("Can't start {}: {}", program, e))?;
+		let stdin = child.stdin.take().expect("reference.rs: stdin is piped");
+		let lines = BufReader::new(child.stdout.take().expect("reference.rs: stdout is piped")).lines();
+
+		let mut engine = ReferenceEngine { child, stdin, lines };
+		engine.send("uci")?;
+		engine.read_until("uciok")?;
+
+		Ok(engine)
+	}
+
+	pub fn divide(&mut self, fen: &str, moves: &[String], depth: u8) -> Result<Divide, String> {
+		let mut position = format!("position fen {}", fen);
+		if !moves.is_empty() {
+			position.push_str(" moves ");
+			position.push_str(&moves.join(" "));
+		}
+
+		self.send(&position)?;
+		self.send(&format!("go perft {}", depth))?;
+
+		parse_divide(&self.read_until("Nodes")?.join("\n"))
+	}
+
+	fn send(&mut self, command: &str) -> Result<(), String> {
+		writeln!(self.stdin, "{}", command).and_then(|_| self.stdin.flush()).map_err(|e| format!("The reference engine stopped accepting commands: {}", e))
+	}
+
+	/// Lines up to and including the first one starting with `token`
+	fn read_until(&mut self, token: &str) -> Result<Vec<String>, String> {
+		let mut lines = Vec::new();
+
+		loop {
+			let line = self.lines.next().ok_or("The reference engine exited".to_string())?.map_err(|e| format!("Can't read from the reference engine: {}", e))?;
+			let done = line.starts_with(token);
+			lines.push(line);
+			if done {
+				return Ok(lines);
+			}
+		}
+	}
+}
+
+impl Drop for ReferenceEngine {
+	fn drop(&mut self) {
+		let _ = self.send("quit");
+		let _ = self.child.wait();
+	}
+}
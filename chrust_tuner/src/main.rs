@@ -0,0 +1,118 @@
+use std::process::ExitCode;
+
+use chrust_core::{
+	datagen::decode_records,
+	evaluation::DEFAULT_EVAL_PARAMS,
+	tuner::{LabelledPosition, Tuner, TunerOptions, parse_labelled_position},
+};
+
+const USAGE: &str = "Usage: chrust_tuner --input <file>... [--epochs <n>] [--learning-rate <x>] [--k <x>] [--output <file>]
+
+Tunes the evaluation weights on labelled positions and writes them as Rust source, to stdout without --output.
+Inputs ending in .bin are read as chrust_datagen records, anything else as one FEN and result per line.";
+
+const REPORT_INTERVAL: usize = 50;
+
+struct Args {
+	inputs: Vec<String>,
+	output: Option<String>,
+	epochs: usize,
+	options: TunerOptions,
+}
+
+fn main() -> ExitCode {
+	let args = match parse_args(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run(&args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(message) => {
+			eprintln!("{}", message);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+	let mut inputs = Vec::new();
+	let mut output = None;
+	let mut epochs = 500;
+	let mut options = TunerOptions::default();
+
+	while let Some(flag) = args.next() {
+		let mut value = || args.next().ok_or(format!("Missing value for {}", flag));
+
+		match flag.as_str() {
+			"--input" => inputs.push(value()?),
+			"--output" => output = Some(value()?),
+			"--epochs" => epochs = parse_number(&value()?)?,
+			"--learning-rate" => options.learning_rate = parse_number(&value()?)?,
+			"--k" => options.k = Some(parse_number(&value()?)?),
+			"--help" | "-h" => return Err(String::new()),
+			_ => return Err(format!("Unknown argument {}", flag)),
+		}
+	}
+
+	if inputs.is_empty() {
+		return Err("Missing --input".to_string());
+	}
+
+	Ok(Args { inputs, output, epochs, options })
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid number {}", value))
+}
+
+fn run(args: &Args) -> Result<(), String> {
+	let mut positions = Vec::new();
+	for input in &args.inputs {
+		positions.extend(read_positions(input)?);
+	}
+
+	eprintln!("Resolving {} positions", positions.len());
+	let mut tuner = Tuner::new(&positions, &DEFAULT_EVAL_PARAMS, &args.options).map_err(|e| format!("Can't resolve positions: {:?}", e))?;
+	if tuner.is_empty() {
+		return Err("No usable positions".to_string());
+	}
+	eprintln!("Tuning on {} positions with k = {:.4}, initial loss {:.6}", tuner.len(), tuner.k(), tuner.loss());
+
+	for epoch in 1..=args.epochs {
+		tuner.step();
+
+		if epoch % REPORT_INTERVAL == 0 || epoch == args.epochs {
+			eprintln!("Epoch {} / {}, loss {:.6}", epoch, args.epochs, tuner.loss());
+		}
+	}
+
+	let source = tuner.params().to_rust_source();
+	match &args.output {
+		Some(output) => std::fs::write(output, source).map_err(|e| format!("Can't write {}: {}", output, e)),
+		None => {
+			print!("{}", source);
+			Ok(())
+		}
+	}
+}
+
+fn read_positions(path: &str) -> Result<Vec<LabelledPosition>, String> {
+	if path.ends_with(".bin") {
+		let bytes = std::fs::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+		let records = decode_records(&bytes).map_err(|e| format!("Invalid records in {}: {}", path, e))?;
+
+		return Ok(records.into_iter().map(LabelledPosition::from).collect());
+	}
+
+	let text = std::fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+	text
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+		.map(|(index, line)| parse_labelled_position(line).map_err(|e| format!("{}:{}: {:?}", path, index + 1, e)))
+		.collect()
+}
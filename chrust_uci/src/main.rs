@@ -0,0 +1,45 @@
+mod session;
+
+use std::{
+	io::{BufRead, Write},
+	sync::mpsc,
+	thread,
+};
+
+use chrust_core::search::SearchOptions;
+
+use crate::session::{Command, Session};
+
+/// Speaks UCI on stdin and stdout, so the engine can be loaded into a chess GUI or a match runner
+fn main() {
+	let (output, lines) = mpsc::channel::<String>();
+	let printer = thread::spawn(move || {
+		let mut stdout = std::io::stdout().lock();
+		for line in lines {
+			// The GUI is gone, nothing left to answer
+			if writeln!(stdout, "{}", line).and_then(|_| stdout.flush()).is_err() {
+				break;
+			}
+		}
+	});
+
+	let mut session = Session::new(SearchOptions::default(), output);
+
+	for line in std::io::stdin().lock().lines() {
+		let Ok(line) = line else {
+			break;
+		};
+
+		match Command::parse(&line) {
+			Some(Command::Quit) => break,
+			Some(command) => session.execute(command),
+			None => {}
+		}
+	}
+
+	// Stops a running search, also when the GUI just closed the input. Dropping the session then
+	// closes the channel and the printer ends once everything is written.
+	session.execute(Command::Quit);
+	drop(session);
+	_ = printer.join();
+}
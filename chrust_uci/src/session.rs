@@ -0,0 +1,281 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+		mpsc::Sender,
+	},
+	thread::{self, JoinHandle},
+	time::Duration,
+};
+
+use chrust_core::{
+	position::{Game, STARTING_FEN},
+	search::{Search, SearchInfo, SearchOptions, mate_in},
+	time_manager::SearchLimits,
+};
+
+const ENGINE_NAME: &str = "Chrust";
+const ENGINE_AUTHOR: &str = "the Chrust developers";
+/// No position has more legal moves, so more lines are never searched
+const MAX_MULTI_PV: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+	Uci,
+	IsReady,
+	UciNewGame,
+	/// Starting FEN and the moves played from it, in UCI notation
+	Position {
+		fen: String,
+		moves: Vec<String>,
+	},
+	SetOption {
+		name: String,
+		value: String,
+	},
+	/// `infinite` (and `ponder`) searches only answer after `stop`, even if they end before
+	Go {
+		limits: SearchLimits,
+		infinite: bool,
+	},
+	/// Ends the running search, which answers with the best move found so far
+	Stop,
+	/// The engine doesn't offer pondering, a GUI that ponders anyway gets its move right away
+	PonderHit,
+	Quit,
+}
+
+/// Answers the commands of a GUI. Everything meant for the GUI is sent line by line to `output`.
+/// Searches run on a worker thread, so commands like `stop` and `isready` are read while they run.
+pub struct Session {
+	game: Game,
+	/// `None` while the worker searches with it
+	search: Option<Search>,
+	worker: Option<JoinHandle<Search>>,
+	stop: Arc<AtomicBool>,
+	output: Sender<String>,
+}
+
+impl Command {
+	/// Parses a line sent by the GUI, `None` for empty lines and commands the engine ignores
+	pub fn parse(input: &str) -> Option<Command> {
+		let mut words = input.split_whitespace();
+		let first = words.next()?;
+		let rest: Vec<&str> = words.collect();
+
+		let command = match first {
+			"uci" => Command::Uci,
+			"isready" => Command::IsReady,
+			"ucinewgame" => Command::UciNewGame,
+			"position" => parse_position(&rest)?,
+			"setoption" => parse_setoption(&rest)?,
+			"go" => Command::Go {
+				limits: parse_go(&rest),
+				infinite: rest.iter().any(|&word| matches!(word, "infinite" | "ponder")),
+			},
+			"stop" => Command::Stop,
+			"ponderhit" => Command::PonderHit,
+			"quit" => Command::Quit,
+			_ => return None,
+		};
+
+		Some(command)
+	}
+}
+
+/// `position startpos|fen <fen> [moves <move>...]`
+fn parse_position(words: &[&str]) -> Option<Command> {
+	let moves_at = words.iter().position(|&word| word == "moves").unwrap_or(words.len());
+	let (setup, moves) = words.split_at(moves_at);
+
+	let fen = match setup.split_first()? {
+		(&"startpos", _) => STARTING_FEN.to_string(),
+		(&"fen", fen) => fen.join(" "),
+		_ => return None,
+	};
+
+	Some(Command::Position {
+		fen,
+		moves: moves.iter().skip(1).map(|mv| mv.to_string()).collect(),
+	})
+}
+
+/// `setoption name <name> [value <value>]`, both may contain spaces
+fn parse_setoption(words: &[&str]) -> Option<Command> {
+	let (&"name", words) = words.split_first()? else {
+		return None;
+	};
+	let value_at = words.iter().position(|&word| word == "value").unwrap_or(words.len());
+	let (name, value) = words.split_at(value_at);
+
+	Some(Command::SetOption {
+		name: name.join(" "),
+		value: value.iter().skip(1).copied().collect::<Vec<_>>().join(" "),
+	})
+}
+
+/// Reads the limits of `go`, times are in milliseconds. Anything else, like `infinite`, is skipped,
+/// without limits the search runs until it is stopped.
+pub fn parse_go(words: &[&str]) -> SearchLimits {
+	let mut limits = SearchLimits::default();
+	let mut words = words.iter().peekable();
+
+	while let Some(&name) = words.next() {
+		let value = words.peek().and_then(|value| value.parse::<u64>().ok());
+		if value.is_some() {
+			words.next();
+		}
+		let millis = value.map(Duration::from_millis);
+
+		match name {
+			"wtime" => limits.wtime = millis,
+			"btime" => limits.btime = millis,
+			"winc" => limits.winc = millis,
+			"binc" => limits.binc = millis,
+			"movetime" => limits.movetime = millis,
+			"movestogo" => limits.movestogo = value.and_then(|moves| u32::try_from(moves).ok()),
+			"depth" => limits.depth = value.and_then(|depth| u8::try_from(depth).ok()),
+			"nodes" => limits.nodes = value,
+			_ => {}
+		}
+	}
+
+	limits
+}
+
+impl Session {
+	pub fn new(search_options: SearchOptions, output: Sender<String>) -> Session {
+		let mut search = Search::new(search_options);
+		let info_output = output.clone();
+		search.set_info_callback(move |info| _ = info_output.send(info_line(info)));
+
+		Session {
+			game: Game::try_from_fen(STARTING_FEN).expect("the starting position is valid"),
+			stop: search.stop_handle(),
+			search: Some(search),
+			worker: None,
+			output,
+		}
+	}
+
+	pub fn execute(&mut self, command: Command) {
+		match command {
+			Command::Uci => {
+				self.send(format!("id name {}", ENGINE_NAME));
+				self.send(format!("id author {}", ENGINE_AUTHOR));
+				self.send(format!("option name MultiPV type spin default 1 min 1 max {}", MAX_MULTI_PV));
+				self.send("uciok".to_string());
+			}
+			Command::IsReady => self.send("readyok".to_string()),
+			Command::UciNewGame => self.search().new_game(),
+			Command::Position { fen, moves } => {
+				if let Err(message) = self.set_position(&fen, &moves) {
+					self.send(format!("info string {}", message));
+				}
+			}
+			Command::SetOption { name, value } => {
+				if let Err(message) = self.set_option(&name, &value) {
+					self.send(format!("info string {}", message));
+				}
+			}
+			Command::Go { limits, infinite } => self.go(limits, infinite),
+			Command::Stop | Command::PonderHit | Command::Quit => self.stop_search(),
+		}
+	}
+
+	fn set_position(&mut self, fen: &str, moves: &[String]) -> Result<(), String> {
+		let mut game = Game::try_from_fen(fen).map_err(|e| format!("Invalid FEN {}: {}", fen, e))?;
+		for mv in moves {
+			game.make_move_from_uci(mv).map_err(|e| format!("Illegal move {}: {}", mv, e))?;
+		}
+
+		self.game = game;
+		Ok(())
+	}
+
+	/// Option names are case insensitive
+	fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+		match name.to_lowercase().as_str() {
+			"multipv" => {
+				let lines = value.parse::<usize>().ok().filter(|lines| (1..=MAX_MULTI_PV).contains(lines)).ok_or(format!("Invalid MultiPV {}", value))?;
+				let search = self.search();
+				search.set_options(SearchOptions { multi_pv: lines, ..*search.options() });
+				Ok(())
+			}
+			_ => Err(format!("Unknown option {}", name)),
+		}
+	}
+
+	/// Starts searching the current position on the worker, the search derives its time from the
+	/// clock of the side to move and answers with `bestmove` when it ends, or on `stop` if `infinite`
+	fn go(&mut self, limits: SearchLimits, infinite: bool) {
+		let mut search = self.take_search();
+		let position = self.game.position;
+		// The last entry of the hash history is the current position
+		let history = self.game.hash_history[..self.game.hash_history.len().saturating_sub(1)].to_vec();
+		let output = self.output.clone();
+		let stop = Arc::clone(&self.stop);
+
+		stop.store(false, Ordering::Relaxed);
+		self.worker = Some(thread::spawn(move || {
+			let best_move = match search.search(&position, &history, &limits) {
+				Ok(result) => result.best_move.map(|mv| mv.to_uci()),
+				Err(e) => {
+					_ = output.send(format!("info string Search failed: {}", e));
+					None
+				}
+			};
+
+			// `stop_search` wakes the worker after setting the flag
+			while infinite && !stop.load(Ordering::Relaxed) {
+				thread::park();
+			}
+
+			// UCI has no way to say there is no move, `0000` is what GUIs expect instead
+			_ = output.send(format!("bestmove {}", best_move.as_deref().unwrap_or("0000")));
+			search
+		}));
+	}
+
+	/// Stops the running search and waits for it to answer
+	fn stop_search(&mut self) {
+		let Some(worker) = self.worker.take() else {
+			return;
+		};
+
+		self.stop.store(true, Ordering::Relaxed);
+		worker.thread().unpark();
+		self.search = Some(worker.join().expect("the search thread panicked"));
+	}
+
+	/// The search, once the running one is stopped
+	fn search(&mut self) -> &mut Search {
+		self.stop_search();
+		self.search.as_mut().expect("the search is back once the worker is joined")
+	}
+
+	/// Hands the search to a new worker, once the running one is stopped
+	fn take_search(&mut self) -> Search {
+		self.stop_search();
+		self.search.take().expect("the search is back once the worker is joined")
+	}
+
+	fn send(&self, line: String) {
+		// The receiver only goes away when the engine shuts down
+		_ = self.output.send(line);
+	}
+}
+
+/// `info depth <d> multipv <k> score cp <s>|mate <m> nodes <n> time <ms> pv <moves>`
+fn info_line(info: &SearchInfo) -> String {
+	let score = match mate_in(info.score) {
+		Some(moves) => format!("mate {}", moves),
+		None => format!("cp {}", info.score),
+	};
+	let pv: Vec<String> = info.pv.iter().map(|mv| mv.to_uci()).collect();
+
+	format!("info depth {} multipv {} score {} nodes {} time {} pv {}", info.depth, info.multi_pv, score, info.nodes, info.time.as_millis(), pv.join(" "))
+}
+
+#[cfg(test)]
+mod tests;
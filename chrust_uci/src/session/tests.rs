@@ -0,0 +1,300 @@
+use std::{
+	sync::mpsc::{self, Receiver},
+	thread,
+};
+
+use super::*;
+
+fn session() -> (Session, Receiver<String>) {
+	let (output, lines) = mpsc::channel();
+	let options = SearchOptions { hash_size_mb: 1, ..Default::default() };
+
+	(Session::new(options, output), lines)
+}
+
+fn run(session: &mut Session, lines: &Receiver<String>, input: &str) -> Vec<String> {
+	session.execute(Command::parse(input).unwrap());
+	lines.try_iter().collect()
+}
+
+/// Runs a `go` command and collects the lines up to its `bestmove`
+fn go(session: &mut Session, lines: &Receiver<String>, input: &str) -> Vec<String> {
+	session.execute(Command::parse(input).unwrap());
+	answer(lines)
+}
+
+fn answer(lines: &Receiver<String>) -> Vec<String> {
+	let mut answer = Vec::new();
+	while let Ok(line) = lines.recv_timeout(Duration::from_secs(60)) {
+		let done = line.starts_with("bestmove ");
+		answer.push(line);
+		if done {
+			break;
+		}
+	}
+	answer
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Command parsing tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn commands_are_parsed() {
+	assert_eq!(Command::parse(" isready "), Some(Command::IsReady));
+	assert_eq!(Command::parse("ucinewgame"), Some(Command::UciNewGame));
+	assert_eq!(Command::parse("quit"), Some(Command::Quit));
+	assert_eq!(
+		Command::parse("go infinite"),
+		Some(Command::Go {
+			limits: SearchLimits::default(),
+			infinite: true,
+		})
+	);
+	assert_eq!(
+		Command::parse("go ponder wtime 1000"),
+		Some(Command::Go {
+			limits: SearchLimits {
+				wtime: Some(Duration::from_secs(1)),
+				..Default::default()
+			},
+			infinite: true,
+		})
+	);
+	assert_eq!(Command::parse("debug on"), None);
+	assert_eq!(Command::parse("   "), None);
+}
+
+#[test]
+fn positions_are_parsed() {
+	assert_eq!(
+		Command::parse("position startpos moves e2e4 e7e5"),
+		Some(Command::Position {
+			fen: STARTING_FEN.to_string(),
+			moves: vec!["e2e4".to_string(), "e7e5".to_string()],
+		})
+	);
+	assert_eq!(
+		Command::parse("position fen 4k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+		Some(Command::Position {
+			fen: "4k3/8/8/8/8/8/8/4K3 w - - 0 1".to_string(),
+			moves: Vec::new(),
+		})
+	);
+	assert_eq!(Command::parse("position"), None);
+	assert_eq!(Command::parse("position e2e4"), None);
+}
+
+#[test]
+fn go_reads_the_clock_in_milliseconds() {
+	let limits = parse_go(&[
+		"wtime",
+		"60000",
+		"btime",
+		"55000",
+		"winc",
+		"1000",
+		"binc",
+		"500",
+		"movestogo",
+		"20",
+	]);
+
+	assert_eq!(
+		limits,
+		SearchLimits {
+			wtime: Some(Duration::from_secs(60)),
+			btime: Some(Duration::from_secs(55)),
+			winc: Some(Duration::from_secs(1)),
+			binc: Some(Duration::from_millis(500)),
+			movestogo: Some(20),
+			..Default::default()
+		}
+	);
+}
+
+#[test]
+fn go_reads_fixed_limits_and_skips_the_rest() {
+	assert_eq!(parse_go(&["movetime", "250"]).movetime, Some(Duration::from_millis(250)));
+	assert_eq!(parse_go(&["infinite", "depth", "6"]).depth, Some(6));
+	assert_eq!(parse_go(&["nodes", "1000", "ponder"]).nodes, Some(1000));
+	assert_eq!(parse_go(&["depth", "300"]), SearchLimits::default());
+	assert_eq!(parse_go(&["wtime", "soon", "btime", "100"]).btime, Some(Duration::from_millis(100)));
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Session tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn uci_and_isready_are_answered() {
+	let (mut session, lines) = session();
+
+	let uci = run(&mut session, &lines, "uci");
+	assert!(uci[0].starts_with("id name"));
+	assert_eq!(uci.last().map(String::as_str), Some("uciok"));
+	assert_eq!(run(&mut session, &lines, "isready"), vec!["readyok"]);
+}
+
+#[test]
+fn go_answers_with_a_legal_best_move() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "position startpos moves e2e4");
+	let answer = go(&mut session, &lines, "go depth 2");
+	let best_move = answer.last().and_then(|line| line.strip_prefix("bestmove ")).unwrap();
+
+	let mut game = Game::try_from_fen(STARTING_FEN).unwrap();
+	game.make_move_from_uci("e2e4").unwrap();
+	assert!(game.make_move_from_uci(best_move).is_ok());
+}
+
+#[test]
+fn go_on_the_clock_finds_the_mate() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+	assert_eq!(go(&mut session, &lines, "go wtime 1000 btime 1000").last().map(String::as_str), Some("bestmove a1a8"));
+}
+
+#[test]
+fn invalid_positions_keep_the_previous_one() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+	assert!(run(&mut session, &lines, "position startpos moves e2e5")[0].starts_with("info string Illegal move e2e5"));
+	assert!(run(&mut session, &lines, "position fen 9/8 w - - 0 1")[0].starts_with("info string Invalid FEN"));
+	assert_eq!(go(&mut session, &lines, "go depth 2").last().map(String::as_str), Some("bestmove a1a8"));
+}
+
+#[test]
+fn go_without_moves_answers_the_null_move() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "position fen R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+	assert_eq!(go(&mut session, &lines, "go depth 2").last().map(String::as_str), Some("bestmove 0000"));
+}
+
+#[test]
+fn stop_ends_a_running_search() {
+	let (mut session, lines) = session();
+
+	session.execute(Command::parse("go").unwrap());
+	thread::sleep(Duration::from_millis(50));
+	let answer = run(&mut session, &lines, "stop");
+
+	assert!(answer.last().unwrap().starts_with("bestmove "), "{:?}", answer);
+	assert!(run(&mut session, &lines, "stop").is_empty(), "only the running search answers");
+}
+
+#[test]
+fn isready_is_answered_while_searching() {
+	let (mut session, lines) = session();
+
+	session.execute(Command::parse("go").unwrap());
+	assert!(run(&mut session, &lines, "isready").contains(&"readyok".to_string()));
+	assert!(run(&mut session, &lines, "quit").last().unwrap().starts_with("bestmove "));
+}
+
+#[test]
+fn a_new_search_stops_the_running_one() {
+	let (mut session, lines) = session();
+
+	session.execute(Command::parse("go").unwrap());
+	session.execute(Command::parse("go depth 1").unwrap());
+
+	assert!(answer(&lines).last().unwrap().starts_with("bestmove "));
+	assert!(answer(&lines).last().unwrap().starts_with("bestmove "));
+	assert!(run(&mut session, &lines, "stop").is_empty());
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// MultiPV tests
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn setoption_is_parsed() {
+	assert_eq!(
+		Command::parse("setoption name MultiPV value 3"),
+		Some(Command::SetOption {
+			name: "MultiPV".to_string(),
+			value: "3".to_string(),
+		})
+	);
+	assert_eq!(
+		Command::parse("setoption name Clear Hash"),
+		Some(Command::SetOption {
+			name: "Clear Hash".to_string(),
+			value: String::new(),
+		})
+	);
+	assert_eq!(Command::parse("setoption MultiPV 3"), None);
+}
+
+#[test]
+fn uci_offers_the_multipv_option() {
+	let (mut session, lines) = session();
+
+	assert!(run(&mut session, &lines, "uci").iter().any(|line| line.starts_with("option name MultiPV type spin default 1")));
+}
+
+#[test]
+fn invalid_multipv_values_are_rejected() {
+	let (mut session, lines) = session();
+
+	assert_eq!(run(&mut session, &lines, "setoption name MultiPV value 0"), vec!["info string Invalid MultiPV 0"]);
+	assert_eq!(run(&mut session, &lines, "setoption name Ponder value true"), vec!["info string Unknown option Ponder"]);
+	assert!(run(&mut session, &lines, "setoption name multipv value 2").is_empty());
+}
+
+#[test]
+fn multipv_reports_every_line() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "setoption name MultiPV value 3");
+	let answer = go(&mut session, &lines, "go depth 3");
+	let last_iteration: Vec<&String> = answer.iter().filter(|line| line.starts_with("info depth 3 ")).collect();
+
+	assert_eq!(last_iteration.len(), 3);
+	for (index, line) in last_iteration.iter().enumerate() {
+		assert!(line.contains(&format!(" multipv {} score cp ", index + 1)), "{}", line);
+	}
+	assert!(answer.last().unwrap().starts_with("bestmove "));
+}
+
+#[test]
+fn infinite_analysis_waits_for_stop() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "setoption name MultiPV value 2");
+	run(&mut session, &lines, "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+	session.execute(Command::parse("go infinite").unwrap());
+	// The mate ends the search early, the answer still has to wait for the GUI
+	thread::sleep(Duration::from_millis(200));
+	let analysis: Vec<String> = lines.try_iter().collect();
+
+	assert!(analysis.iter().any(|line| line.contains(" multipv 2 ")));
+	assert!(!analysis.iter().any(|line| line.starts_with("bestmove ")), "{:?}", analysis);
+	assert_eq!(run(&mut session, &lines, "stop").last().map(String::as_str), Some("bestmove a1a8"));
+}
+
+#[test]
+fn ponderhit_answers_a_pondering_search() {
+	let (mut session, lines) = session();
+
+	session.execute(Command::parse("go ponder depth 1").unwrap());
+	thread::sleep(Duration::from_millis(50));
+	assert!(!lines.try_iter().any(|line| line.starts_with("bestmove ")));
+
+	assert!(run(&mut session, &lines, "ponderhit").last().unwrap().starts_with("bestmove "));
+}
+
+#[test]
+fn mates_are_reported_in_moves() {
+	let (mut session, lines) = session();
+
+	run(&mut session, &lines, "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+	let answer = go(&mut session, &lines, "go depth 2");
+
+	assert!(answer.iter().any(|line| line.starts_with("info depth 2 multipv 1 score mate 1 ") && line.ends_with(" pv a1a8")));
+}
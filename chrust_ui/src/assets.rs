@@ -38,6 +38,6 @@ pub fn load_assets(ctx: &egui::Context) -> Assets {
 	Assets { pieces }
 }
 
-pub(crate) fn get_texture(colored_piece: ColoredPiece) -> TextureHandle {
-
+pub(crate) fn get_texture(assets: &Assets, colored_piece: ColoredPiece) -> Option<&TextureHandle> {
+	assets.pieces.get(&(colored_piece.side, colored_piece.piece))
 }
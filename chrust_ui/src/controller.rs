@@ -1,10 +1,31 @@
+use std::time::Duration;
+
 use crate::state::{GameState, Overlay};
-use chrust_core::{Piece, Square, moves::make_move::MoveKind};
+use chrust_core::{
+	Piece, Side, Square,
+	errors::ChessError,
+	game_tree::{GameTree, NodeId},
+	moves::make_move::{Move, MoveKind},
+	position::Game,
+	time_manager::SearchLimits,
+};
+
+/// Thinking time of the engine when the game is played without a clock
+const ENGINE_MOVETIME: Duration = Duration::from_secs(1);
 
 pub enum UiEvent {
 	ClickSquare(Square),
 	ClickPromotionSquare(Piece),
 	ClickResetButton,
+	ToggleAnalysis,
+	GoToStart,
+	Back,
+	Forward,
+	GoToEnd,
+	GoToNode(NodeId),
+	PromoteVariation,
+	DeleteVariation,
+	EngineMove,
 }
 
 pub fn apply_ui_event(game_state: &mut GameState, ui_event: UiEvent) {
@@ -18,6 +39,130 @@ pub fn apply_ui_event(game_state: &mut GameState, ui_event: UiEvent) {
 		UiEvent::ClickSquare(square) => {
 			click_square(game_state, square);
 		}
+		UiEvent::ToggleAnalysis => {
+			toggle_analysis(game_state);
+		}
+		UiEvent::EngineMove => {
+			play_engine_move(game_state);
+		}
+		event => {
+			navigate(game_state, event);
+		}
+	}
+}
+
+pub fn toggle_analysis(game_state: &mut GameState) {
+	game_state.selected = None;
+	game_state.legal_moves.clear();
+	game_state.ui_state = None;
+
+	if game_state.analysis.take().is_some() {
+		return;
+	}
+
+	match GameTree::from_game(&game_state.game) {
+		Ok(tree) => game_state.analysis = Some(tree),
+		Err(e) => println!("Can't start the analysis: {}", e),
+	}
+}
+
+pub fn navigate(game_state: &mut GameState, ui_event: UiEvent) {
+	let result = match game_state.analysis.as_mut() {
+		Some(tree) => navigate_analysis(tree, ui_event),
+		None => navigate_game(&mut game_state.game, ui_event),
+	};
+	if let Err(e) = result {
+		println!("Can't navigate: {}", e);
+	}
+
+	game_state.selected = None;
+	game_state.legal_moves.clear();
+	game_state.ui_state = None;
+}
+
+fn navigate_analysis(tree: &mut GameTree, ui_event: UiEvent) -> Result<(), ChessError> {
+	match ui_event {
+		UiEvent::GoToStart => {
+			tree.go_to_start();
+			Ok(())
+		}
+		UiEvent::Back => {
+			tree.go_back();
+			Ok(())
+		}
+		UiEvent::Forward => {
+			tree.go_forward();
+			Ok(())
+		}
+		UiEvent::GoToEnd => {
+			tree.go_to_end();
+			Ok(())
+		}
+		UiEvent::GoToNode(node_id) => tree.go_to(node_id),
+		UiEvent::PromoteVariation => tree.promote_variation(tree.current()),
+		UiEvent::DeleteVariation => tree.delete_variation(tree.current()),
+		_ => Ok(()),
+	}
+}
+
+/// Takes back and replays moves of the game, the clock keeps the times recorded for each move
+fn navigate_game(game: &mut Game, ui_event: UiEvent) -> Result<(), ChessError> {
+	match ui_event {
+		UiEvent::GoToStart => game.goto_ply(0),
+		UiEvent::Back => game.undo_last_move(),
+		UiEvent::Forward => game.redo(),
+		UiEvent::GoToEnd => game.goto_ply(game.move_history.len() + game.redo_history.len()),
+		_ => Ok(()),
+	}
+}
+
+/// Lets the engine play the side to move, in the analysis while it is open. The search blocks the
+/// window until the time manager ends it.
+pub fn play_engine_move(game_state: &mut GameState) {
+	game_state.selected = None;
+	game_state.legal_moves.clear();
+	game_state.ui_state = None;
+
+	let limits = engine_limits(&game_state.game);
+	let result = match game_state.analysis.as_ref() {
+		Some(tree) => game_state.search.search(tree.position(), &[], &limits),
+		None => game_state.game.search_best_move(&mut game_state.search, &limits),
+	};
+
+	match result.map(|result| result.best_move) {
+		Ok(Some(mv)) => {
+			if let Err(e) = play_move(game_state, &mv) {
+				println!("Can't make the engine move: {}", e);
+			}
+		}
+		Ok(None) => println!("The engine has no move"),
+		Err(e) => println!("Search failed: {}", e),
+	}
+}
+
+/// Searches on the game clock, or for a fixed time without one
+fn engine_limits(game: &Game) -> SearchLimits {
+	match &game.clock {
+		Some(clock) => SearchLimits {
+			wtime: Some(clock.remaining(Side::White)),
+			btime: Some(clock.remaining(Side::Black)),
+			winc: Some(clock.increment(Side::White)),
+			binc: Some(clock.increment(Side::Black)),
+			movestogo: clock.moves_to_go(game.position.side_to_move),
+			..Default::default()
+		},
+		None => SearchLimits {
+			movetime: Some(ENGINE_MOVETIME),
+			..Default::default()
+		},
+	}
+}
+
+/// Plays the move in the analysis while it is open, in the game otherwise
+fn play_move(game_state: &mut GameState, mv: &Move) -> Result<(), ChessError> {
+	match game_state.analysis.as_mut() {
+		Some(tree) => tree.add_move(mv).map(|_| ()),
+		None => game_state.game.make_move(mv).map(|_| ()),
 	}
 }
 
@@ -39,19 +184,11 @@ pub fn click_promotion(game_state: &mut GameState, piece: Piece) {
 
 	mv.move_kind = MoveKind::Promotion { promotion_piece: piece };
 
-	match game_state.game.make_move(&mv) {
-		Ok(_undo) => {
-			game_state.legal_moves.clear();
-			game_state.selected = None;
-			return;
-		}
-		Err(_e) => {
-			println!("Error occured, please implement error handling");
-			game_state.selected = None;
-			game_state.legal_moves.clear();
-			return;
-		}
-	};
+	if let Err(e) = play_move(game_state, &mv) {
+		println!("Can't promote: {}", e);
+	}
+	game_state.selected = None;
+	game_state.legal_moves.clear();
 }
 
 pub fn click_square(game_state: &mut GameState, from_square: Square) {
@@ -59,8 +196,10 @@ pub fn click_square(game_state: &mut GameState, from_square: Square) {
 		return;
 	}
 
+	let mut position = *game_state.position();
+
 	if game_state.selected.is_none() {
-		let square_occupant = match game_state.game.position.board[from_square as usize] {
+		let square_occupant = match position.board[from_square as usize] {
 			Some(p) => p,
 			None => {
 				game_state.legal_moves.clear();
@@ -68,29 +207,27 @@ pub fn click_square(game_state: &mut GameState, from_square: Square) {
 			}
 		};
 
-		if square_occupant.side != game_state.game.position.side_to_move {
+		if square_occupant.side != position.side_to_move {
 			game_state.legal_moves.clear();
 			return;
 		}
 
-		match game_state.game.position.get_legal_moves(from_square, game_state.game.position.side_to_move) {
+		match position.get_legal_moves(from_square, position.side_to_move) {
 			Ok(moves) => {
 				game_state.selected = Some(from_square);
 				game_state.legal_moves = moves;
-				return;
 			}
-			Err(_x) => {
-				println!("Error occured, please implement error handling");
+			Err(x) => {
+				println!("Can't generate moves: {}", x);
 				game_state.selected = None;
 				game_state.legal_moves.clear();
-				return;
 			}
 		}
 	} else {
 		let Some(selected_square) = game_state.selected else {
 			return;
 		};
-		let clicked_occupant = game_state.game.position.board[from_square as usize];
+		let clicked_occupant = position.board[from_square as usize];
 
 		if from_square == selected_square {
 			game_state.legal_moves.clear();
@@ -99,22 +236,22 @@ pub fn click_square(game_state: &mut GameState, from_square: Square) {
 		}
 
 		// Make move
-		if let Some(chosen_move) = game_state.legal_moves.iter().find(|m| m.to_square == from_square) {
+		if let Some(chosen_move) = game_state.legal_moves.iter().find(|m| m.to_square == from_square).copied() {
 			if matches!(chosen_move.move_kind, MoveKind::Promotion { .. }) {
-				game_state.ui_state = Some(Overlay::Promotion { pending_move: chosen_move.clone() });
+				game_state.ui_state = Some(Overlay::Promotion { pending_move: chosen_move });
 				game_state.selected = None;
 				game_state.legal_moves.clear();
 				return;
 			}
 
-			match game_state.game.make_move(&chosen_move) {
-				Ok(_undo) => {
+			match play_move(game_state, &chosen_move) {
+				Ok(()) => {
 					game_state.legal_moves.clear();
 					game_state.selected = None;
 					return;
 				}
 				Err(e) => {
-					println!("Error occured, please implement error handling");
+					println!("Can't make the move: {}", e);
 					game_state.selected = None;
 					game_state.legal_moves.clear();
 					return;
@@ -122,20 +259,20 @@ pub fn click_square(game_state: &mut GameState, from_square: Square) {
 			}
 		}
 
-		if let Some(piece) = clicked_occupant {
-			if piece.side == game_state.game.position.side_to_move {
-				match game_state.game.position.get_legal_moves(from_square, game_state.game.position.side_to_move) {
-					Ok(moves) => {
-						game_state.selected = Some(from_square);
-						game_state.legal_moves = moves;
-						return;
-					}
-					Err(x) => {
-						println!("Error occured, please implement error handling");
-						game_state.selected = None;
-						game_state.legal_moves.clear();
-						return;
-					}
+		if let Some(piece) = clicked_occupant
+			&& piece.side == position.side_to_move
+		{
+			match position.get_legal_moves(from_square, position.side_to_move) {
+				Ok(moves) => {
+					game_state.selected = Some(from_square);
+					game_state.legal_moves = moves;
+					return;
+				}
+				Err(x) => {
+					println!("Can't generate moves: {}", x);
+					game_state.selected = None;
+					game_state.legal_moves.clear();
+					return;
 				}
 			}
 		}
@@ -4,13 +4,15 @@ mod input;
 mod layout;
 mod renderer;
 mod helper;
+mod sidebar;
 mod state;
 
 use crate::assets::Assets;
 use crate::renderer::render_board;
+use crate::sidebar::render_sidebar;
 use crate::state::{GameState, InputState};
 use crate::{assets::load_assets, controller::apply_ui_event, input::route_click, layout::TEST_FEN_STRING};
-use chrust_core::position::Game;
+use chrust_core::{position::Game, search::{Search, SearchOptions}};
 use macroquad::file::set_pc_assets_folder;
 use macroquad::prelude::coroutines::wait_seconds;
 use macroquad::prelude::*;
@@ -27,6 +29,8 @@ async fn main() {
 				return;
 			}
 		},
+		analysis: None,
+		search: Search::new(SearchOptions::default()),
 		assets: None,
 		selected: None,
 		legal_moves: Vec::new(),
@@ -53,11 +57,13 @@ async fn main() {
 				game_state.assets = Some(load_assets(egui_ctx))
 			}
 
-			egui::SidePanel::right("sidebar").show(egui_ctx, |ui| {
-				ui.label("Overview!");
-			});
+			if let Some(ui_event) = render_sidebar(egui_ctx, &game_state) {
+				apply_ui_event(&mut game_state, ui_event);
+			}
 
-			render_board(egui_ctx, &mut game_state);
+			if let Some(ui_event) = render_board(egui_ctx, &game_state) {
+				apply_ui_event(&mut game_state, ui_event);
+			}
 
 		});
 
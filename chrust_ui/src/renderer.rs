@@ -1,10 +1,12 @@
-use crate::{helper::position_to_square, layout::{BOARD_BLACK_COLOR, BOARD_HIGHLIGHTED_COLOR, BOARD_WHITE_COLOR}, state::GameState};
+use crate::{assets::get_texture, controller::UiEvent, helper::position_to_square, layout::{BOARD_BLACK_COLOR, BOARD_HIGHLIGHTED_COLOR, BOARD_WHITE_COLOR}, state::GameState};
 use chrust_core::{helper::{square}};
 use core::f32;
 use std::usize;
 use egui::{Context, Pos2, Rect};
 
-pub(crate) fn render_board(egui_ctx: &Context, game_state: &mut GameState) {
+pub(crate) fn render_board(egui_ctx: &Context, game_state: &GameState) -> Option<UiEvent> {
+	let mut ui_event = None;
+
 	egui::CentralPanel::default().show(egui_ctx, |ui| {
 		let board = ui.available_rect_before_wrap();
 		let board_size = board.width().min(board.height());
@@ -17,7 +19,9 @@ pub(crate) fn render_board(egui_ctx: &Context, game_state: &mut GameState) {
 			
 			let (file, rank) = position_to_square(mouse_position, board, square_side);
 
-			game_state.selected = Some(square(file as u8, rank as u8));
+			if (0.0..8.0).contains(&file) && (0.0..8.0).contains(&rank) {
+				ui_event = Some(UiEvent::ClickSquare(square(file as u8, rank as u8)));
+			}
 		}
 
 		for rank in (0..8).rev() {
@@ -46,13 +50,17 @@ pub(crate) fn render_board(egui_ctx: &Context, game_state: &mut GameState) {
 
 				let _ = ui.painter().rect_filled(square_rect, 0, color);
 
-				let piece = match game_state.game.position.board[square as usize] {
+				let piece = match game_state.position().board[square as usize] {
 					Some(x) => x,
 					None => continue,
 				};
 
-
+				if let Some(texture) = game_state.assets.as_ref().and_then(|assets| get_texture(assets, piece)) {
+					egui::Image::new(egui::load::SizedTexture::from_handle(texture)).paint_at(ui, square_rect);
+				}
 			}
 		}
 	});
+
+	ui_event
 }
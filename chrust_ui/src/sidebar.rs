@@ -0,0 +1,87 @@
+use crate::{controller::UiEvent, state::GameState};
+use chrust_core::game_tree::{GameTree, NodeId, ROOT_NODE};
+use egui::{Context, Ui};
+
+pub(crate) fn render_sidebar(egui_ctx: &Context, game_state: &GameState) -> Option<UiEvent> {
+	let mut ui_event = None;
+
+	egui::SidePanel::right("sidebar").show(egui_ctx, |ui| {
+		let toggle_label = match game_state.analysis {
+			Some(_) => "Back to game",
+			None => "Analyse",
+		};
+		if ui.button(toggle_label).clicked() {
+			ui_event = Some(UiEvent::ToggleAnalysis);
+		}
+		if ui.button("Engine move").clicked() {
+			ui_event = Some(UiEvent::EngineMove);
+		}
+
+		// Moves through the analysis while it is open, takes back and replays game moves otherwise
+		ui.horizontal(|ui| {
+			for (label, event) in [("|<", UiEvent::GoToStart), ("<", UiEvent::Back), (">", UiEvent::Forward), (">|", UiEvent::GoToEnd)] {
+				if ui.button(label).clicked() {
+					ui_event = Some(event);
+				}
+			}
+		});
+
+		let Some(tree) = &game_state.analysis else {
+			return;
+		};
+
+		ui.separator();
+		ui.horizontal_wrapped(|ui| {
+			render_line(ui, tree, ROOT_NODE, true, &mut ui_event);
+		});
+		ui.separator();
+
+		let on_move = tree.node(tree.current()).ok().and_then(|node| node.parent).is_some();
+		ui.add_enabled_ui(on_move, |ui| {
+			ui.horizontal(|ui| {
+				if ui.button("Promote").clicked() {
+					ui_event = Some(UiEvent::PromoteVariation);
+				}
+				if ui.button("Delete").clicked() {
+					ui_event = Some(UiEvent::DeleteVariation);
+				}
+			});
+		});
+	});
+
+	ui_event
+}
+
+/// Moves after `parent` in movetext order, variations in parentheses right after the move they replace
+fn render_line(ui: &mut Ui, tree: &GameTree, parent: NodeId, force_number: bool, ui_event: &mut Option<UiEvent>) {
+	let mut parent = parent;
+	let mut force_number = force_number;
+
+	while let Ok(node) = tree.node(parent) {
+		let Some((&mainline, variations)) = node.children.split_first() else {
+			return;
+		};
+
+		render_move(ui, tree, mainline, force_number, ui_event);
+		force_number = !variations.is_empty();
+
+		for &variation in variations {
+			ui.label("(");
+			render_move(ui, tree, variation, true, ui_event);
+			render_line(ui, tree, variation, false, ui_event);
+			ui.label(")");
+		}
+
+		parent = mainline;
+	}
+}
+
+fn render_move(ui: &mut Ui, tree: &GameTree, node_id: NodeId, force_number: bool, ui_event: &mut Option<UiEvent>) {
+	let Ok(text) = tree.numbered_san(node_id, force_number) else {
+		return;
+	};
+
+	if ui.selectable_label(tree.current() == node_id, text).clicked() {
+		*ui_event = Some(UiEvent::GoToNode(node_id));
+	}
+}
@@ -1,14 +1,34 @@
 use crate::assets::Assets;
-use chrust_core::{Square, moves::make_move::Move, position::Game};
+use chrust_core::{
+	Square,
+	game_tree::GameTree,
+	moves::make_move::Move,
+	position::{Game, Position},
+	search::Search,
+};
 
 pub struct GameState {
 	pub game: Game,
+	/// Variations explored from the game, the board shows and edits these while set
+	pub analysis: Option<GameTree>,
+	/// Plays the computer moves
+	pub search: Search,
 	pub assets: Option<Assets>,
 	pub selected: Option<Square>,
 	pub legal_moves: Vec<Move>,
 	pub ui_state: Option<Overlay>,
 }
 
+impl GameState {
+	/// The position on the board
+	pub fn position(&self) -> &Position {
+		match &self.analysis {
+			Some(tree) => tree.position(),
+			None => &self.game.position,
+		}
+	}
+}
+
 pub struct InputState {
 	pub mouse_x: f32,
 	pub mouse_y: f32,